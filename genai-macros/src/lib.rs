@@ -0,0 +1,67 @@
+//! Derive and attribute macros for `genai` (re-exported by `genai` with the `macros` feature).
+//!
+//! - `#[derive(genai::GenaiSchema)]` implements `genai::chat::GenaiSchema` (the JSON Schema of the type).
+//! - `#[genai::tool]` on an async function generates its `Tool` definition and registry handler.
+
+// region:    --- Modules
+
+mod schema_derive;
+mod support;
+mod tool_attr;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, ItemFn, parse_macro_input};
+
+// endregion: --- Modules
+
+/// Implements `genai::chat::GenaiSchema` for a struct or an enum.
+///
+/// - The doc comments are the descriptions (of the type, fields, and variants).
+/// - The `Option` and `#[serde(default)]` fields are not required, the `Option` ones are nullable.
+/// - Unit-only enums are string enums, the others follow the serde representation
+///   (externally tagged, `#[serde(tag = "...")]`, or `#[serde(untagged)]`).
+/// - The serde `rename`, `rename_all`, and `skip` attributes are applied.
+///
+/// ```ignore
+/// /// Weather request.
+/// #[derive(serde::Deserialize, genai::GenaiSchema)]
+/// struct WeatherArgs {
+///     /// The city name.
+///     city: String,
+///     unit: Option<Unit>,
+/// }
+/// ```
+#[proc_macro_derive(GenaiSchema, attributes(serde))]
+pub fn derive_genai_schema(input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as DeriveInput);
+	schema_derive::derive_genai_schema(input)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
+
+/// Generates the `Tool` definition and the registry handler of an async function,
+/// in a module of the same name (next to the function):
+/// - `<fn_name>::tool()`: the `Tool`, with the function doc comment as description, and the arguments schema
+///   (from their `GenaiSchema`, the `Option` ones not required).
+/// - `<fn_name>::handler`: the async handler for `ToolRegistry::with_tool`, deserializing the arguments
+///   and serializing the output.
+///
+/// The function must be async, take deserializable `GenaiSchema` arguments, and return a `Result<T, E>`
+/// with `T: Serialize` and `E: Into<BoxError>`. The tool name can be set with `#[genai::tool(name = "...")]`.
+///
+/// ```ignore
+/// /// Returns the current weather of a city.
+/// #[genai::tool]
+/// async fn get_weather(city: String, unit: Option<Unit>) -> Result<Weather, genai::BoxError> {
+///     // ...
+/// }
+///
+/// let registry = ToolRegistry::new().with_tool(get_weather::tool(), get_weather::handler);
+/// ```
+#[proc_macro_attribute]
+pub fn tool(args: TokenStream, item: TokenStream) -> TokenStream {
+	let item_fn = parse_macro_input!(item as ItemFn);
+	tool_attr::expand_tool(args.into(), item_fn)
+		.unwrap_or_else(syn::Error::into_compile_error)
+		.into()
+}
@@ -0,0 +1,211 @@
+//! `#[derive(GenaiSchema)]` implementation.
+
+use crate::support::{RenameRule, SerdeAttrs, doc_description, is_option, option_str, serde_attrs, unraw};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DataEnum, DeriveInput, Fields, parse_quote};
+
+pub fn derive_genai_schema(input: DeriveInput) -> syn::Result<TokenStream> {
+	let container = serde_attrs(&input.attrs)?;
+	let description = doc_description(&input.attrs);
+
+	let schema = match &input.data {
+		Data::Struct(data) => fields_schema(
+			&data.fields,
+			container.rename_all,
+			container.default,
+			description.as_deref(),
+		)?,
+		Data::Enum(data) => enum_schema(data, &container, description.as_deref())?,
+		Data::Union(_) => {
+			return Err(syn::Error::new_spanned(
+				&input.ident,
+				"GenaiSchema: unions are not supported",
+			));
+		}
+	};
+
+	// -- Add the `GenaiSchema` bound to the type parameters
+	let mut generics = input.generics.clone();
+	for param in generics.type_params_mut() {
+		param.bounds.push(parse_quote!(::genai::chat::GenaiSchema));
+	}
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	let ident = &input.ident;
+
+	Ok(quote! {
+		impl #impl_generics ::genai::chat::GenaiSchema for #ident #ty_generics #where_clause {
+			fn genai_schema() -> ::genai::__private::serde_json::Value {
+				#schema
+			}
+		}
+	})
+}
+
+// region:    --- Support
+
+/// Returns the schema expression of the fields (of a struct, or an enum variant).
+fn fields_schema(
+	fields: &Fields,
+	rename_all: Option<RenameRule>,
+	all_default: bool,
+	description: Option<&str>,
+) -> syn::Result<TokenStream> {
+	let description = option_str(description);
+
+	let schema = match fields {
+		Fields::Named(named) => {
+			let mut properties: Vec<TokenStream> = Vec::new();
+			for field in &named.named {
+				let attrs = serde_attrs(&field.attrs)?;
+				if attrs.skip {
+					continue;
+				}
+				let field_name = field.ident.as_ref().map(unraw).unwrap_or_default();
+				let name = match (attrs.rename, rename_all) {
+					(Some(rename), _) => rename,
+					(None, Some(rule)) => rule.apply_to_field(&field_name),
+					(None, None) => field_name,
+				};
+				let ty = &field.ty;
+				let field_description = option_str(doc_description(&field.attrs).as_deref());
+				let required = !is_option(ty) && !attrs.default && !all_default;
+
+				properties.push(quote! {
+					(
+						#name,
+						::genai::__private::schema_with_description(
+							<#ty as ::genai::chat::GenaiSchema>::genai_schema(),
+							#field_description,
+						),
+						#required,
+					)
+				});
+			}
+			quote! { ::genai::__private::object_schema(#description, ::std::vec![#(#properties),*]) }
+		}
+		Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+			let ty = &unnamed.unnamed[0].ty;
+			quote! {
+				::genai::__private::schema_with_description(<#ty as ::genai::chat::GenaiSchema>::genai_schema(), #description)
+			}
+		}
+		Fields::Unnamed(unnamed) => {
+			let tys = unnamed.unnamed.iter().map(|field| &field.ty);
+			quote! {
+				::genai::__private::tuple_schema(
+					#description,
+					::std::vec![#(<#tys as ::genai::chat::GenaiSchema>::genai_schema()),*],
+				)
+			}
+		}
+		Fields::Unit => quote! {
+			::genai::__private::schema_with_description(<() as ::genai::chat::GenaiSchema>::genai_schema(), #description)
+		},
+	};
+
+	Ok(schema)
+}
+
+/// Returns the schema expression of the enum, following its serde representation.
+fn enum_schema(data: &DataEnum, container: &SerdeAttrs, description: Option<&str>) -> syn::Result<TokenStream> {
+	let description_tokens = option_str(description);
+
+	// -- Collect the variants (name, attributes, fields)
+	let mut variants = Vec::new();
+	for variant in &data.variants {
+		let attrs = serde_attrs(&variant.attrs)?;
+		if attrs.skip {
+			continue;
+		}
+		let variant_name = unraw(&variant.ident);
+		let name = match (&attrs.rename, container.rename_all) {
+			(Some(rename), _) => rename.clone(),
+			(None, Some(rule)) => rule.apply_to_variant(&variant_name),
+			(None, None) => variant_name,
+		};
+		let variant_description = doc_description(&variant.attrs);
+		variants.push((name, attrs, variant_description, &variant.fields));
+	}
+
+	// -- Unit-only enum, as a string enum
+	let all_unit = variants.iter().all(|(_, _, _, fields)| matches!(fields, Fields::Unit));
+	if all_unit && container.tag.is_none() && !container.untagged {
+		let names = variants.iter().map(|(name, ..)| name);
+		return Ok(quote! {
+			::genai::__private::string_enum_schema(#description_tokens, &[#(#names),*])
+		});
+	}
+
+	// -- Untagged, any of the variant contents
+	if container.untagged {
+		let mut schemas = Vec::new();
+		for (_, attrs, variant_description, fields) in &variants {
+			schemas.push(fields_schema(
+				fields,
+				attrs.rename_all,
+				false,
+				variant_description.as_deref(),
+			)?);
+		}
+		return Ok(quote! {
+			::genai::__private::variants_schema(#description_tokens, ::std::vec![#(#schemas),*], false)
+		});
+	}
+
+	// -- Internally tagged, one of the content objects with the tag property
+	if let Some(tag) = &container.tag {
+		let mut schemas = Vec::new();
+		for (name, attrs, variant_description, fields) in &variants {
+			let content = match fields {
+				Fields::Unit => quote! { ::core::option::Option::None },
+				Fields::Unnamed(unnamed) if unnamed.unnamed.len() > 1 => {
+					return Err(syn::Error::new_spanned(
+						unnamed,
+						"GenaiSchema: tuple variants are not supported with a serde tag",
+					));
+				}
+				_ => {
+					let schema = fields_schema(fields, attrs.rename_all, false, None)?;
+					quote! { ::core::option::Option::Some(#schema) }
+				}
+			};
+			let variant_description = option_str(variant_description.as_deref());
+			schemas.push(quote! {
+				::genai::__private::internal_variant_schema(#variant_description, #tag, #name, #content)
+			});
+		}
+		return Ok(quote! {
+			::genai::__private::variants_schema(#description_tokens, ::std::vec![#(#schemas),*], true)
+		});
+	}
+
+	// -- Externally tagged (serde default), the unit variants as strings, the others as `{"<name>": <content>}`
+	let mut schemas = Vec::new();
+	let unit_names: Vec<&String> = variants
+		.iter()
+		.filter(|(.., fields)| matches!(fields, Fields::Unit))
+		.map(|(name, ..)| name)
+		.collect();
+	if !unit_names.is_empty() {
+		schemas.push(quote! {
+			::genai::__private::string_enum_schema(::core::option::Option::None, &[#(#unit_names),*])
+		});
+	}
+	for (name, attrs, variant_description, fields) in &variants {
+		if matches!(fields, Fields::Unit) {
+			continue;
+		}
+		let content = fields_schema(fields, attrs.rename_all, false, None)?;
+		let variant_description = option_str(variant_description.as_deref());
+		schemas.push(quote! {
+			::genai::__private::external_variant_schema(#variant_description, #name, #content)
+		});
+	}
+
+	Ok(quote! {
+		::genai::__private::variants_schema(#description_tokens, ::std::vec![#(#schemas),*], true)
+	})
+}
+
+// endregion: --- Support
@@ -0,0 +1,225 @@
+//! Attribute parsing support (doc comments and serde attributes).
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Expr, ExprLit, GenericArgument, Lit, LitStr, Meta, PathArguments, Token, Type};
+
+// region:    --- Doc Comments
+
+/// Returns the doc comment text (lines trimmed and joined), if any.
+pub fn doc_description(attrs: &[Attribute]) -> Option<String> {
+	let lines: Vec<String> = attrs
+		.iter()
+		.filter(|attr| attr.path().is_ident("doc"))
+		.filter_map(|attr| match &attr.meta {
+			Meta::NameValue(name_value) => match &name_value.value {
+				Expr::Lit(ExprLit {
+					lit: Lit::Str(text), ..
+				}) => Some(text.value().trim().to_string()),
+				_ => None,
+			},
+			_ => None,
+		})
+		.collect();
+
+	let description = lines.join("\n").trim().to_string();
+	(!description.is_empty()).then_some(description)
+}
+
+// endregion: --- Doc Comments
+
+// region:    --- Serde Attributes
+
+/// The serde attributes affecting the schema.
+#[derive(Default)]
+pub struct SerdeAttrs {
+	pub rename: Option<String>,
+	pub rename_all: Option<RenameRule>,
+	pub tag: Option<String>,
+	pub untagged: bool,
+	pub default: bool,
+	pub skip: bool,
+}
+
+/// Parses the `#[serde(...)]` attributes (the unsupported representations are errors, the others are ignored).
+pub fn serde_attrs(attrs: &[Attribute]) -> syn::Result<SerdeAttrs> {
+	let mut serde_attrs = SerdeAttrs::default();
+
+	for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+		attr.parse_nested_meta(|meta| {
+			let path = &meta.path;
+			if path.is_ident("rename") {
+				serde_attrs.rename = Some(parse_deserialize_name(&meta)?);
+			} else if path.is_ident("rename_all") {
+				let rule = parse_deserialize_name(&meta)?;
+				serde_attrs.rename_all = Some(
+					RenameRule::from_name(&rule)
+						.ok_or_else(|| meta.error(format!("GenaiSchema: unknown serde rename_all rule '{rule}'")))?,
+				);
+			} else if path.is_ident("tag") {
+				serde_attrs.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+			} else if path.is_ident("untagged") {
+				serde_attrs.untagged = true;
+			} else if path.is_ident("default") {
+				serde_attrs.default = true;
+				skip_meta_value(&meta)?;
+			} else if path.is_ident("skip") || path.is_ident("skip_deserializing") {
+				serde_attrs.skip = true;
+			} else if path.is_ident("content") || path.is_ident("flatten") {
+				return Err(meta.error("GenaiSchema: this serde attribute is not supported"));
+			} else {
+				skip_meta_value(&meta)?;
+			}
+			Ok(())
+		})?;
+	}
+
+	Ok(serde_attrs)
+}
+
+/// Parses `name = "..."`, or `name(deserialize = "...")` (the serialize name is ignored).
+fn parse_deserialize_name(meta: &syn::meta::ParseNestedMeta) -> syn::Result<String> {
+	if meta.input.peek(Token![=]) {
+		return Ok(meta.value()?.parse::<LitStr>()?.value());
+	}
+
+	let mut name = None;
+	meta.parse_nested_meta(|nested| {
+		let value = nested.value()?.parse::<LitStr>()?.value();
+		if nested.path.is_ident("deserialize") {
+			name = Some(value);
+		}
+		Ok(())
+	})?;
+	name.ok_or_else(|| meta.error("GenaiSchema: missing the deserialize name"))
+}
+
+/// Skips the value of an ignored attribute (`= value` or `(...)`).
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+	if meta.input.peek(Token![=]) {
+		meta.value()?.parse::<Expr>()?;
+	} else if meta.input.peek(syn::token::Paren) {
+		meta.parse_nested_meta(|nested| skip_meta_value(&nested))?;
+	}
+	Ok(())
+}
+
+// endregion: --- Serde Attributes
+
+// region:    --- Rename Rules
+
+/// The serde `rename_all` rules.
+#[derive(Clone, Copy)]
+pub enum RenameRule {
+	Lower,
+	Upper,
+	Pascal,
+	Camel,
+	Snake,
+	ScreamingSnake,
+	Kebab,
+	ScreamingKebab,
+}
+
+impl RenameRule {
+	fn from_name(name: &str) -> Option<Self> {
+		Some(match name {
+			"lowercase" => Self::Lower,
+			"UPPERCASE" => Self::Upper,
+			"PascalCase" => Self::Pascal,
+			"camelCase" => Self::Camel,
+			"snake_case" => Self::Snake,
+			"SCREAMING_SNAKE_CASE" => Self::ScreamingSnake,
+			"kebab-case" => Self::Kebab,
+			"SCREAMING-KEBAB-CASE" => Self::ScreamingKebab,
+			_ => return None,
+		})
+	}
+
+	/// Applies the rule to a variant name (PascalCase), as serde does.
+	pub fn apply_to_variant(self, variant: &str) -> String {
+		match self {
+			Self::Pascal => variant.to_string(),
+			Self::Lower => variant.to_ascii_lowercase(),
+			Self::Upper => variant.to_ascii_uppercase(),
+			Self::Camel => {
+				let mut chars = variant.chars();
+				chars
+					.next()
+					.map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+					.unwrap_or_default()
+			}
+			Self::Snake | Self::ScreamingSnake | Self::Kebab | Self::ScreamingKebab => {
+				let mut snake = String::new();
+				for (idx, ch) in variant.char_indices() {
+					if idx > 0 && ch.is_uppercase() {
+						snake.push('_');
+					}
+					snake.push(ch.to_ascii_lowercase());
+				}
+				self.apply_to_field(&snake)
+			}
+		}
+	}
+
+	/// Applies the rule to a field name (snake_case), as serde does.
+	pub fn apply_to_field(self, field: &str) -> String {
+		match self {
+			Self::Lower | Self::Snake => field.to_string(),
+			Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+			Self::Kebab => field.replace('_', "-"),
+			Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+			Self::Pascal | Self::Camel => {
+				let mut pascal = String::new();
+				let mut capitalize = matches!(self, Self::Pascal);
+				for ch in field.chars() {
+					if ch == '_' {
+						capitalize = true;
+					} else if capitalize {
+						pascal.push(ch.to_ascii_uppercase());
+						capitalize = false;
+					} else {
+						pascal.push(ch);
+					}
+				}
+				pascal
+			}
+		}
+	}
+}
+
+// endregion: --- Rename Rules
+
+// region:    --- Type Support
+
+/// Returns true if the type is an `Option<T>` (by its last path segment).
+pub fn is_option(ty: &Type) -> bool {
+	let Type::Path(type_path) = ty else {
+		return false;
+	};
+	type_path.path.segments.last().is_some_and(|segment| {
+		segment.ident == "Option"
+			&& matches!(&segment.arguments, PathArguments::AngleBracketed(args)
+				if matches!(args.args.first(), Some(GenericArgument::Type(_))))
+	})
+}
+
+/// Returns the identifier name without the raw prefix (e.g., `r#type` -> `type`).
+pub fn unraw(ident: &syn::Ident) -> String {
+	let name = ident.to_string();
+	name.strip_prefix("r#").map(String::from).unwrap_or(name)
+}
+
+// endregion: --- Type Support
+
+// region:    --- Tokens Support
+
+/// Returns the `Option<&str>` expression.
+pub fn option_str(value: Option<&str>) -> TokenStream {
+	match value {
+		Some(value) => quote! { ::core::option::Option::Some(#value) },
+		None => quote! { ::core::option::Option::None },
+	}
+}
+
+// endregion: --- Tokens Support
@@ -0,0 +1,104 @@
+//! `#[genai::tool]` implementation.
+
+use crate::support::{doc_description, is_option, unraw};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{FnArg, ItemFn, LitStr, Pat};
+
+pub fn expand_tool(args: TokenStream, item_fn: ItemFn) -> syn::Result<TokenStream> {
+	// -- Parse the `name = "..."` and `description = "..."` arguments
+	let mut name: Option<String> = None;
+	let mut description: Option<String> = None;
+	let args_parser = syn::meta::parser(|meta| {
+		if meta.path.is_ident("name") {
+			name = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else if meta.path.is_ident("description") {
+			description = Some(meta.value()?.parse::<LitStr>()?.value());
+		} else {
+			return Err(meta.error("genai::tool: unsupported argument (expected `name` or `description`)"));
+		}
+		Ok(())
+	});
+	syn::parse::Parser::parse2(args_parser, args)?;
+
+	// -- Validate the function
+	let sig = &item_fn.sig;
+	if sig.asyncness.is_none() {
+		return Err(syn::Error::new_spanned(
+			sig.fn_token,
+			"genai::tool: the function must be async",
+		));
+	}
+	if !sig.generics.params.is_empty() {
+		return Err(syn::Error::new_spanned(
+			&sig.generics,
+			"genai::tool: generic functions are not supported",
+		));
+	}
+
+	// -- Collect the arguments (ident, name, type)
+	let mut arg_idents = Vec::new();
+	let mut properties = Vec::new();
+	let mut arg_reads = Vec::new();
+	for input in &sig.inputs {
+		let pat_type = match input {
+			FnArg::Typed(pat_type) => pat_type,
+			FnArg::Receiver(receiver) => {
+				return Err(syn::Error::new_spanned(
+					receiver,
+					"genai::tool: methods are not supported",
+				));
+			}
+		};
+		let Pat::Ident(pat_ident) = &*pat_type.pat else {
+			return Err(syn::Error::new_spanned(
+				&pat_type.pat,
+				"genai::tool: the arguments must be identifiers",
+			));
+		};
+		let ident = &pat_ident.ident;
+		let arg_name = unraw(ident);
+		let ty = &*pat_type.ty;
+		let required = !is_option(ty);
+
+		properties.push(quote! {
+			(#arg_name, <#ty as ::genai::chat::GenaiSchema>::genai_schema(), #required)
+		});
+		arg_reads.push(quote! {
+			let #ident: #ty = ::genai::__private::tool_argument(&arguments, #arg_name)?;
+		});
+		arg_idents.push(ident);
+	}
+
+	// -- Generate the tool module
+	let fn_ident = &sig.ident;
+	let vis = &item_fn.vis;
+	let tool_name = name.unwrap_or_else(|| unraw(fn_ident));
+	let description = description.or_else(|| doc_description(&item_fn.attrs));
+	let with_description = description.map(|description| quote! { .with_description(#description) });
+	let mod_doc = format!("The `{tool_name}` tool definition and registry handler (generated by `#[genai::tool]`).");
+
+	Ok(quote! {
+		#item_fn
+
+		#[doc = #mod_doc]
+		#vis mod #fn_ident {
+			#[allow(unused_imports)]
+			use super::*;
+
+			/// Returns the `Tool`, with the arguments schema.
+			pub fn tool() -> ::genai::chat::Tool {
+				let schema = ::genai::__private::object_schema(::core::option::Option::None, ::std::vec![#(#properties),*]);
+				::genai::chat::Tool::new(#tool_name) #with_description .with_schema(schema)
+			}
+
+			/// Calls the function with the tool call arguments, and returns its serialized output.
+			pub async fn handler(
+				arguments: ::genai::__private::serde_json::Value,
+			) -> ::core::result::Result<::genai::__private::serde_json::Value, ::genai::BoxError> {
+				#(#arg_reads)*
+				::genai::__private::IntoToolOutput::into_tool_output(super::#fn_ident(#(#arg_idents),*).await)
+			}
+		}
+	})
+}
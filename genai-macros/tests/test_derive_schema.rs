@@ -0,0 +1,157 @@
+//! `#[derive(GenaiSchema)]` tests (generated schemas).
+
+use genai::GenaiSchema;
+use genai::chat::GenaiSchema as _;
+use serde::Deserialize;
+use serde_json::json;
+
+type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+// region:    --- Types
+
+/// The temperature unit.
+#[allow(unused)]
+#[derive(Deserialize, GenaiSchema)]
+enum Unit {
+	#[serde(rename = "C")]
+	Celsius,
+	#[serde(rename = "F")]
+	Fahrenheit,
+}
+
+/// Weather request.
+#[allow(unused)]
+#[derive(Deserialize, GenaiSchema)]
+#[serde(rename_all = "camelCase")]
+struct WeatherArgs {
+	/// The city name.
+	city_name: String,
+	unit: Option<Unit>,
+	#[serde(default)]
+	days: u32,
+	#[serde(skip)]
+	internal: bool,
+}
+
+#[allow(unused)]
+#[derive(Deserialize, GenaiSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Shape {
+	/// A circle.
+	Circle {
+		radius: f64,
+	},
+	Empty,
+}
+
+#[allow(unused)]
+#[derive(Deserialize, GenaiSchema)]
+enum Command {
+	Stop,
+	Move(i32, i32),
+	Say(String),
+}
+
+#[allow(unused)]
+#[derive(Deserialize, GenaiSchema)]
+#[serde(untagged)]
+enum Id {
+	Num(u64),
+	Name(String),
+}
+
+#[allow(unused)]
+#[derive(Deserialize, GenaiSchema)]
+struct Page<T> {
+	items: Vec<T>,
+}
+
+// endregion: --- Types
+
+#[test]
+fn test_derive_schema_struct_ok() -> Result<()> {
+	// -- Exec
+	let schema = WeatherArgs::genai_schema();
+
+	// -- Check
+	let expected = json!({
+		"type": "object",
+		"description": "Weather request.",
+		"properties": {
+			"cityName": {"type": "string", "description": "The city name."},
+			"unit": {
+				"type": ["string", "null"],
+				"enum": ["C", "F", null],
+				"description": "The temperature unit."
+			},
+			"days": {"type": "integer", "minimum": 0}
+		},
+		"required": ["cityName"]
+	});
+	assert_eq!(schema, expected);
+
+	Ok(())
+}
+
+#[test]
+fn test_derive_schema_enums_ok() -> Result<()> {
+	// -- Exec & Check
+	assert_eq!(
+		Unit::genai_schema(),
+		json!({"type": "string", "enum": ["C", "F"], "description": "The temperature unit."})
+	);
+
+	assert_eq!(
+		Shape::genai_schema(),
+		json!({"oneOf": [
+			{
+				"type": "object",
+				"description": "A circle.",
+				"properties": {"type": {"type": "string", "enum": ["circle"]}, "radius": {"type": "number"}},
+				"required": ["type", "radius"]
+			},
+			{
+				"type": "object",
+				"properties": {"type": {"type": "string", "enum": ["empty"]}},
+				"required": ["type"]
+			}
+		]})
+	);
+
+	assert_eq!(
+		Command::genai_schema(),
+		json!({"oneOf": [
+			{"type": "string", "enum": ["Stop"]},
+			{
+				"type": "object",
+				"properties": {"Move": {
+					"type": "array",
+					"prefixItems": [{"type": "integer"}, {"type": "integer"}],
+					"minItems": 2,
+					"maxItems": 2
+				}},
+				"required": ["Move"]
+			},
+			{"type": "object", "properties": {"Say": {"type": "string"}}, "required": ["Say"]}
+		]})
+	);
+
+	assert_eq!(
+		Id::genai_schema(),
+		json!({"anyOf": [{"type": "integer", "minimum": 0}, {"type": "string"}]})
+	);
+
+	Ok(())
+}
+
+#[test]
+fn test_derive_schema_generic_ok() -> Result<()> {
+	// -- Exec
+	let schema = Page::<Id>::genai_schema();
+
+	// -- Check
+	assert_eq!(schema["properties"]["items"]["items"], Id::genai_schema());
+	assert_eq!(schema["required"], json!(["items"]));
+
+	Ok(())
+}
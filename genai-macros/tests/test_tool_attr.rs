@@ -0,0 +1,113 @@
+//! `#[genai::tool]` tests (tool definition, and handler through a `ToolRegistry`).
+
+use genai::GenaiSchema;
+use genai::chat::{ToolCall, ToolName, ToolRegistry};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>; // For tests.
+
+// region:    --- Tools
+
+#[derive(Deserialize, GenaiSchema)]
+#[serde(rename_all = "lowercase")]
+enum Unit {
+	Celsius,
+	Fahrenheit,
+}
+
+#[derive(Serialize)]
+struct Weather {
+	city: String,
+	temperature: f64,
+}
+
+/// Returns the current weather of a city.
+#[genai::tool]
+async fn get_weather(city: String, unit: Option<Unit>) -> core::result::Result<Weather, genai::BoxError> {
+	if city.is_empty() {
+		return Err("empty city".into());
+	}
+	let temperature = match unit {
+		Some(Unit::Fahrenheit) => 71.6,
+		Some(Unit::Celsius) | None => 22.0,
+	};
+	Ok(Weather { city, temperature })
+}
+
+#[genai::tool(name = "add_numbers", description = "Adds two numbers.")]
+async fn add(a: i64, b: i64) -> core::result::Result<i64, std::io::Error> {
+	Ok(a + b)
+}
+
+// endregion: --- Tools
+
+#[test]
+fn test_tool_attr_tool_ok() -> Result<()> {
+	// -- Exec
+	let tool = get_weather::tool();
+
+	// -- Check
+	assert!(matches!(&tool.name, ToolName::Custom(name) if name == "get_weather"));
+	assert_eq!(
+		tool.description.as_deref(),
+		Some("Returns the current weather of a city.")
+	);
+	let expected = json!({
+		"type": "object",
+		"properties": {
+			"city": {"type": "string"},
+			"unit": {"type": ["string", "null"], "enum": ["celsius", "fahrenheit", null]}
+		},
+		"required": ["city"]
+	});
+	assert_eq!(tool.schema, Some(expected));
+
+	let tool = add::tool();
+	assert!(matches!(&tool.name, ToolName::Custom(name) if name == "add_numbers"));
+	assert_eq!(tool.description.as_deref(), Some("Adds two numbers."));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_attr_handler_ok() -> Result<()> {
+	// -- Setup & Fixtures
+	let registry = ToolRegistry::new()
+		.with_tool(get_weather::tool(), get_weather::handler)
+		.with_tool(add::tool(), add::handler);
+	let call = |call_id: &str, fn_name: &str, fn_arguments| ToolCall {
+		call_id: call_id.to_string(),
+		fn_name: fn_name.to_string(),
+		fn_arguments,
+		thought_signatures: None,
+	};
+
+	// -- Exec
+	let weather = registry
+		.execute(&call(
+			"call_1",
+			"get_weather",
+			json!({"city": "Paris", "unit": "fahrenheit"}),
+		))
+		.await;
+	let sum = registry.execute(&call("call_2", "add_numbers", json!({"a": 2, "b": 3}))).await;
+	let invalid = registry.execute(&call("call_3", "add_numbers", json!({"a": 2}))).await;
+	let failed = registry.execute(&call("call_4", "get_weather", json!({"city": ""}))).await;
+	let handler_err = add::handler(json!({"a": "two", "b": 3})).await.err().map(|err| err.to_string());
+
+	// -- Check
+	assert_eq!(
+		weather.json_content(),
+		Some(&json!({"city": "Paris", "temperature": 71.6}))
+	);
+	assert!(!weather.is_error);
+	assert_eq!(sum.json_content(), Some(&json!(5)));
+	assert!(invalid.is_error);
+	assert!(invalid.content_text().contains("missing required property 'b'"));
+	assert!(failed.is_error);
+	assert!(failed.content_text().contains("empty city"));
+	assert!(handler_err.is_some_and(|err| err.contains("invalid argument 'a'")));
+
+	Ok(())
+}
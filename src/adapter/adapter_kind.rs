@@ -185,6 +185,11 @@ impl AdapterKind {
 			AdapterKind::Zhipu => ZhipuAdapter::DEFAULT_API_KEY_ENV_NAME,
 		}
 	}
+
+	/// Returns true when the adapter can return several candidates in one call (see `ChatOptions::candidate_count`).
+	pub fn supports_candidate_count(&self) -> bool {
+		matches!(self, AdapterKind::OpenAI | AdapterKind::Gemini)
+	}
 }
 
 /// From Model implementations
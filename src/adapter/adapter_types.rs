@@ -1,11 +1,13 @@
 use crate::adapter::AdapterKind;
+use crate::adapter::openai::OpenAIAdapter;
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
-use crate::{Headers, ModelIden};
+use crate::webc::{StreamRequest, WebResponse};
+use crate::{Headers, ModelIden, ProviderError};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
 use serde_json::Value;
 
 pub trait Adapter {
@@ -40,7 +42,7 @@ pub trait Adapter {
 	/// To be implemented by Adapters.
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse>;
 
@@ -57,11 +59,19 @@ pub trait Adapter {
 		web_response: WebResponse,
 		options_set: EmbedOptionsSet<'_, '_>,
 	) -> Result<EmbedResponse>;
+
+	/// Parses a provider error body (HTTP error response or in-stream error event) into a `ProviderError`.
+	///
+	/// Defaults to the OpenAI error shape (`{"error": {"message", "type", "code"}}`), which most providers follow.
+	fn to_provider_error(status: Option<StatusCode>, _headers: Option<&HeaderMap>, body: &Value) -> ProviderError {
+		OpenAIAdapter::util_to_provider_error(status, body)
+	}
 }
 
 // region:    --- ServiceType
 
-#[derive(Debug, Clone, Copy)]
+/// The kind of service call a request is made for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ServiceType {
 	Chat,
 	ChatStream,
@@ -73,6 +83,7 @@ pub enum ServiceType {
 // region:    --- WebRequestData
 
 // NOTE: This cannot really move to `webc` because it must be public with the adapter, and `webc` is private for now.
+/// The provider HTTP request built by the adapter (url, headers, JSON payload).
 #[derive(Debug, Clone)]
 pub struct WebRequestData {
 	pub url: String,
@@ -4,9 +4,8 @@ use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
+use crate::webc::{StreamRequest, WebResponse};
 use crate::{ModelIden, Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 /// Aliyun Adapter - Uses OpenAI-compatible API for Dashscope (Aliyun)
 ///
@@ -116,10 +115,10 @@ impl Adapter for AliyunAdapter {
 	/// Delegates to OpenAIAdapter due to API compatibility.
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set)
+		OpenAIAdapter::to_chat_stream(model_iden, stream_request, options_set)
 	}
 
 	/// Converts embedding request data to web request format
@@ -1,16 +1,17 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_unsupported_sampling, get_api_key};
 use crate::adapter::anthropic::AnthropicStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	Binary, BinarySource, CacheControl, CacheCreationDetails, ChatOptionsSet, ChatRequest, ChatResponse, ChatRole,
-	ChatStream, ChatStreamResponse, ContentPart, MessageContent, PromptTokensDetails, ReasoningEffort, StopReason,
-	Tool, ToolCall, ToolConfig, ToolName, Usage,
+	ChatStream, ChatStreamResponse, ContentPart, MessageContent, PromptTokensDetails, ReasoningEffort, SchemaDialect,
+	StopReason, Tool, ToolCall, ToolChoice, ToolConfig, ToolName, ToolResponse, ToolResponsePart, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
-use crate::{Headers, ModelIden};
+use crate::webc::{EventSourceStream, StreamRequest, WebResponse};
+use crate::{ErrorKind, Headers, ModelIden, ProviderError};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
 use serde_json::{Value, json};
 use tracing::warn;
 use value_ext::JsonValueExt;
@@ -280,6 +281,18 @@ impl Adapter for AnthropicAdapter {
 
 		if let Some(tools) = tools {
 			payload.x_insert("/tools", tools)?;
+			// Note: `disable_parallel_tool_use` is a property of the tool_choice (not available for "none").
+			let mut tool_choice = options_set.tool_choice().map(Self::tool_choice_to_anthropic_tool_choice);
+			if options_set.parallel_tool_calls() == Some(false)
+				&& !matches!(options_set.tool_choice(), Some(ToolChoice::None))
+			{
+				tool_choice
+					.get_or_insert_with(|| json!({"type": "auto"}))
+					.x_insert("disable_parallel_tool_use", true)?;
+			}
+			if let Some(tool_choice) = tool_choice {
+				payload.x_insert("tool_choice", tool_choice)?;
+			}
 		}
 
 		// -- Set the reasoning effort
@@ -292,6 +305,20 @@ impl Adapter for AnthropicAdapter {
 			payload.x_insert("temperature", temperature)?;
 		}
 
+		if let Some(top_k) = options_set.top_k() {
+			payload.x_insert("top_k", top_k)?;
+		}
+		check_unsupported_sampling(
+			model.adapter_kind,
+			&options_set,
+			&[
+				("min_p", options_set.min_p().is_some()),
+				("frequency_penalty", options_set.frequency_penalty().is_some()),
+				("presence_penalty", options_set.presence_penalty().is_some()),
+				("repetition_penalty", options_set.repetition_penalty().is_some()),
+			],
+		)?;
+
 		if !options_set.stop_sequences().is_empty() {
 			payload.x_insert("stop_sequences", options_set.stop_sequences())?;
 		}
@@ -406,15 +433,20 @@ impl Adapter for AnthropicAdapter {
 			stop_reason,
 			usage,
 			captured_raw_body: None, // Set by the client exec_chat
+			candidates: Vec::new(),  // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs: None,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source =
+			EventSourceStream::new(stream_request).with_retry_policy(options_set.retry_policy().cloned());
 		let anthropic_stream = AnthropicStreamer::new(event_source, model_iden.clone(), options_set);
 		let chat_stream = ChatStream::from_inter_stream(anthropic_stream);
 		Ok(ChatStreamResponse {
@@ -444,6 +476,27 @@ impl Adapter for AnthropicAdapter {
 			feature: "embeddings".to_string(),
 		})
 	}
+
+	/// Anthropic error shape: `{"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}`
+	fn to_provider_error(status: Option<StatusCode>, _headers: Option<&HeaderMap>, body: &Value) -> ProviderError {
+		let error = body.get("error").unwrap_or(body);
+		let code = error.x_get::<String>("type").ok();
+		let message = error.x_get::<String>("message").ok();
+
+		let code_kind = code.as_deref().and_then(|code| match code {
+			"invalid_request_error" | "request_too_large" => Some(ErrorKind::InvalidRequest),
+			"authentication_error" => Some(ErrorKind::AuthenticationFailed),
+			"permission_error" | "billing_error" => Some(ErrorKind::PermissionDenied),
+			"not_found_error" => Some(ErrorKind::NotFound),
+			"rate_limit_error" => Some(ErrorKind::RateLimited),
+			"overloaded_error" => Some(ErrorKind::Overloaded),
+			"timeout_error" => Some(ErrorKind::Timeout),
+			"api_error" => Some(ErrorKind::ServerError),
+			_ => None,
+		});
+
+		ProviderError::from_parts(status, code, message, code_kind)
+	}
 }
 
 // region:    --- Support
@@ -552,62 +605,14 @@ impl AnthropicAdapter {
 									values.push(json!({"type": "text", "text": text}));
 								}
 								ContentPart::Binary(binary) => {
-									let is_image = binary.is_image();
-									let Binary {
-										content_type, source, ..
-									} = binary;
-
-									if is_image {
-										match &source {
-											BinarySource::Url(_) => {
-												// As of this API version, Anthropic doesn't support images by URL directly in messages.
-												warn!(
-													"Anthropic doesn't support images from URL, need to handle it gracefully"
-												);
-											}
-											BinarySource::Base64(content) => {
-												values.push(json!({
-													"type": "image",
-													"source": {
-														"type": "base64",
-														"media_type": content_type,
-														"data": content,
-													}
-												}));
-											}
-										}
-									} else {
-										match &source {
-											BinarySource::Url(url) => {
-												values.push(json!({
-													"type": "document",
-													"source": {
-														"type": "url",
-														"url": url,
-													}
-												}));
-											}
-											BinarySource::Base64(b64) => {
-												values.push(json!({
-													"type": "document",
-													"source": {
-														"type": "base64",
-														"media_type": content_type,
-														"data": b64,
-													}
-												}));
-											}
-										}
+									if let Some(value) = Self::binary_to_anthropic_part(binary) {
+										values.push(value);
 									}
 								}
 								// ToolCall is not valid in user content for Anthropic; skip gracefully.
 								ContentPart::ToolCall(_tc) => {}
 								ContentPart::ToolResponse(tool_response) => {
-									values.push(json!({
-										"type": "tool_result",
-										"content": tool_response.content,
-										"tool_use_id": tool_response.call_id,
-									}));
+									values.push(Self::tool_response_to_anthropic_tool_result(tool_response));
 								}
 								ContentPart::ThoughtSignature(_) => {}
 								ContentPart::ReasoningContent(_) => {}
@@ -673,11 +678,7 @@ impl AnthropicAdapter {
 					let mut values: Vec<Value> = Vec::new();
 					for part in msg.content {
 						if let ContentPart::ToolResponse(tool_response) = part {
-							values.push(json!({
-								"type": "tool_result",
-								"content": tool_response.content,
-								"tool_use_id": tool_response.call_id,
-							}));
+							values.push(Self::tool_response_to_anthropic_tool_result(tool_response));
 						}
 					}
 					if !values.is_empty() {
@@ -735,6 +736,89 @@ impl AnthropicAdapter {
 		})
 	}
 
+	/// Returns the image or document content block of the binary (None if not supported).
+	fn binary_to_anthropic_part(binary: Binary) -> Option<Value> {
+		let is_image = binary.is_image();
+		let Binary {
+			content_type, source, ..
+		} = binary;
+
+		if is_image {
+			match &source {
+				BinarySource::Url(_) => {
+					// As of this API version, Anthropic doesn't support images by URL directly in messages.
+					warn!("Anthropic doesn't support images from URL, need to handle it gracefully");
+					None
+				}
+				BinarySource::Base64(content) => Some(json!({
+					"type": "image",
+					"source": {
+						"type": "base64",
+						"media_type": content_type,
+						"data": content,
+					}
+				})),
+			}
+		} else {
+			match &source {
+				BinarySource::Url(url) => Some(json!({
+					"type": "document",
+					"source": {
+						"type": "url",
+						"url": url,
+					}
+				})),
+				BinarySource::Base64(b64) => Some(json!({
+					"type": "document",
+					"source": {
+						"type": "base64",
+						"media_type": content_type,
+						"data": b64,
+					}
+				})),
+			}
+		}
+	}
+
+	/// Returns the `tool_result` block, with a string content when there are no binaries,
+	/// otherwise with text (text and JSON parts) and image/document blocks.
+	fn tool_response_to_anthropic_tool_result(tool_response: ToolResponse) -> Value {
+		let content = if tool_response.binaries().is_empty() {
+			json!(tool_response.content_text())
+		} else {
+			let blocks: Vec<Value> = tool_response
+				.content
+				.into_iter()
+				.filter_map(|part| match part {
+					ToolResponsePart::Text(text) => Some(json!({"type": "text", "text": text})),
+					ToolResponsePart::Json(value) => Some(json!({"type": "text", "text": value.to_string()})),
+					ToolResponsePart::Binary(binary) => Self::binary_to_anthropic_part(binary),
+				})
+				.collect();
+			json!(blocks)
+		};
+
+		let mut tool_result = json!({
+			"type": "tool_result",
+			"content": content,
+			"tool_use_id": tool_response.call_id,
+		});
+		if tool_response.is_error {
+			tool_result["is_error"] = json!(true);
+		}
+		tool_result
+	}
+
+	fn tool_choice_to_anthropic_tool_choice(tool_choice: &ToolChoice) -> Value {
+		match tool_choice {
+			ToolChoice::Auto => json!({"type": "auto"}),
+			ToolChoice::None => json!({"type": "none"}),
+			ToolChoice::Required => json!({"type": "any"}),
+			ToolChoice::Tool(ToolName::WebSearch) => json!({"type": "tool", "name": "web_search"}),
+			ToolChoice::Tool(ToolName::Custom(name)) => json!({"type": "tool", "name": name}),
+		}
+	}
+
 	fn tool_to_anthropic_tool(tool: Tool) -> Result<Value> {
 		let Tool {
 			name,
@@ -782,6 +866,7 @@ impl AnthropicAdapter {
 				}
 			}
 		} else {
+			let schema = schema.map(|schema| SchemaDialect::ToolInputObject.normalized(schema));
 			tool_value.x_insert("input_schema", schema)?;
 			if let Some(description) = description {
 				// TODO: need to handle error
@@ -215,6 +215,16 @@ impl futures::Stream for AnthropicStreamer {
 						}
 
 						"ping" => continue, // Loop to the next event
+
+						// -- ERROR (e.g., overloaded_error mid-stream)
+						"error" => {
+							self.done = true;
+							let body = self.parse_message_data(&message.data)?;
+							return Poll::Ready(Some(Err(Error::ChatResponse {
+								model_iden: self.options.model_iden.clone(),
+								body,
+							})));
+						}
 						other => tracing::warn!("UNKNOWN MESSAGE TYPE: {other}"),
 					}
 				}
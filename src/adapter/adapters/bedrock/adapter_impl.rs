@@ -4,16 +4,18 @@
 //! Uses Bearer token authentication with AWS Bedrock API keys.
 
 use crate::adapter::adapters::bedrock::streamer::BedrockStreamer;
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_unsupported_sampling, get_api_key};
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	Binary, BinarySource, ChatOptionsSet, ChatRequest, ChatResponse, ChatRole, ChatStream, ChatStreamResponse,
-	ContentPart, MessageContent, StopReason, ToolCall, Usage,
+	ContentPart, MessageContent, SchemaDialect, StopReason, ToolCall, ToolChoice, ToolName, ToolResponse,
+	ToolResponsePart, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
-use crate::{Error, Headers, ModelIden, Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::webc::{EventSourceStream, StreamRequest, WebResponse};
+use crate::{Error, ErrorKind, Headers, ModelIden, ProviderError, Result, ServiceTarget};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
 use serde_json::{Value, json};
 use tracing::warn;
 use value_ext::JsonValueExt;
@@ -132,12 +134,7 @@ impl BedrockAdapter {
 					let mut tool_results: Vec<Value> = Vec::new();
 					for part in msg.content {
 						if let ContentPart::ToolResponse(tool_response) = part {
-							tool_results.push(json!({
-								"toolResult": {
-									"toolUseId": tool_response.call_id,
-									"content": [{"text": tool_response.content}]
-								}
-							}));
+							tool_results.push(Self::tool_response_to_bedrock_tool_result(tool_response));
 						}
 					}
 					if !tool_results.is_empty() {
@@ -159,7 +156,7 @@ impl BedrockAdapter {
 						"toolSpec": {
 							"name": tool.name,
 							"inputSchema": {
-								"json": tool.schema
+								"json": tool.schema.map(|schema| SchemaDialect::ToolInputObject.normalized(schema))
 							}
 						}
 					});
@@ -183,6 +180,23 @@ impl BedrockAdapter {
 		})
 	}
 
+	/// Convert the GenAI tool choice to the Bedrock `toolChoice` (the Converse API has no "none" tool choice).
+	fn tool_choice_to_bedrock_tool_choice(tool_choice: &ToolChoice) -> Result<Value> {
+		let tool_choice = match tool_choice {
+			ToolChoice::Auto => json!({"auto": {}}),
+			ToolChoice::Required => json!({"any": {}}),
+			ToolChoice::Tool(ToolName::Custom(name)) => json!({"tool": {"name": name}}),
+			ToolChoice::None | ToolChoice::Tool(ToolName::WebSearch) => {
+				return Err(Error::AdapterNotSupported {
+					adapter_kind: AdapterKind::Bedrock,
+					feature: format!("tool_choice {tool_choice:?}"),
+				});
+			}
+		};
+
+		Ok(tool_choice)
+	}
+
 	/// Convert GenAI content parts to Bedrock content format
 	fn convert_content_parts_to_bedrock(content: MessageContent, is_user: bool) -> Result<Vec<Value>> {
 		let mut parts: Vec<Value> = Vec::new();
@@ -193,47 +207,8 @@ impl BedrockAdapter {
 					parts.push(json!({"text": text}));
 				}
 				ContentPart::Binary(binary) if is_user => {
-					let is_image = binary.is_image();
-					let Binary {
-						content_type, source, ..
-					} = binary;
-
-					if is_image {
-						match source {
-							BinarySource::Base64(data) => {
-								// Extract format from content type (e.g., "image/png" -> "png")
-								let format = content_type.split('/').nth(1).unwrap_or("png").to_string();
-								parts.push(json!({
-									"image": {
-										"format": format,
-										"source": {
-											"bytes": data
-										}
-									}
-								}));
-							}
-							BinarySource::Url(_) => {
-								warn!("Bedrock doesn't support images from URL directly");
-							}
-						}
-					} else {
-						// Document handling
-						match source {
-							BinarySource::Base64(data) => {
-								let format = content_type.split('/').nth(1).unwrap_or("pdf").to_string();
-								parts.push(json!({
-									"document": {
-										"format": format,
-										"source": {
-											"bytes": data
-										}
-									}
-								}));
-							}
-							BinarySource::Url(_) => {
-								warn!("Bedrock doesn't support documents from URL directly");
-							}
-						}
+					if let Some(value) = Self::binary_to_bedrock_part(binary) {
+						parts.push(value);
 					}
 				}
 				ContentPart::ToolCall(tool_call) if !is_user => {
@@ -246,12 +221,7 @@ impl BedrockAdapter {
 					}));
 				}
 				ContentPart::ToolResponse(tool_response) if is_user => {
-					parts.push(json!({
-						"toolResult": {
-							"toolUseId": tool_response.call_id,
-							"content": [{"text": tool_response.content}]
-						}
-					}));
+					parts.push(Self::tool_response_to_bedrock_tool_result(tool_response));
 				}
 				ContentPart::ReasoningContent(_) => {
 					// Reasoning content is not sent back to Bedrock in requests
@@ -264,6 +234,77 @@ impl BedrockAdapter {
 
 		Ok(parts)
 	}
+
+	/// Returns the image or document content block of the binary (None if not supported).
+	fn binary_to_bedrock_part(binary: Binary) -> Option<Value> {
+		let is_image = binary.is_image();
+		let Binary {
+			content_type, source, ..
+		} = binary;
+
+		if is_image {
+			match source {
+				BinarySource::Base64(data) => {
+					// Extract format from content type (e.g., "image/png" -> "png")
+					let format = content_type.split('/').nth(1).unwrap_or("png").to_string();
+					Some(json!({
+						"image": {
+							"format": format,
+							"source": {
+								"bytes": data
+							}
+						}
+					}))
+				}
+				BinarySource::Url(_) => {
+					warn!("Bedrock doesn't support images from URL directly");
+					None
+				}
+			}
+		} else {
+			// Document handling
+			match source {
+				BinarySource::Base64(data) => {
+					let format = content_type.split('/').nth(1).unwrap_or("pdf").to_string();
+					Some(json!({
+						"document": {
+							"format": format,
+							"source": {
+								"bytes": data
+							}
+						}
+					}))
+				}
+				BinarySource::Url(_) => {
+					warn!("Bedrock doesn't support documents from URL directly");
+					None
+				}
+			}
+		}
+	}
+
+	/// Returns the `toolResult` block, with `text`, `json`, and image/document content blocks,
+	/// and the `error` status for an error response.
+	fn tool_response_to_bedrock_tool_result(tool_response: ToolResponse) -> Value {
+		let content: Vec<Value> = tool_response
+			.content
+			.into_iter()
+			.filter_map(|part| match part {
+				ToolResponsePart::Text(text) => Some(json!({"text": text})),
+				ToolResponsePart::Json(value) => Some(json!({"json": value})),
+				ToolResponsePart::Binary(binary) => Self::binary_to_bedrock_part(binary),
+			})
+			.collect();
+
+		let mut tool_result = json!({
+			"toolUseId": tool_response.call_id,
+			"content": content
+		});
+		if tool_response.is_error {
+			tool_result["status"] = json!("error");
+		}
+		json!({ "toolResult": tool_result })
+	}
 }
 
 impl Adapter for BedrockAdapter {
@@ -342,7 +383,10 @@ impl Adapter for BedrockAdapter {
 		}
 
 		// Add tool config if present
-		if let Some(tool_config) = tool_config {
+		if let Some(mut tool_config) = tool_config {
+			if let Some(tool_choice) = options_set.tool_choice() {
+				tool_config.x_insert("toolChoice", Self::tool_choice_to_bedrock_tool_choice(tool_choice)?)?;
+			}
 			payload.x_insert("toolConfig", tool_config)?;
 		}
 
@@ -368,6 +412,19 @@ impl Adapter for BedrockAdapter {
 			inference_config.x_insert("topP", top_p)?;
 		}
 
+		// Note: The Converse API inference config has no top-k, min-p, or penalties (they are model specific)
+		check_unsupported_sampling(
+			model.adapter_kind,
+			&options_set,
+			&[
+				("top_k", options_set.top_k().is_some()),
+				("min_p", options_set.min_p().is_some()),
+				("frequency_penalty", options_set.frequency_penalty().is_some()),
+				("presence_penalty", options_set.presence_penalty().is_some()),
+				("repetition_penalty", options_set.repetition_penalty().is_some()),
+			],
+		)?;
+
 		// Stop sequences
 		if !options_set.stop_sequences().is_empty() {
 			inference_config.x_insert("stopSequences", options_set.stop_sequences())?;
@@ -437,15 +494,20 @@ impl Adapter for BedrockAdapter {
 			stop_reason,
 			usage,
 			captured_raw_body,
+			candidates: Vec::new(), // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs: None,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source =
+			EventSourceStream::new(stream_request).with_retry_policy(options_set.retry_policy().cloned());
 		let bedrock_stream = BedrockStreamer::new(event_source, model_iden.clone(), options_set);
 		let chat_stream = ChatStream::from_inter_stream(bedrock_stream);
 
@@ -476,6 +538,39 @@ impl Adapter for BedrockAdapter {
 			feature: "embeddings".to_string(),
 		})
 	}
+
+	/// Bedrock error shape: `x-amzn-errortype` header (e.g., `ThrottlingException:...`) or `__type` body property,
+	/// with the message in the `message` (or `Message`) body property.
+	fn to_provider_error(status: Option<StatusCode>, headers: Option<&HeaderMap>, body: &Value) -> ProviderError {
+		let code = headers
+			.and_then(|headers| headers.get("x-amzn-errortype"))
+			.and_then(|value| value.to_str().ok())
+			.map(|value| value.to_string())
+			.or_else(|| body.x_get::<String>("__type").ok())
+			// e.g., `ThrottlingException:http://internal.amazon.com/...` or `com.amazon.bedrock#ThrottlingException`
+			.map(|value| {
+				let value = value.split(':').next().unwrap_or_default();
+				value.rsplit('#').next().unwrap_or_default().to_string()
+			});
+		let message = body
+			.x_get::<String>("message")
+			.ok()
+			.or_else(|| body.x_get::<String>("Message").ok());
+
+		let code_kind = code.as_deref().and_then(|code| match code {
+			"ThrottlingException" | "ServiceQuotaExceededException" => Some(ErrorKind::RateLimited),
+			"ValidationException" => Some(ErrorKind::InvalidRequest),
+			"AccessDeniedException" => Some(ErrorKind::PermissionDenied),
+			"UnrecognizedClientException" | "ExpiredTokenException" => Some(ErrorKind::AuthenticationFailed),
+			"ResourceNotFoundException" => Some(ErrorKind::NotFound),
+			"ModelTimeoutException" => Some(ErrorKind::Timeout),
+			"ServiceUnavailableException" | "ModelNotReadyException" => Some(ErrorKind::Overloaded),
+			"InternalServerException" | "ModelErrorException" => Some(ErrorKind::ServerError),
+			_ => None,
+		});
+
+		ProviderError::from_parts(status, code, message, code_kind)
+	}
 }
 
 /// Helper struct for Bedrock request parts
@@ -3,9 +3,8 @@ use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
+use crate::webc::{StreamRequest, WebResponse};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 /// The BigModel adapter. Only available via namespace.
 ///
@@ -67,10 +66,10 @@ impl Adapter for BigModelAdapter {
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set)
+		OpenAIAdapter::to_chat_stream(model_iden, stream_request, options_set)
 	}
 
 	fn to_embed_request_data(
@@ -3,9 +3,8 @@ use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
+use crate::webc::{EventSourceStream, StreamRequest, WebResponse};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 pub struct CerebrasAdapter;
 
@@ -63,10 +62,11 @@ impl Adapter for CerebrasAdapter {
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source =
+			EventSourceStream::new(stream_request).with_retry_policy(options_set.retry_policy().cloned());
 		let cerebras_stream = super::streamer::CerebrasStreamer::new(event_source, model_iden.clone(), options_set);
 		let chat_stream = crate::chat::ChatStream::from_inter_stream(cerebras_stream);
 
@@ -1,15 +1,16 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_unsupported_sampling, get_api_key};
 use crate::adapter::cohere::CohereStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	ChatOptionsSet, ChatRequest, ChatResponse, ChatRole, ChatStream, ChatStreamResponse, MessageContent, StopReason,
-	Usage,
+	ToolChoice, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{WebResponse, WebStream};
-use crate::{Error, Headers, Result};
+use crate::webc::{StreamRequest, WebResponse, WebStream};
+use crate::{Error, Headers, ProviderError, Result};
 use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
 use serde_json::{Value, json};
 use value_ext::JsonValueExt;
 
@@ -78,6 +79,17 @@ impl Adapter for CohereAdapter {
 		// -- headers
 		let headers = Headers::from(("Authorization".to_string(), format!("Bearer {api_key}")));
 
+		// -- tool choice
+		// Note: This adapter does not send the tools, so it cannot force their use.
+		if chat_req.tools.as_ref().is_some_and(|tools| !tools.is_empty())
+			&& let Some(tool_choice @ (ToolChoice::Required | ToolChoice::Tool(_))) = options_set.tool_choice()
+		{
+			return Err(Error::AdapterNotSupported {
+				adapter_kind: AdapterKind::Cohere,
+				feature: format!("tool_choice {tool_choice:?}"),
+			});
+		}
+
 		// -- parts
 		let CohereChatRequestParts {
 			preamble,
@@ -118,6 +130,27 @@ impl Adapter for CohereAdapter {
 			payload.x_insert("p", top_p)?;
 		}
 
+		if let Some(top_k) = options_set.top_k() {
+			payload.x_insert("k", top_k)?;
+		}
+
+		if let Some(frequency_penalty) = options_set.frequency_penalty() {
+			payload.x_insert("frequency_penalty", frequency_penalty)?;
+		}
+
+		if let Some(presence_penalty) = options_set.presence_penalty() {
+			payload.x_insert("presence_penalty", presence_penalty)?;
+		}
+
+		check_unsupported_sampling(
+			model.adapter_kind,
+			&options_set,
+			&[
+				("min_p", options_set.min_p().is_some()),
+				("repetition_penalty", options_set.repetition_penalty().is_some()),
+			],
+		)?;
+
 		Ok(WebRequestData { url, headers, payload })
 	}
 
@@ -162,15 +195,20 @@ impl Adapter for CohereAdapter {
 			stop_reason,
 			usage,
 			captured_raw_body: None, // Set by the client exec_chat
+			candidates: Vec::new(),  // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs: None,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let web_stream = WebStream::new_with_delimiter(reqwest_builder, "\n");
+		let web_stream =
+			WebStream::new_with_delimiter(stream_request, "\n").with_retry_policy(options_set.retry_policy().cloned());
 		let cohere_stream = CohereStreamer::new(web_stream, model_iden.clone(), options_set);
 		let chat_stream = ChatStream::from_inter_stream(cohere_stream);
 
@@ -195,6 +233,12 @@ impl Adapter for CohereAdapter {
 	) -> Result<crate::embed::EmbedResponse> {
 		super::embed::to_embed_response(model_iden, web_response, options_set)
 	}
+
+	/// Cohere error shape: `{"id": "...", "message": "..."}` (the kind comes from the status and the message).
+	fn to_provider_error(status: Option<StatusCode>, _headers: Option<&HeaderMap>, body: &Value) -> ProviderError {
+		let message = body.x_get::<String>("message").ok();
+		ProviderError::from_parts(status, None, message, None)
+	}
 }
 
 // region:    --- Support
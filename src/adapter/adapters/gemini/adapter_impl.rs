@@ -1,15 +1,17 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_unsupported_sampling, get_api_key};
 use crate::adapter::gemini::GeminiStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
-	Binary, BinarySource, ChatOptionsSet, ChatRequest, ChatResponse, ChatResponseFormat, ChatRole, ChatStream,
-	ChatStreamResponse, CompletionTokensDetails, ContentPart, MessageContent, PromptTokensDetails, ReasoningEffort,
-	StopReason, Tool, ToolCall, ToolConfig, ToolName, Usage,
+	Binary, BinarySource, ChatCandidate, ChatOptionsSet, ChatRequest, ChatResponse, ChatResponseFormat, ChatRole,
+	ChatStream, ChatStreamResponse, CompletionTokensDetails, ContentPart, MessageContent, PromptTokensDetails,
+	ReasoningEffort, SchemaDialect, StopReason, TokenLogprob, Tool, ToolCall, ToolChoice, ToolConfig, ToolName,
+	ToolResponse, ToolResponsePart, TopLogprob, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{WebResponse, WebStream};
-use crate::{Error, Headers, ModelIden, Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use crate::webc::{StreamRequest, WebResponse, WebStream};
+use crate::{Error, ErrorKind, Headers, ModelIden, ProviderError, Result, ServiceTarget};
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
 use serde_json::{Value, json};
 use value_ext::JsonValueExt;
 
@@ -205,13 +207,17 @@ impl Adapter for GeminiAdapter {
 		// -- Tools
 		if let Some(tools) = tools {
 			payload.x_insert("tools", tools)?;
+			if let Some(tool_choice) = options_set.tool_choice() {
+				let function_calling_config = Self::tool_choice_to_gemini_function_calling_config(tool_choice)?;
+				payload.x_insert("/toolConfig/functionCallingConfig", function_calling_config)?;
+			}
 		}
 
 		// -- Response Format
 		if let Some(ChatResponseFormat::JsonSpec(st_json)) = options_set.response_format() {
 			payload.x_insert("/generationConfig/responseMimeType", "application/json")?;
 			let mut schema = st_json.schema.clone();
-			super::openapi_schema::to_openapi_schema(&mut schema);
+			SchemaDialect::OpenApi.normalize(&mut schema);
 			payload.x_insert("/generationConfig/responseJsonSchema", schema)?;
 		}
 
@@ -220,6 +226,24 @@ impl Adapter for GeminiAdapter {
 			payload.x_insert("/generationConfig/temperature", temperature)?;
 		}
 
+		if let Some(top_k) = options_set.top_k() {
+			payload.x_insert("/generationConfig/topK", top_k)?;
+		}
+		if let Some(frequency_penalty) = options_set.frequency_penalty() {
+			payload.x_insert("/generationConfig/frequencyPenalty", frequency_penalty)?;
+		}
+		if let Some(presence_penalty) = options_set.presence_penalty() {
+			payload.x_insert("/generationConfig/presencePenalty", presence_penalty)?;
+		}
+		check_unsupported_sampling(
+			model.adapter_kind,
+			&options_set,
+			&[
+				("min_p", options_set.min_p().is_some()),
+				("repetition_penalty", options_set.repetition_penalty().is_some()),
+			],
+		)?;
+
 		if !options_set.stop_sequences().is_empty() {
 			payload.x_insert("/generationConfig/stopSequences", options_set.stop_sequences())?;
 		}
@@ -230,6 +254,20 @@ impl Adapter for GeminiAdapter {
 		if let Some(top_p) = options_set.top_p() {
 			payload.x_insert("/generationConfig/topP", top_p)?;
 		}
+		// Note: Several candidates are only for the non-stream chat (the streamer reads the first candidate).
+		if let Some(candidate_count) = options_set.candidate_count()
+			&& candidate_count > 1
+			&& matches!(service_type, ServiceType::Chat)
+		{
+			payload.x_insert("/generationConfig/candidateCount", candidate_count)?;
+		}
+
+		if options_set.logprobs() {
+			payload.x_insert("/generationConfig/responseLogprobs", true)?;
+			if let Some(top_logprobs) = options_set.top_logprobs() {
+				payload.x_insert("/generationConfig/logprobs", top_logprobs)?;
+			}
+		}
 
 		// -- url
 		let provider_model = model.from_name(provider_model_name);
@@ -249,14 +287,150 @@ impl Adapter for GeminiAdapter {
 		// TODO: Need to be implemented (if available), for now, just clone model_iden
 		let provider_model_name: Option<String> = body.x_remove("modelVersion").ok();
 		let provider_model_iden = model_iden.from_optional_name(provider_model_name);
+
+		// -- Split the eventual extra candidates (the first one is the response content)
+		let extra_candidates: Vec<Value> = match body.get_mut("candidates").and_then(Value::as_array_mut) {
+			Some(candidates) if candidates.len() > 1 => candidates.split_off(1),
+			_ => Vec::new(),
+		};
+
 		let gemini_response = Self::body_to_gemini_chat_response(&model_iden.clone(), body)?;
 		let GeminiChatResponse {
 			content: gemini_content,
 			usage,
 			stop_reason,
+			logprobs,
 		} = gemini_response;
-		let stop_reason = stop_reason.map(StopReason::from);
+		let first_candidate = Self::gemini_content_to_candidate(gemini_content, stop_reason, logprobs);
+
+		let mut candidates = vec![first_candidate.clone()];
+		for candidate in extra_candidates {
+			let GeminiChatResponse {
+				content,
+				stop_reason,
+				logprobs,
+				..
+			} = Self::body_to_gemini_chat_response(&model_iden, json!({ "candidates": [candidate] }))?;
+			candidates.push(Self::gemini_content_to_candidate(content, stop_reason, logprobs));
+		}
+
+		let ChatCandidate {
+			content,
+			reasoning_content,
+			stop_reason,
+			logprobs,
+		} = first_candidate;
+
+		Ok(ChatResponse {
+			content,
+			reasoning_content,
+			model_iden,
+			provider_model_iden,
+			stop_reason,
+			candidates,
+			usage,
+			captured_raw_body: None, // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs,
+		})
+	}
+
+	fn to_chat_stream(
+		model_iden: ModelIden,
+		stream_request: StreamRequest,
+		options_set: ChatOptionsSet<'_, '_>,
+	) -> Result<ChatStreamResponse> {
+		let web_stream = WebStream::new_with_pretty_json_array(stream_request)
+			.with_retry_policy(options_set.retry_policy().cloned());
+
+		let gemini_stream = GeminiStreamer::new(web_stream, model_iden.clone(), options_set);
+		let chat_stream = ChatStream::from_inter_stream(gemini_stream);
+
+		Ok(ChatStreamResponse {
+			model_iden,
+			stream: chat_stream,
+		})
+	}
 
+	fn to_embed_request_data(
+		service_target: crate::ServiceTarget,
+		embed_req: crate::embed::EmbedRequest,
+		options_set: crate::embed::EmbedOptionsSet<'_, '_>,
+	) -> Result<crate::adapter::WebRequestData> {
+		super::embed::to_embed_request_data(service_target, embed_req, options_set)
+	}
+
+	fn to_embed_response(
+		model_iden: crate::ModelIden,
+		web_response: crate::webc::WebResponse,
+		options_set: crate::embed::EmbedOptionsSet<'_, '_>,
+	) -> Result<crate::embed::EmbedResponse> {
+		super::embed::to_embed_response(model_iden, web_response, options_set)
+	}
+
+	/// Gemini error shape: `{"error": {"code": 429, "message": "...", "status": "RESOURCE_EXHAUSTED", "details": [...]}}`
+	///
+	/// Also handles the `Error::ChatResponse` bodies for blocked responses (`finishReason` / `promptFeedback.blockReason`).
+	fn to_provider_error(status: Option<StatusCode>, _headers: Option<&HeaderMap>, body: &Value) -> ProviderError {
+		let Some(error) = body.get("error") else {
+			let block_reason = body
+				.x_get::<String>("/promptFeedback/blockReason")
+				.ok()
+				.or_else(|| body.x_get::<String>("finishReason").ok());
+			let code_kind = block_reason.as_deref().and_then(|reason| match reason {
+				"SAFETY" | "RECITATION" | "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII" | "IMAGE_SAFETY" => {
+					Some(ErrorKind::ContentFiltered)
+				}
+				_ => None,
+			});
+			return ProviderError::from_parts(status, block_reason, None, code_kind);
+		};
+
+		let code = error.x_get::<String>("status").ok();
+		let message = error.x_get::<String>("message").ok();
+		let status =
+			status.or_else(|| error.x_get::<u16>("code").ok().and_then(|code| StatusCode::from_u16(code).ok()));
+
+		let invalid_api_key = error
+			.get("details")
+			.and_then(|details| details.as_array())
+			.is_some_and(|details| {
+				details
+					.iter()
+					.any(|detail| detail.x_get::<String>("reason").is_ok_and(|reason| reason == "API_KEY_INVALID"))
+			});
+
+		let code_kind = if invalid_api_key {
+			Some(ErrorKind::AuthenticationFailed)
+		} else {
+			code.as_deref().and_then(|code| match code {
+				"INVALID_ARGUMENT" | "FAILED_PRECONDITION" | "OUT_OF_RANGE" => Some(ErrorKind::InvalidRequest),
+				"UNAUTHENTICATED" => Some(ErrorKind::AuthenticationFailed),
+				"PERMISSION_DENIED" => Some(ErrorKind::PermissionDenied),
+				"NOT_FOUND" => Some(ErrorKind::NotFound),
+				"RESOURCE_EXHAUSTED" => Some(ErrorKind::RateLimited),
+				"UNAVAILABLE" => Some(ErrorKind::Overloaded),
+				"DEADLINE_EXCEEDED" => Some(ErrorKind::Timeout),
+				"INTERNAL" | "UNKNOWN" => Some(ErrorKind::ServerError),
+				_ => None,
+			})
+		};
+
+		ProviderError::from_parts(status, code, message, code_kind)
+	}
+}
+
+// region:    --- Support
+
+/// Support functions for GeminiAdapter
+impl GeminiAdapter {
+	/// Assembles the content parts of one Gemini candidate (thought signatures, text, binaries, and tool calls).
+	fn gemini_content_to_candidate(
+		gemini_content: Vec<GeminiChatContent>,
+		stop_reason: Option<String>,
+		logprobs: Option<Vec<TokenLogprob>>,
+	) -> ChatCandidate {
 		let mut thoughts: Vec<String> = Vec::new();
 		let mut reasonings: Vec<String> = Vec::new();
 		let mut texts: Vec<String> = Vec::new();
@@ -308,54 +482,14 @@ impl Adapter for GeminiAdapter {
 		parts.extend(tool_calls.into_iter().map(ContentPart::ToolCall));
 		let content = MessageContent::from_parts(parts);
 
-		Ok(ChatResponse {
+		ChatCandidate {
 			content,
 			reasoning_content: Some(reasoning_text),
-			model_iden,
-			provider_model_iden,
-			stop_reason,
-			usage,
-			captured_raw_body: None, // Set by the client exec_chat
-		})
-	}
-
-	fn to_chat_stream(
-		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
-		options_set: ChatOptionsSet<'_, '_>,
-	) -> Result<ChatStreamResponse> {
-		let web_stream = WebStream::new_with_pretty_json_array(reqwest_builder);
-
-		let gemini_stream = GeminiStreamer::new(web_stream, model_iden.clone(), options_set);
-		let chat_stream = ChatStream::from_inter_stream(gemini_stream);
-
-		Ok(ChatStreamResponse {
-			model_iden,
-			stream: chat_stream,
-		})
-	}
-
-	fn to_embed_request_data(
-		service_target: crate::ServiceTarget,
-		embed_req: crate::embed::EmbedRequest,
-		options_set: crate::embed::EmbedOptionsSet<'_, '_>,
-	) -> Result<crate::adapter::WebRequestData> {
-		super::embed::to_embed_request_data(service_target, embed_req, options_set)
-	}
-
-	fn to_embed_response(
-		model_iden: crate::ModelIden,
-		web_response: crate::webc::WebResponse,
-		options_set: crate::embed::EmbedOptionsSet<'_, '_>,
-	) -> Result<crate::embed::EmbedResponse> {
-		super::embed::to_embed_response(model_iden, web_response, options_set)
+			stop_reason: stop_reason.map(StopReason::from),
+			logprobs,
+		}
 	}
-}
 
-// region:    --- Support
-
-/// Support functions for GeminiAdapter
-impl GeminiAdapter {
 	pub(super) fn body_to_gemini_chat_response(model_iden: &ModelIden, mut body: Value) -> Result<GeminiChatResponse> {
 		// If the body has an `error` property, then it is assumed to be an error.
 		if body.get("error").is_some() {
@@ -370,6 +504,10 @@ impl GeminiAdapter {
 		// Extract usage before content/parts so it is available even in
 		// usage-only tail frames (finishReason + usageMetadata but no content).
 		let usage = body.x_take::<Value>("usageMetadata").map(Self::into_usage).unwrap_or_default();
+		let logprobs = body
+			.x_take::<Value>("/candidates/0/logprobsResult")
+			.ok()
+			.and_then(Self::into_logprobs);
 
 		// -- Read multipart
 		let parts = match body.x_take::<Vec<Value>>("/candidates/0/content/parts") {
@@ -388,11 +526,13 @@ impl GeminiAdapter {
 						content,
 						usage,
 						stop_reason: finish_reason,
+						logprobs,
 					});
 				}
 
 				let body = json!({
 					"finishReason": finish_reason,
+					"promptFeedback": body.x_take::<Value>("promptFeedback").unwrap_or_default(),
 					"usageMetadata": Value::Null,
 				});
 				return Err(Error::ChatResponse {
@@ -455,9 +595,46 @@ impl GeminiAdapter {
 			content,
 			usage,
 			stop_reason,
+			logprobs,
 		})
 	}
 
+	/// See gemini doc: https://ai.google.dev/api/generate-content#LogprobsResult
+	///
+	/// `topCandidates` are aligned with `chosenCandidates` (one entry per position). Returns None when empty.
+	pub(super) fn into_logprobs(mut logprobs_result: Value) -> Option<Vec<TokenLogprob>> {
+		let chosen: Vec<Value> = logprobs_result.x_take("chosenCandidates").ok()?;
+		let mut top_candidates = logprobs_result
+			.x_take::<Vec<Value>>("topCandidates")
+			.unwrap_or_default()
+			.into_iter();
+
+		let logprobs: Vec<TokenLogprob> = chosen
+			.into_iter()
+			.map(|mut candidate| {
+				let top = top_candidates
+					.next()
+					.and_then(|mut top| top.x_take::<Vec<Value>>("candidates").ok())
+					.unwrap_or_default()
+					.into_iter()
+					.map(|mut top| TopLogprob {
+						token: top.x_take("token").unwrap_or_default(),
+						logprob: top.x_take("logProbability").unwrap_or_default(),
+						bytes: None,
+					})
+					.collect();
+				TokenLogprob {
+					token: candidate.x_take("token").unwrap_or_default(),
+					logprob: candidate.x_take("logProbability").unwrap_or_default(),
+					bytes: None,
+					top,
+				}
+			})
+			.collect();
+
+		(!logprobs.is_empty()).then_some(logprobs)
+	}
+
 	/// See gemini doc: https://ai.google.dev/api/generate-content#UsageMetadata
 	pub(super) fn into_usage(mut usage_value: Value) -> Usage {
 		let total_tokens: Option<i32> = usage_value.x_take("totalTokenCount").ok();
@@ -560,25 +737,7 @@ impl GeminiAdapter {
 					for part in msg.content {
 						match part {
 							ContentPart::Text(text) => parts_values.push(json!({"text": text})),
-							ContentPart::Binary(binary) => {
-								let Binary {
-									content_type, source, ..
-								} = binary;
-								match &source {
-									BinarySource::Url(url) => parts_values.push(json!({
-										"file_data": {
-											"mime_type": content_type,
-											"file_uri": url
-										}
-									})),
-									BinarySource::Base64(content) => parts_values.push(json!({
-										"inline_data": {
-											"mime_type": content_type,
-											"data": content
-										}
-									})),
-								}
-							}
+							ContentPart::Binary(binary) => parts_values.push(Self::binary_to_gemini_part(binary)),
 							ContentPart::ToolCall(tool_call) => {
 								parts_values.push(json!({
 									"functionCall": {
@@ -588,15 +747,7 @@ impl GeminiAdapter {
 								}));
 							}
 							ContentPart::ToolResponse(tool_response) => {
-								parts_values.push(json!({
-									"functionResponse": {
-										"name": tool_response.call_id,
-										"response": {
-											"name": tool_response.call_id,
-											"content": tool_response.content,
-										}
-									}
-								}));
+								parts_values.push(Self::tool_response_to_gemini_part(tool_response));
 							}
 							ContentPart::ThoughtSignature(thought) => {
 								parts_values.push(json!({
@@ -699,15 +850,7 @@ impl GeminiAdapter {
 								}));
 							}
 							ContentPart::ToolResponse(tool_response) => {
-								parts_values.push(json!({
-									"functionResponse": {
-										"name": tool_response.call_id,
-										"response": {
-											"name": tool_response.call_id,
-											"content": tool_response.content,
-										}
-									}
-								}));
+								parts_values.push(Self::tool_response_to_gemini_part(tool_response));
 							}
 							ContentPart::ThoughtSignature(thought) => {
 								parts_values.push(json!({
@@ -767,6 +910,79 @@ impl GeminiAdapter {
 		})
 	}
 
+	/// Returns the `inline_data` (base64) or `file_data` (URL) part of the binary.
+	fn binary_to_gemini_part(binary: Binary) -> Value {
+		let Binary {
+			content_type, source, ..
+		} = binary;
+		match &source {
+			BinarySource::Url(url) => json!({
+				"file_data": {
+					"mime_type": content_type,
+					"file_uri": url
+				}
+			}),
+			BinarySource::Base64(content) => json!({
+				"inline_data": {
+					"mime_type": content_type,
+					"data": content
+				}
+			}),
+		}
+	}
+
+	/// Returns the `functionResponse` part of the tool response.
+	/// - The response `content` is the JSON value for a single JSON part, otherwise the text content.
+	/// - An error response uses the `error` key instead of `content`.
+	/// - The binaries are sent as the function response `parts` (multimodal function responses).
+	fn tool_response_to_gemini_part(tool_response: ToolResponse) -> Value {
+		let content = match tool_response.json_content() {
+			Some(value) => value.clone(),
+			None => json!(tool_response.content_text()),
+		};
+		let content_key = if tool_response.is_error { "error" } else { "content" };
+
+		let mut function_response = json!({
+			"name": tool_response.call_id,
+			"response": {
+				"name": tool_response.call_id,
+				content_key: content,
+			}
+		});
+
+		let binary_parts: Vec<Value> = tool_response
+			.content
+			.into_iter()
+			.filter_map(|part| match part {
+				ToolResponsePart::Binary(binary) => Some(Self::binary_to_gemini_part(binary)),
+				_ => None,
+			})
+			.collect();
+		if !binary_parts.is_empty() {
+			function_response["parts"] = json!(binary_parts);
+		}
+
+		json!({ "functionResponse": function_response })
+	}
+
+	/// Note: `functionCallingConfig` only applies to function declarations (not to the builtin tools).
+	fn tool_choice_to_gemini_function_calling_config(tool_choice: &ToolChoice) -> Result<Value> {
+		let config = match tool_choice {
+			ToolChoice::Auto => json!({"mode": "AUTO"}),
+			ToolChoice::None => json!({"mode": "NONE"}),
+			ToolChoice::Required => json!({"mode": "ANY"}),
+			ToolChoice::Tool(ToolName::Custom(name)) => json!({"mode": "ANY", "allowedFunctionNames": [name]}),
+			ToolChoice::Tool(ToolName::WebSearch) => {
+				return Err(Error::AdapterNotSupported {
+					adapter_kind: AdapterKind::Gemini,
+					feature: "tool_choice for the WebSearch tool".to_string(),
+				});
+			}
+		};
+
+		Ok(config)
+	}
+
 	fn tool_to_gemini_tool(tool: Tool) -> Result<GeminiTool> {
 		let Tool {
 			name,
@@ -799,7 +1015,7 @@ impl GeminiAdapter {
 		// -- otherwise, user tool
 		else {
 			let mut parameters = schema.unwrap_or(Value::Null);
-			super::openapi_schema::to_openapi_schema(&mut parameters);
+			SchemaDialect::OpenApi.normalize(&mut parameters);
 			let parameters = if parameters.is_null() { None } else { Some(parameters) };
 			Ok(GeminiTool::User(json!({
 				"name": name_str,
@@ -855,6 +1071,7 @@ pub(super) struct GeminiChatResponse {
 	pub content: Vec<GeminiChatContent>,
 	pub usage: Usage,
 	pub stop_reason: Option<String>,
+	pub logprobs: Option<Vec<TokenLogprob>>,
 }
 
 pub(super) enum GeminiChatContent {
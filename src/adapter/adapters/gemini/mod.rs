@@ -6,7 +6,6 @@
 
 mod adapter_impl;
 mod embed;
-mod openapi_schema;
 mod streamer;
 
 pub use adapter_impl::*;
@@ -100,6 +100,7 @@ impl futures::Stream for GeminiStreamer {
 								content,
 								usage,
 								stop_reason,
+								logprobs,
 							} = gemini_response;
 
 							// -- Capture stop_reason if present (typically in the last chunk)
@@ -176,7 +177,8 @@ impl futures::Stream for GeminiStreamer {
 									self.captured_data.usage = Some(usage.clone());
 								}
 
-								self.pending_events.push_back(InterStreamEvent::Chunk(stream_text_content));
+								self.pending_events
+									.push_back(InterStreamEvent::chunk(stream_text_content, logprobs));
 							}
 
 							// 3. Tool Call
@@ -3,9 +3,8 @@ use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
+use crate::webc::{StreamRequest, WebResponse};
 use crate::{ModelIden, Result, ServiceTarget};
-use reqwest::RequestBuilder;
 
 pub struct KimiAdapter;
 
@@ -51,10 +50,10 @@ impl Adapter for KimiAdapter {
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		AnthropicAdapter::to_chat_stream(model_iden, reqwest_builder, options_set)
+		AnthropicAdapter::to_chat_stream(model_iden, stream_request, options_set)
 	}
 
 	fn to_embed_request_data(
@@ -5,14 +5,15 @@ use crate::adapter::ollama::OllamaStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	Binary, BinarySource, ChatOptionsSet, ChatRequest, ChatResponse, ChatStream, ChatStreamResponse, ContentPart,
-	MessageContent, StopReason, Tool, ToolCall, ToolName, Usage,
+	MessageContent, SchemaDialect, StopReason, Tool, ToolCall, ToolChoice, ToolName, Usage,
 };
 use crate::embed::{EmbedResponse, Embedding};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
-use crate::{Error, Result};
+use crate::webc::{StreamRequest, WebResponse};
+use crate::{Error, ProviderError, Result};
 use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
 use serde_json::{Value, json};
 use value_ext::JsonValueExt;
 
@@ -93,6 +94,21 @@ impl Adapter for OllamaAdapter {
 		if let Some(top_p) = chat_options.top_p() {
 			options.x_insert("top_p", top_p)?;
 		}
+		if let Some(top_k) = chat_options.top_k() {
+			options.x_insert("top_k", top_k)?;
+		}
+		if let Some(min_p) = chat_options.min_p() {
+			options.x_insert("min_p", min_p)?;
+		}
+		if let Some(frequency_penalty) = chat_options.frequency_penalty() {
+			options.x_insert("frequency_penalty", frequency_penalty)?;
+		}
+		if let Some(presence_penalty) = chat_options.presence_penalty() {
+			options.x_insert("presence_penalty", presence_penalty)?;
+		}
+		if let Some(repetition_penalty) = chat_options.repetition_penalty() {
+			options.x_insert("repeat_penalty", repetition_penalty)?;
+		}
 		if let Some(max_tokens) = chat_options.max_tokens() {
 			options.x_insert("num_predict", max_tokens)?;
 		}
@@ -117,14 +133,29 @@ impl Adapter for OllamaAdapter {
 			payload.x_insert("options", options)?;
 		}
 
-		if let Some(tools) = tools {
-			payload.x_insert("tools", tools)?;
+		// -- Tools
+		// Note: Ollama has no tool choice, so `ToolChoice::None` is honored by not sending the tools.
+		match (tools, chat_options.tool_choice()) {
+			(Some(_), Some(ToolChoice::None)) => (),
+			(Some(_), Some(tool_choice @ (ToolChoice::Required | ToolChoice::Tool(_)))) => {
+				return Err(Error::AdapterNotSupported {
+					adapter_kind: AdapterKind::Ollama,
+					feature: format!("tool_choice {tool_choice:?}"),
+				});
+			}
+			(Some(tools), _) => {
+				payload.x_insert("tools", tools)?;
+			}
+			(None, _) => (),
 		}
 
 		if let Some(format) = chat_options.response_format() {
-			// Note: Ollama's API uses "format": "json" for its JSON mode, so we set that if the chat options specify json mode.
-			if matches!(format, crate::chat::ChatResponseFormat::JsonMode) {
-				payload.x_insert("format", "json")?;
+			// Note: Ollama's API uses "format": "json" for its JSON mode, and the JSON schema itself for structured outputs.
+			match format {
+				crate::chat::ChatResponseFormat::JsonMode => payload.x_insert("format", "json")?,
+				crate::chat::ChatResponseFormat::JsonSpec(st_json) => {
+					payload.x_insert("format", SchemaDialect::JsonSchema.normalized(st_json.schema.clone()))?
+				}
 			}
 		}
 
@@ -197,16 +228,21 @@ impl Adapter for OllamaAdapter {
 				.map(StopReason::from),
 			usage,
 			captured_raw_body,
+			candidates: Vec::new(), // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs: None,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
 		let streamer = OllamaStreamer::new(
-			crate::webc::WebStream::new_with_delimiter(reqwest_builder, "\n"),
+			crate::webc::WebStream::new_with_delimiter(stream_request, "\n")
+				.with_retry_policy(options_set.retry_policy().cloned()),
 			model_iden.clone(),
 			options_set,
 		);
@@ -275,8 +311,15 @@ impl Adapter for OllamaAdapter {
 			provider_model_iden: model_iden,
 			usage,
 			captured_raw_body,
+			cache_hit: false,
 		})
 	}
+
+	/// Ollama error shape: `{"error": "model \"llama3\" not found, try pulling it first"}`
+	fn to_provider_error(status: Option<StatusCode>, _headers: Option<&HeaderMap>, body: &Value) -> ProviderError {
+		let message = body.x_get::<String>("error").ok();
+		ProviderError::from_parts(status, None, message, None)
+	}
 }
 
 // endregion: --- Adapter Impl
@@ -344,8 +387,15 @@ impl OllamaAdapter {
 						}));
 					}
 					ContentPart::ToolResponse(tr) => {
-						// Note: Ollama native API expects role "tool" for tool response
-						ollama_msg.x_insert("content", tr.content)?;
+						// Note: Ollama native API expects role "tool" for tool response (text, with the base64 images).
+						for binary in tr.binaries() {
+							if let BinarySource::Base64(data) = &binary.source
+								&& binary.is_image()
+							{
+								images.push(data.clone());
+							}
+						}
+						ollama_msg.x_insert("content", tr.content_text())?;
 					}
 					_ => {}
 				}
@@ -397,7 +447,7 @@ impl OllamaAdapter {
 			tool_value.x_insert("/function/description", description)?;
 		}
 		if let Some(parameters) = schema {
-			tool_value.x_insert("/function/parameters", parameters)?;
+			tool_value.x_insert("/function/parameters", SchemaDialect::JsonSchema.normalized(parameters))?;
 		}
 
 		Ok(tool_value)
@@ -1,13 +1,13 @@
 use crate::adapter::openai::OpenAIStreamer;
 use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
-	ChatOptionsSet, ChatRequest, ChatResponse, ChatStream, ChatStreamResponse, MessageContent, StopReason, ToolCall,
+	ChatCandidate, ChatOptionsSet, ChatRequest, ChatResponse, ChatStream, ChatStreamResponse, MessageContent,
+	StopReason, ToolCall,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
+use crate::webc::{EventSourceStream, StreamRequest, WebResponse};
 use crate::{Error, Result};
 use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
 use serde::Deserialize;
 use serde_json::Value;
 use value_ext::JsonValueExt;
@@ -68,61 +68,24 @@ impl Adapter for OpenAIAdapter {
 			.map(|value| OpenAIAdapter::into_usage(model_iden.adapter_kind, value))
 			.unwrap_or_default();
 
-		// -- Capture the content
-		let mut content: MessageContent = MessageContent::default();
-		let mut reasoning_content: Option<String> = None;
-		let mut stop_reason: Option<StopReason> = None;
-
-		if let Ok(Some(mut first_choice)) = body.x_take::<Option<Value>>("/choices/0") {
-			stop_reason = first_choice
-				.x_take::<Option<String>>("finish_reason")
-				.ok()
-				.flatten()
-				.map(StopReason::from);
-
-			// Check if reasoning is present
-			// Can be in two places:
-			// - /message/reasoning
-			// - /message/reasoning_content
-			// Extracted before content as some model can return reasoning without content
-			reasoning_content = first_choice
-				.x_take::<Option<String>>("/message/reasoning")
-				.ok()
-				.unwrap_or_else(|| {
-					first_choice
-						.x_take::<Option<String>>("/message/reasoning_content")
-						.ok()
-						.flatten()
-				})
-				.map(|s| s.trim().to_string());
-
-			// -- Push eventual text message
-			if let Ok(Some(mut text_content)) = first_choice.x_take::<Option<String>>("/message/content") {
-				text_content = text_content.trim().to_string();
-				// If not reasoning_content, but
-				if reasoning_content.is_none() && options_set.normalize_reasoning_content().unwrap_or_default() {
-					let (content_tmp, reasoning_content_tmp) = extract_think(text_content);
-					reasoning_content = reasoning_content_tmp;
-					text_content = content_tmp;
-				}
-
-				// After extracting reasoning_content, sometimes the content is empty.
-				if !text_content.is_empty() {
-					content.push(text_content);
-				}
-			}
+		// -- Capture the candidates (one per choice, the first one being the response content)
+		let choices: Vec<Value> = body.x_take("choices").unwrap_or_default();
+		let candidates = choices
+			.into_iter()
+			.map(|choice| Self::choice_to_candidate(choice, &options_set))
+			.collect::<Result<Vec<_>>>()?;
 
-			// -- Push eventual ToolCalls
-			if let Some(tool_calls) = first_choice
-				.x_take("/message/tool_calls")
-				.ok()
-				.map(parse_tool_calls)
-				.transpose()?
-				.map(MessageContent::from_tool_calls)
-			{
-				content.extend(tool_calls);
-			}
-		}
+		let ChatCandidate {
+			content,
+			reasoning_content,
+			stop_reason,
+			logprobs,
+		} = candidates.first().cloned().unwrap_or_else(|| ChatCandidate {
+			content: MessageContent::default(),
+			reasoning_content: None,
+			stop_reason: None,
+			logprobs: None,
+		});
 
 		Ok(ChatResponse {
 			content,
@@ -130,17 +93,22 @@ impl Adapter for OpenAIAdapter {
 			model_iden,
 			provider_model_iden,
 			stop_reason,
+			candidates,
 			usage,
 			captured_raw_body: None, // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_sets: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source =
+			EventSourceStream::new(stream_request).with_retry_policy(options_sets.retry_policy().cloned());
 		let openai_stream = OpenAIStreamer::new(event_source, model_iden.clone(), options_sets);
 		let chat_stream = ChatStream::from_inter_stream(openai_stream);
 
@@ -169,6 +137,69 @@ impl Adapter for OpenAIAdapter {
 
 // region:    --- Support
 
+impl OpenAIAdapter {
+	/// Parses one `choices[]` item (finish reason, reasoning, text, tool calls, and logprobs).
+	fn choice_to_candidate(mut choice: Value, options_set: &ChatOptionsSet<'_, '_>) -> Result<ChatCandidate> {
+		let logprobs = choice
+			.x_take::<Value>("/logprobs/content")
+			.ok()
+			.and_then(OpenAIAdapter::into_logprobs);
+
+		let stop_reason = choice
+			.x_take::<Option<String>>("finish_reason")
+			.ok()
+			.flatten()
+			.map(StopReason::from);
+
+		// Check if reasoning is present
+		// Can be in two places:
+		// - /message/reasoning
+		// - /message/reasoning_content
+		// Extracted before content as some model can return reasoning without content
+		let mut reasoning_content = choice
+			.x_take::<Option<String>>("/message/reasoning")
+			.ok()
+			.unwrap_or_else(|| choice.x_take::<Option<String>>("/message/reasoning_content").ok().flatten())
+			.map(|s| s.trim().to_string());
+
+		let mut content = MessageContent::default();
+
+		// -- Push eventual text message
+		if let Ok(Some(mut text_content)) = choice.x_take::<Option<String>>("/message/content") {
+			text_content = text_content.trim().to_string();
+			// If not reasoning_content, but
+			if reasoning_content.is_none() && options_set.normalize_reasoning_content().unwrap_or_default() {
+				let (content_tmp, reasoning_content_tmp) = extract_think(text_content);
+				reasoning_content = reasoning_content_tmp;
+				text_content = content_tmp;
+			}
+
+			// After extracting reasoning_content, sometimes the content is empty.
+			if !text_content.is_empty() {
+				content.push(text_content);
+			}
+		}
+
+		// -- Push eventual ToolCalls
+		if let Some(tool_calls) = choice
+			.x_take("/message/tool_calls")
+			.ok()
+			.map(parse_tool_calls)
+			.transpose()?
+			.map(MessageContent::from_tool_calls)
+		{
+			content.extend(tool_calls);
+		}
+
+		Ok(ChatCandidate {
+			content,
+			reasoning_content,
+			stop_reason,
+			logprobs,
+		})
+	}
+}
+
 fn extract_think(content: String) -> (String, Option<String>) {
 	let start_tag = "<think>";
 	let end_tag = "</think>";
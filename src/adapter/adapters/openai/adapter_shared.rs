@@ -1,14 +1,17 @@
 //! This is support implementation of the OpenAI Adapter which can also be called by other OpenAI Adapter Variants
 
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_unsupported_sampling, get_api_key};
 use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::{AdapterDispatcher, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
-	BinarySource, ChatOptionsSet, ChatRequest, ChatResponseFormat, ChatRole, ContentPart, ReasoningEffort, Usage,
+	BinarySource, ChatOptionsSet, ChatRequest, ChatResponseFormat, ChatRole, ContentPart, ReasoningEffort,
+	SchemaDialect, TokenLogprob, ToolChoice, ToolName, ToolResponse, TopLogprob, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::{Error, Headers, Result};
+use crate::{Error, ErrorKind, Headers, ProviderError, Result};
 use crate::{ModelIden, ServiceTarget};
+use reqwest::StatusCode;
+use serde::Deserialize;
 use serde_json::{Value, json};
 use tracing::error;
 use tracing::warn;
@@ -29,6 +32,23 @@ fn insert_openai_reasoning_effort(payload: &mut Value, effort: &ReasoningEffort)
 	Ok(())
 }
 
+fn into_openai_tool_choice(adapter_kind: AdapterKind, tool_choice: &ToolChoice) -> Result<Value> {
+	let tool_choice = match tool_choice {
+		ToolChoice::Auto => json!("auto"),
+		ToolChoice::None => json!("none"),
+		ToolChoice::Required => json!("required"),
+		ToolChoice::Tool(ToolName::Custom(name)) => json!({"type": "function", "function": {"name": name}}),
+		ToolChoice::Tool(ToolName::WebSearch) => {
+			return Err(Error::AdapterNotSupported {
+				adapter_kind,
+				feature: "tool_choice for the WebSearch tool".to_string(),
+			});
+		}
+	};
+
+	Ok(tool_choice)
+}
+
 /// Support functions for other adapters that share OpenAI APIs
 impl OpenAIAdapter {
 	pub(in crate::adapter::adapters) fn util_get_service_url(
@@ -94,7 +114,8 @@ impl OpenAIAdapter {
 
 		// -- Build the basic payload
 
-		let OpenAIRequestParts { messages, tools } = Self::into_openai_request_parts(&model, chat_req)?;
+		let strict_tools = options_set.strict_tools().unwrap_or(false);
+		let OpenAIRequestParts { messages, tools } = Self::into_openai_request_parts(&model, chat_req, strict_tools)?;
 		let mut payload = json!({
 			"model": model_name,
 			"messages": messages,
@@ -116,6 +137,12 @@ impl OpenAIAdapter {
 		// -- Tools
 		if let Some(tools) = tools {
 			payload.x_insert("/tools", tools)?;
+			if let Some(tool_choice) = options_set.tool_choice() {
+				payload.x_insert("tool_choice", into_openai_tool_choice(adapter_kind, tool_choice)?)?;
+			}
+			if let Some(parallel_tool_calls) = options_set.parallel_tool_calls() {
+				payload.x_insert("parallel_tool_calls", parallel_tool_calls)?;
+			}
 		}
 
 		// -- Add options
@@ -125,16 +152,8 @@ impl OpenAIAdapter {
 				ChatResponseFormat::JsonSpec(st_json) => {
 					// "type": "json_schema", "json_schema": {...}
 
-					let mut schema = st_json.schema.clone();
-					schema.x_walk(|parent_map, name| {
-						if name == "type" {
-							let typ = parent_map.get("type").and_then(|v| v.as_str()).unwrap_or("");
-							if typ == "object" {
-								parent_map.insert("additionalProperties".to_string(), false.into());
-							}
-						}
-						true
-					});
+					let strict = options_set.strict_response_format().unwrap_or(false);
+					let schema = Self::util_json_spec_schema(st_json.schema.clone(), strict);
 
 					Some(json!({
 						"type": "json_schema",
@@ -181,6 +200,52 @@ impl OpenAIAdapter {
 		if let Some(seed) = options_set.seed() {
 			payload.x_insert("seed", seed)?;
 		}
+		if let Some(frequency_penalty) = options_set.frequency_penalty() {
+			payload.x_insert("frequency_penalty", frequency_penalty)?;
+		}
+		if let Some(presence_penalty) = options_set.presence_penalty() {
+			payload.x_insert("presence_penalty", presence_penalty)?;
+		}
+		// Note: `top_k`, `min_p`, and `repetition_penalty` are only accepted by the open-model providers
+		if matches!(
+			adapter_kind,
+			AdapterKind::Together | AdapterKind::Fireworks | AdapterKind::OpenRouter | AdapterKind::Nebius
+		) {
+			if let Some(top_k) = options_set.top_k() {
+				payload.x_insert("top_k", top_k)?;
+			}
+			if let Some(min_p) = options_set.min_p() {
+				payload.x_insert("min_p", min_p)?;
+			}
+			if let Some(repetition_penalty) = options_set.repetition_penalty() {
+				payload.x_insert("repetition_penalty", repetition_penalty)?;
+			}
+		} else {
+			check_unsupported_sampling(
+				adapter_kind,
+				&options_set,
+				&[
+					("top_k", options_set.top_k().is_some()),
+					("min_p", options_set.min_p().is_some()),
+					("repetition_penalty", options_set.repetition_penalty().is_some()),
+				],
+			)?;
+		}
+		// Note: Several candidates are only for the non-stream chat, and only OpenAI supports them natively
+		//       (the client fans out the calls for the other adapters, if enabled).
+		if let Some(candidate_count) = options_set.candidate_count()
+			&& candidate_count > 1
+			&& !stream
+			&& adapter_kind.supports_candidate_count()
+		{
+			payload.x_insert("n", candidate_count)?;
+		}
+		if options_set.logprobs() {
+			payload.x_insert("logprobs", true)?;
+			if let Some(top_logprobs) = options_set.top_logprobs() {
+				payload.x_insert("top_logprobs", top_logprobs)?;
+			}
+		}
 		if let Some(service_tier) = options_set.service_tier()
 			&& let Some(keyword) = service_tier.as_keyword()
 		{
@@ -217,10 +282,53 @@ impl OpenAIAdapter {
 		usage
 	}
 
+	/// Parses an OpenAI logprobs array (`choices[].logprobs.content`, or the Responses API `output_text.logprobs`).
+	///
+	/// Returns None when absent or empty.
+	///
+	/// Note: Needs to be called from super::streamer and the openai_resp adapter as well
+	pub(in crate::adapter::adapters) fn into_logprobs(logprobs_value: Value) -> Option<Vec<TokenLogprob>> {
+		if logprobs_value.is_null() {
+			return None;
+		}
+		// NOTE: Like usage, we do not want to fail the response if the logprobs cannot be parsed
+		let logprobs: Vec<OpenAILogprob> = serde_json::from_value(logprobs_value)
+			.map_err(|err| error!("Fail to deserialize logprobs. Cause: {err}"))
+			.ok()?;
+		if logprobs.is_empty() {
+			return None;
+		}
+
+		let logprobs = logprobs
+			.into_iter()
+			.map(|logprob| TokenLogprob {
+				token: logprob.token,
+				logprob: logprob.logprob,
+				bytes: logprob.bytes,
+				top: logprob
+					.top_logprobs
+					.into_iter()
+					.map(|top| TopLogprob {
+						token: top.token,
+						logprob: top.logprob,
+						bytes: top.bytes,
+					})
+					.collect(),
+			})
+			.collect();
+
+		Some(logprobs)
+	}
+
 	/// Takes the genai ChatMessages and builds the OpenAIChatRequestParts
 	/// - `genai::ChatRequest.system`, if present, is added as the first message with role 'system'.
 	/// - All messages get added with the corresponding roles (tools are not supported for now)
-	fn into_openai_request_parts(_model_iden: &ModelIden, chat_req: ChatRequest) -> Result<OpenAIRequestParts> {
+	/// - With `strict_tools`, the tools are sent with `strict: true` and their schema in the OpenAI strict dialect.
+	fn into_openai_request_parts(
+		_model_iden: &ModelIden,
+		chat_req: ChatRequest,
+		strict_tools: bool,
+	) -> Result<OpenAIRequestParts> {
 		let mut messages: Vec<Value> = Vec::new();
 
 		// -- Process the system
@@ -361,15 +469,29 @@ impl OpenAIAdapter {
 
 				// Tool - For now, support only tool responses
 				ChatRole::Tool => {
+					// NOTE: OpenAI tool messages are text only, so the images of the tool responses
+					//       are sent in a follow-up user message.
+					let mut image_values: Vec<Value> = Vec::new();
 					for part in msg.content {
 						if let ContentPart::ToolResponse(tool_response) = part {
+							let image_urls = Self::util_tool_response_image_urls(&tool_response);
+							if !image_urls.is_empty() {
+								let text = format!("Images of the tool call '{}':", tool_response.call_id);
+								image_values.push(json!({"type": "text", "text": text}));
+								for image_url in image_urls {
+									image_values.push(json!({"type": "image_url", "image_url": {"url": image_url}}));
+								}
+							}
 							messages.push(json!({
 								"role": "tool",
-								"content": tool_response.content,
+								"content": Self::util_tool_response_text(&tool_response),
 								"tool_call_id": tool_response.call_id,
 							}))
 						}
 					}
+					if !image_values.is_empty() {
+						messages.push(json!({"role": "user", "content": image_values}));
+					}
 
 					// TODO: Probably need to trace/warn that this will be ignored
 				}
@@ -377,22 +499,24 @@ impl OpenAIAdapter {
 		}
 
 		// -- Process the tools
+		let schema_dialect = if strict_tools {
+			SchemaDialect::OpenAIStrict
+		} else {
+			SchemaDialect::JsonSchema
+		};
 		let tools = chat_req.tools.map(|tools| {
 			tools
 				.into_iter()
 				.map(|tool| {
-					// TODO: Need to handle the error correctly
 					// TODO: Needs to have a custom serializer (tool should not have to match to a provider)
-					// NOTE: Right now, low probability, so, we just return null if cannot convert to value.
+					let parameters = tool.schema.map(|schema| schema_dialect.normalized(schema));
 					json!({
 						"type": "function",
 						"function": {
 							"name": tool.name,
 							"description": tool.description,
-							"parameters": tool.schema,
-							// TODO: If we need to support `strict: true` we need to add additionalProperties: false into the schema
-							//       above (like structured output)
-							"strict": false,
+							"parameters": parameters,
+							"strict": strict_tools,
 						}
 					})
 				})
@@ -439,6 +563,94 @@ impl OpenAIAdapter {
 
 		Ok(models)
 	}
+
+	/// Shared OpenAI error body parsing for the OpenAI compatible adapters.
+	///
+	/// Supports the `{"error": {"message", "type", "code"}}` shape, and the inner error object itself
+	/// (as sent in-stream). The `code` is taken first, then the `type`.
+	pub(in crate::adapter) fn util_to_provider_error(status: Option<StatusCode>, body: &Value) -> ProviderError {
+		let error = match body.get("error") {
+			Some(Value::String(message)) => {
+				return ProviderError::from_parts(status, None, Some(message.to_string()), None);
+			}
+			Some(error @ Value::Object(_)) => error,
+			_ => body,
+		};
+
+		let message = error.x_get::<String>("message").ok();
+		let code = match error.get("code") {
+			Some(Value::String(code)) => Some(code.to_string()),
+			_ => error.x_get::<String>("type").ok(),
+		};
+
+		let code_kind = code.as_deref().and_then(|code| match code {
+			"context_length_exceeded" | "string_above_max_length" => Some(ErrorKind::ContextLengthExceeded),
+			"rate_limit_exceeded" | "rate_limit_error" => Some(ErrorKind::RateLimited),
+			"invalid_api_key" | "authentication_error" | "invalid_authentication" => {
+				Some(ErrorKind::AuthenticationFailed)
+			}
+			"permission_error" | "unsupported_country_region_territory" => Some(ErrorKind::PermissionDenied),
+			"model_not_found" | "not_found_error" => Some(ErrorKind::NotFound),
+			"content_filter" | "content_policy_violation" => Some(ErrorKind::ContentFiltered),
+			"engine_overloaded" | "overloaded_error" => Some(ErrorKind::Overloaded),
+			"server_error" | "internal_error" => Some(ErrorKind::ServerError),
+			"invalid_request_error" => Some(ErrorKind::InvalidRequest),
+			_ => None,
+		});
+
+		ProviderError::from_parts(status, code, message, code_kind)
+	}
+
+	/// Returns the `JsonSpec` schema for the OpenAI structured outputs:
+	/// - By default, `"additionalProperties": false` added to all the objects.
+	/// - With `strict`, normalized with `SchemaDialect::OpenAIStrict` (all the properties required, the optional ones nullable).
+	pub(in crate::adapter::adapters) fn util_json_spec_schema(schema: Value, strict: bool) -> Value {
+		if strict {
+			return SchemaDialect::OpenAIStrict.normalized(schema);
+		}
+
+		let mut schema = SchemaDialect::JsonSchema.normalized(schema);
+		schema.x_walk(|parent_map, name| {
+			if name == "type" {
+				let typ = parent_map.get("type").and_then(|v| v.as_str()).unwrap_or("");
+				if typ == "object" {
+					parent_map.insert("additionalProperties".to_string(), false.into());
+				}
+			}
+			true
+		});
+		schema
+	}
+
+	/// Returns the text of a tool response for the OpenAI tool outputs, which are text only
+	/// (the text and JSON parts, see [`ToolResponse::content_text`]).
+	///
+	/// NOTE: OpenAI has no tool error flag, so the content has to describe the error.
+	pub(in crate::adapter::adapters) fn util_tool_response_text(tool_response: &ToolResponse) -> String {
+		let text = tool_response.content_text();
+		if text.is_empty() && !tool_response.binaries().is_empty() {
+			"The tool result is in the next message.".to_string()
+		} else {
+			text
+		}
+	}
+
+	/// Returns the image URLs (or base64 data URLs) of a tool response, sent in a follow-up user message.
+	/// The other binaries are not supported in this follow-up message, and are skipped.
+	pub(in crate::adapter::adapters) fn util_tool_response_image_urls(tool_response: &ToolResponse) -> Vec<String> {
+		tool_response
+			.binaries()
+			.into_iter()
+			.filter(|binary| {
+				let is_image = binary.is_image();
+				if !is_image {
+					warn!("OpenAI doesn't support non-image binaries in tool responses, skipping it");
+				}
+				is_image
+			})
+			.map(|binary| binary.clone().into_url())
+			.collect()
+	}
 }
 
 /// Custom OpenAI structure for Adapters to use to customize
@@ -454,6 +666,25 @@ struct OpenAIRequestParts {
 	tools: Option<Vec<Value>>,
 }
 
+/// OpenAI logprob item (also used by the Responses API).
+#[derive(Deserialize)]
+struct OpenAILogprob {
+	token: String,
+	logprob: f64,
+	#[serde(default)]
+	bytes: Option<Vec<u8>>,
+	#[serde(default)]
+	top_logprobs: Vec<OpenAITopLogprob>,
+}
+
+#[derive(Deserialize)]
+struct OpenAITopLogprob {
+	token: String,
+	logprob: f64,
+	#[serde(default)]
+	bytes: Option<Vec<u8>>,
+}
+
 // endregion: --- Support
 
 // region:    --- Tests
@@ -488,7 +719,7 @@ mod tests {
 
 		let chat_req = ChatRequest::new(vec![ChatMessage::user("What's the weather in Paris?"), assistant_msg]);
 
-		let parts = OpenAIAdapter::into_openai_request_parts(&test_model(), chat_req).expect("should serialize");
+		let parts = OpenAIAdapter::into_openai_request_parts(&test_model(), chat_req, false).expect("should serialize");
 
 		// The assistant message is the second message (after user)
 		let assistant_json = &parts.messages[1];
@@ -504,7 +735,7 @@ mod tests {
 	fn test_no_reasoning_content_when_absent() {
 		let chat_req = ChatRequest::new(vec![ChatMessage::user("Hello"), ChatMessage::assistant("Hi there!")]);
 
-		let parts = OpenAIAdapter::into_openai_request_parts(&test_model(), chat_req).expect("should serialize");
+		let parts = OpenAIAdapter::into_openai_request_parts(&test_model(), chat_req, false).expect("should serialize");
 
 		let assistant_json = &parts.messages[1];
 		assert_eq!(assistant_json["role"], "assistant");
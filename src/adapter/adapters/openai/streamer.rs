@@ -227,6 +227,10 @@ impl futures::Stream for OpenAIStreamer {
 							// in the same SSE message. We must capture and emit that final content chunk
 							// before continuing to the next message, otherwise it is silently lost.
 							let content = first_choice.x_take::<Option<String>>("/delta/content").ok().flatten();
+							let logprobs = first_choice
+								.x_take::<Value>("/logprobs/content")
+								.ok()
+								.and_then(OpenAIAdapter::into_logprobs);
 							let reasoning_content = first_choice
 								.x_take::<Option<String>>("/delta/reasoning_content")
 								.ok()
@@ -242,7 +246,7 @@ impl futures::Stream for OpenAIStreamer {
 										None => self.captured_data.content = Some(content.clone()),
 									}
 								}
-								return Poll::Ready(Some(Ok(InterStreamEvent::Chunk(content))));
+								return Poll::Ready(Some(Ok(InterStreamEvent::chunk(content, logprobs))));
 							} else if let Some(reasoning_content) = reasoning_content
 								&& !reasoning_content.is_empty()
 							{
@@ -292,6 +296,10 @@ impl futures::Stream for OpenAIStreamer {
 						// Some providers (e.g., Ollama) emit reasoning in `delta.reasoning` and send empty content.
 						else {
 							let content = first_choice.x_take::<Option<String>>("/delta/content").ok().flatten();
+							let logprobs = first_choice
+								.x_take::<Value>("/logprobs/content")
+								.ok()
+								.and_then(OpenAIAdapter::into_logprobs);
 							let reasoning_content = first_choice
 								.x_take::<Option<String>>("/delta/reasoning_content")
 								.ok()
@@ -310,7 +318,7 @@ impl futures::Stream for OpenAIStreamer {
 								}
 
 								// Return the Event
-								return Poll::Ready(Some(Ok(InterStreamEvent::Chunk(content))));
+								return Poll::Ready(Some(Ok(InterStreamEvent::chunk(content, logprobs))));
 							} else if let Some(reasoning_content) = reasoning_content
 								&& !reasoning_content.is_empty()
 							{
@@ -1,17 +1,17 @@
-use crate::adapter::adapters::support::get_api_key;
+use crate::adapter::adapters::support::{check_unsupported_sampling, get_api_key};
 use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::openai_resp::OpenAIRespStreamer;
 use crate::adapter::openai_resp::resp_types::RespResponse;
 use crate::adapter::{Adapter, AdapterDispatcher, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{
 	ChatOptionsSet, ChatRequest, ChatResponse, ChatResponseFormat, ChatRole, ChatStream, ChatStreamResponse,
-	ContentPart, MessageContent, ReasoningEffort, StopReason, Tool, ToolConfig, ToolName, Usage,
+	ContentPart, MessageContent, ReasoningEffort, SchemaDialect, StopReason, TokenLogprob, Tool, ToolChoice,
+	ToolConfig, ToolName, Usage,
 };
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::{EventSourceStream, WebResponse};
+use crate::webc::{EventSourceStream, StreamRequest, WebResponse};
 use crate::{Error, Headers, Result};
 use crate::{ModelIden, ServiceTarget};
-use reqwest::RequestBuilder;
 use serde_json::{Map, Value, json};
 use value_ext::JsonValueExt;
 
@@ -94,7 +94,7 @@ impl Adapter for OpenAIRespAdapter {
 		let OpenAIRespRequestParts {
 			input_items: messages,
 			tools,
-		} = Self::into_openai_request_parts(&model, chat_req)?;
+		} = Self::into_openai_request_parts(&model, chat_req, chat_options.strict_tools().unwrap_or(false))?;
 
 		// IMPORTANT: `store = false` - To maintain consistent behavior with other chat completions, store is set to false
 		let mut payload = json!({
@@ -117,6 +117,20 @@ impl Adapter for OpenAIRespAdapter {
 		// -- Tools
 		if let Some(tools) = tools {
 			payload.x_insert("/tools", tools)?;
+			if let Some(tool_choice) = chat_options.tool_choice() {
+				payload.x_insert("tool_choice", Self::tool_choice_to_openai_tool_choice(tool_choice))?;
+			}
+			if let Some(parallel_tool_calls) = chat_options.parallel_tool_calls() {
+				payload.x_insert("parallel_tool_calls", parallel_tool_calls)?;
+			}
+		}
+
+		// -- Logprobs
+		if chat_options.logprobs() {
+			payload.x_insert("include", json!(["message.output_text.logprobs"]))?;
+			if let Some(top_logprobs) = chat_options.top_logprobs() {
+				payload.x_insert("top_logprobs", top_logprobs)?;
+			}
 		}
 
 		// -- Compute response format
@@ -125,16 +139,8 @@ impl Adapter for OpenAIRespAdapter {
 				ChatResponseFormat::JsonMode => Some(json!({"type": "json_object"})),
 				ChatResponseFormat::JsonSpec(st_json) => {
 					// "type": "json_schema", "json_schema": {...}
-					let mut schema = st_json.schema.clone();
-					schema.x_walk(|parent_map, name| {
-						if name == "type" {
-							let typ = parent_map.get("type").and_then(|v| v.as_str()).unwrap_or("");
-							if typ == "object" {
-								parent_map.insert("additionalProperties".to_string(), false.into());
-							}
-						}
-						true
-					});
+					let strict = chat_options.strict_response_format().unwrap_or(false);
+					let schema = OpenAIAdapter::util_json_spec_schema(st_json.schema.clone(), strict);
 
 					// Flatten for OpenAI Responses
 					Some(json!({
@@ -170,6 +176,18 @@ impl Adapter for OpenAIRespAdapter {
 			payload.x_insert("temperature", temperature)?;
 		}
 
+		check_unsupported_sampling(
+			model.adapter_kind,
+			&chat_options,
+			&[
+				("top_k", chat_options.top_k().is_some()),
+				("min_p", chat_options.min_p().is_some()),
+				("frequency_penalty", chat_options.frequency_penalty().is_some()),
+				("presence_penalty", chat_options.presence_penalty().is_some()),
+				("repetition_penalty", chat_options.repetition_penalty().is_some()),
+			],
+		)?;
+
 		if !chat_options.stop_sequences().is_empty() {
 			payload.x_insert("stop", chat_options.stop_sequences())?;
 		}
@@ -208,6 +226,9 @@ impl Adapter for OpenAIRespAdapter {
 		let mut content: MessageContent = MessageContent::default();
 		let reasoning_content: Option<String> = None;
 
+		// -- Capture the logprobs (of all the output texts)
+		let logprobs = Self::output_logprobs(&resp.output);
+
 		// -- Extract the content message
 		for output_item in resp.output {
 			let parts = ContentPart::from_resp_output_item(output_item)?;
@@ -222,15 +243,20 @@ impl Adapter for OpenAIRespAdapter {
 			stop_reason: Some(StopReason::from(resp.status)),
 			usage,
 			captured_raw_body,
+			candidates: Vec::new(), // Set by the client exec_chat
+			cache_hit: false,
+			pricing: None,
+			logprobs,
 		})
 	}
 
 	fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_sets: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
-		let event_source = EventSourceStream::new(reqwest_builder);
+		let event_source =
+			EventSourceStream::new(stream_request).with_retry_policy(options_sets.retry_policy().cloned());
 		let openai_stream = OpenAIRespStreamer::new(event_source, model_iden.clone(), options_sets);
 		let chat_stream = ChatStream::from_inter_stream(openai_stream);
 
@@ -293,8 +319,13 @@ impl OpenAIRespAdapter {
 	/// Takes the genai ChatMessages and builds the OpenAIChatRequestParts
 	/// - `genai::ChatRequest.system`, if present, is added as the first message with role 'system'.
 	/// - All messages get added with the corresponding roles (tools are not supported for now)
+	/// - With `strict_tools`, the function tools are sent with `strict: true` and their schema in the OpenAI strict dialect.
 	///
-	fn into_openai_request_parts(_model_iden: &ModelIden, chat_req: ChatRequest) -> Result<OpenAIRespRequestParts> {
+	fn into_openai_request_parts(
+		_model_iden: &ModelIden,
+		chat_req: ChatRequest,
+		strict_tools: bool,
+	) -> Result<OpenAIRespRequestParts> {
 		let mut input_items: Vec<Value> = Vec::new();
 
 		// -- Process the system
@@ -445,15 +476,29 @@ impl OpenAIRespAdapter {
 
 				// Tool Response (Function tool call output)
 				ChatRole::Tool => {
+					// NOTE: The images of the tool responses are sent in a follow-up user message
+					//       (as with the Chat Completions API).
+					let mut image_items: Vec<Value> = Vec::new();
 					for part in msg.content {
 						if let ContentPart::ToolResponse(tool_response) = part {
+							let image_urls = OpenAIAdapter::util_tool_response_image_urls(&tool_response);
+							if !image_urls.is_empty() {
+								let text = format!("Images of the tool call '{}':", tool_response.call_id);
+								image_items.push(json!({"type": "input_text", "text": text}));
+								for image_url in image_urls {
+									image_items.push(json!({"type": "input_image", "image_url": image_url}));
+								}
+							}
 							input_items.push(json!({
 								"type": "function_call_output",
 								"call_id": tool_response.call_id,
-								"output": tool_response.content,
+								"output": OpenAIAdapter::util_tool_response_text(&tool_response),
 							}))
 						}
 					}
+					if !image_items.is_empty() {
+						input_items.push(json!({"type": "message", "role": "user", "content": image_items}));
+					}
 
 					// TODO: Probably need to trace/warn that this will be ignored
 				}
@@ -463,13 +508,46 @@ impl OpenAIRespAdapter {
 		// -- Process the tools
 		let tools = chat_req
 			.tools
-			.map(|tools| tools.into_iter().map(Self::tool_to_openai_tool).collect::<Result<Vec<Value>>>())
+			.map(|tools| {
+				tools
+					.into_iter()
+					.map(|tool| Self::tool_to_openai_tool(tool, strict_tools))
+					.collect::<Result<Vec<Value>>>()
+			})
 			.transpose()?;
 
 		Ok(OpenAIRespRequestParts { input_items, tools })
 	}
 
-	fn tool_to_openai_tool(tool: Tool) -> Result<Value> {
+	/// Returns the logprobs of the `output_text` contents of the message output items (None if there are none).
+	fn output_logprobs(output: &[Value]) -> Option<Vec<TokenLogprob>> {
+		let logprobs: Vec<TokenLogprob> = output
+			.iter()
+			.filter(|item| item.get("type").and_then(Value::as_str) == Some("message"))
+			.filter_map(|item| item.get("content").and_then(Value::as_array))
+			.flatten()
+			.filter(|content_item| content_item.get("type").and_then(Value::as_str) == Some("output_text"))
+			.filter_map(|content_item| content_item.get("logprobs").cloned())
+			.filter_map(OpenAIAdapter::into_logprobs)
+			.flatten()
+			.collect();
+
+		(!logprobs.is_empty()).then_some(logprobs)
+	}
+
+	fn tool_choice_to_openai_tool_choice(tool_choice: &ToolChoice) -> Value {
+		match tool_choice {
+			ToolChoice::Auto => json!("auto"),
+			ToolChoice::None => json!("none"),
+			ToolChoice::Required => json!("required"),
+			// NOTE: Same as `tool_to_openai_tool`, a custom "web_search" tool is the built-in web search.
+			ToolChoice::Tool(ToolName::WebSearch) => json!({"type": "web_search"}),
+			ToolChoice::Tool(ToolName::Custom(name)) if name == "web_search" => json!({"type": "web_search"}),
+			ToolChoice::Tool(ToolName::Custom(name)) => json!({"type": "function", "name": name}),
+		}
+	}
+
+	fn tool_to_openai_tool(tool: Tool, strict: bool) -> Result<Value> {
 		let Tool {
 			name,
 			description,
@@ -500,14 +578,17 @@ impl OpenAIRespAdapter {
 				tool_value
 			}
 			name => {
+				let schema_dialect = if strict {
+					SchemaDialect::OpenAIStrict
+				} else {
+					SchemaDialect::JsonSchema
+				};
 				json!({
 					"type": "function",
 					"name": name,
 					"description": description,
-					"parameters": schema,
-					// TODO: If we need to support `strict: true` we need to add additionalProperties: false into the schema
-					//       above (like structured output)
-					"strict": false,
+					"parameters": schema.map(|schema| schema_dialect.normalized(schema)),
+					"strict": strict,
 				})
 			}
 		};
@@ -548,8 +629,8 @@ mod tests {
 			.append_message(ChatMessage::assistant("The weather is sunny."));
 
 		// Serialize to OpenAI Responses API format
-		let parts =
-			OpenAIRespAdapter::into_openai_request_parts(&model_iden, chat_req).expect("Should serialize successfully");
+		let parts = OpenAIRespAdapter::into_openai_request_parts(&model_iden, chat_req, false)
+			.expect("Should serialize successfully");
 
 		// Find the assistant message in input_items
 		let assistant_msg = parts
@@ -1,5 +1,6 @@
 use crate::adapter::adapters::support::{StreamerCapturedData, StreamerOptions};
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
+use crate::adapter::openai::OpenAIAdapter;
 use crate::adapter::openai_resp::resp_types::RespResponse;
 use crate::chat::{ChatOptionsSet, StopReason, ToolCall};
 use crate::webc::{Event, EventSourceStream};
@@ -50,6 +51,8 @@ enum RespStreamEvent {
 		#[serde(default)]
 		_content_index: usize,
 		delta: String,
+		#[serde(default)]
+		logprobs: Value,
 	},
 
 	#[serde(rename = "response.reasoning_text.delta")]
@@ -145,14 +148,15 @@ impl futures::Stream for OpenAIRespStreamer {
 							continue;
 						}
 
-						RespStreamEvent::OutputTextDelta { delta, .. } => {
+						RespStreamEvent::OutputTextDelta { delta, logprobs, .. } => {
 							if self.options.capture_content {
 								match self.captured_data.content {
 									Some(ref mut c) => c.push_str(&delta),
 									None => self.captured_data.content = Some(delta.clone()),
 								}
 							}
-							return Poll::Ready(Some(Ok(InterStreamEvent::Chunk(delta))));
+							let logprobs = OpenAIAdapter::into_logprobs(logprobs);
+							return Poll::Ready(Some(Ok(InterStreamEvent::chunk(delta, logprobs))));
 						}
 
 						RespStreamEvent::ReasoningTextDelta { delta, .. } => {
@@ -2,9 +2,11 @@
 //! It should be private to the `crate::adapter::adapters` module.
 
 use crate::ModelIden;
-use crate::chat::{ChatOptionsSet, Usage};
+use crate::adapter::AdapterKind;
+use crate::chat::{ChatOptionsSet, SamplingStrictness, Usage};
 use crate::resolver::AuthData;
 use crate::{Error, Result};
+use tracing::debug;
 
 pub fn get_api_key(auth: AuthData, model: &ModelIden) -> Result<String> {
 	auth.single_key_value().map_err(|resolver_error| Error::Resolver {
@@ -13,6 +15,34 @@ pub fn get_api_key(auth: AuthData, model: &ModelIden) -> Result<String> {
 	})
 }
 
+// region:    --- Sampling Parameters
+
+/// Checks the sampling parameters the adapter does not support, given as `(name, is_set)`.
+///
+/// The ones set are dropped (`SamplingStrictness::Lenient`) or fail the request (`SamplingStrictness::Strict`).
+pub fn check_unsupported_sampling(
+	adapter_kind: AdapterKind,
+	options_set: &ChatOptionsSet<'_, '_>,
+	unsupported: &[(&str, bool)],
+) -> Result<()> {
+	for (name, _) in unsupported.iter().filter(|(_, is_set)| *is_set) {
+		match options_set.sampling_strictness() {
+			SamplingStrictness::Strict => {
+				return Err(Error::AdapterNotSupported {
+					adapter_kind,
+					feature: format!("sampling parameter '{name}'"),
+				});
+			}
+			SamplingStrictness::Lenient => {
+				debug!("genai - sampling parameter '{name}' not supported by {adapter_kind}, dropped")
+			}
+		}
+	}
+	Ok(())
+}
+
+// endregion: --- Sampling Parameters
+
 // region:    --- StreamerChatOptions
 
 #[derive(Debug)]
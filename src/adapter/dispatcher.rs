@@ -23,10 +23,12 @@ use crate::adapter::{Adapter, AdapterKind, ServiceType, WebRequestData};
 use crate::chat::{ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
 use crate::embed::{EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::{AuthData, Endpoint};
-use crate::webc::WebResponse;
-use crate::{Error, ModelIden};
+use crate::webc::{StreamRequest, WebResponse};
+use crate::{Error, ModelIden, ProviderError};
 use crate::{Result, ServiceTarget};
-use reqwest::RequestBuilder;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
 
 /// A construct that allows dispatching calls to the Adapters.
 ///
@@ -212,31 +214,31 @@ impl AdapterDispatcher {
 
 	pub fn to_chat_stream(
 		model_iden: ModelIden,
-		reqwest_builder: RequestBuilder,
+		stream_request: StreamRequest,
 		options_set: ChatOptionsSet<'_, '_>,
 	) -> Result<ChatStreamResponse> {
 		match model_iden.adapter_kind {
-			AdapterKind::OpenAI => OpenAIAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Gemini => GeminiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Anthropic => AnthropicAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Fireworks => FireworksAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Together => TogetherAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Groq => GroqAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Mimo => MimoAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Nebius => NebiusAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Xai => XaiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::DeepSeek => DeepSeekAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Zai => ZaiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::BigModel => BigModelAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Aliyun => AliyunAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Cohere => CohereAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Ollama => OllamaAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::OpenRouter => OpenRouterAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Cerebras => CerebrasAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Bedrock => BedrockAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Kimi => KimiAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
-			AdapterKind::Zhipu => ZhipuAdapter::to_chat_stream(model_iden, reqwest_builder, options_set),
+			AdapterKind::OpenAI => OpenAIAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Gemini => GeminiAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Anthropic => AnthropicAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Fireworks => FireworksAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Together => TogetherAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Groq => GroqAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Mimo => MimoAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Nebius => NebiusAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Xai => XaiAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::DeepSeek => DeepSeekAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Zai => ZaiAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::BigModel => BigModelAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Aliyun => AliyunAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Cohere => CohereAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Ollama => OllamaAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::OpenRouter => OpenRouterAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Cerebras => CerebrasAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Bedrock => BedrockAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Kimi => KimiAdapter::to_chat_stream(model_iden, stream_request, options_set),
+			AdapterKind::Zhipu => ZhipuAdapter::to_chat_stream(model_iden, stream_request, options_set),
 		}
 	}
 
@@ -306,4 +308,35 @@ impl AdapterDispatcher {
 			AdapterKind::Zhipu => ZhipuAdapter::to_embed_response(model_iden, web_response, options_set),
 		}
 	}
+
+	pub fn to_provider_error(
+		kind: AdapterKind,
+		status: Option<StatusCode>,
+		headers: Option<&HeaderMap>,
+		body: &Value,
+	) -> ProviderError {
+		match kind {
+			AdapterKind::OpenAI => OpenAIAdapter::to_provider_error(status, headers, body),
+			AdapterKind::OpenAIResp => OpenAIRespAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Gemini => GeminiAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Anthropic => AnthropicAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Fireworks => FireworksAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Together => TogetherAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Groq => GroqAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Mimo => MimoAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Nebius => NebiusAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Xai => XaiAdapter::to_provider_error(status, headers, body),
+			AdapterKind::DeepSeek => DeepSeekAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Zai => ZaiAdapter::to_provider_error(status, headers, body),
+			AdapterKind::BigModel => BigModelAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Aliyun => AliyunAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Cohere => CohereAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Ollama => OllamaAdapter::to_provider_error(status, headers, body),
+			AdapterKind::OpenRouter => OpenRouterAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Cerebras => CerebrasAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Bedrock => BedrockAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Kimi => KimiAdapter::to_provider_error(status, headers, body),
+			AdapterKind::Zhipu => ZhipuAdapter::to_provider_error(status, headers, body),
+		}
+	}
 }
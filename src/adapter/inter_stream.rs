@@ -33,8 +33,23 @@ pub struct InterStreamEnd {
 pub enum InterStreamEvent {
 	Start,
 	Chunk(String),
+	/// Text chunk with the log-probabilities of its tokens (when requested).
+	ChunkWithLogprobs {
+		content: String,
+		logprobs: Vec<crate::chat::TokenLogprob>,
+	},
 	ReasoningChunk(String),
 	ThoughtSignatureChunk(String),
 	ToolCallChunk(crate::chat::ToolCall),
 	End(InterStreamEnd),
 }
+
+impl InterStreamEvent {
+	/// Returns a `ChunkWithLogprobs` when there are log-probabilities, otherwise a `Chunk`.
+	pub fn chunk(content: String, logprobs: Option<Vec<crate::chat::TokenLogprob>>) -> Self {
+		match logprobs {
+			Some(logprobs) => Self::ChunkWithLogprobs { content, logprobs },
+			None => Self::Chunk(content),
+		}
+	}
+}
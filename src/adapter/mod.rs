@@ -21,6 +21,7 @@ pub(crate) use adapter_types::*;
 pub(crate) use dispatcher::*;
 
 pub use adapter_kind::*;
+pub use adapter_types::{ServiceType, WebRequestData};
 
 // -- Crate modules
 pub(crate) mod inter_stream;
@@ -5,10 +5,14 @@
 //! Note 1: Additional client-level defaults may be added over time.
 //! Note 2: Kept separate from `ChatRequest` for easier reuse and composition.
 
-use crate::Headers;
+use crate::adapter::AdapterKind;
+use crate::chat::ToolChoice;
 use crate::chat::chat_req_response_format::ChatResponseFormat;
 use crate::{Error, Result};
+use crate::{Headers, RetryPolicy};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::ops::Deref;
 
 /// Options considered by all `Client::exec_*` chat calls.
@@ -29,6 +33,25 @@ pub struct ChatOptions {
 	/// Sequences that halt generation when encountered.
 	pub stop_sequences: Vec<String>,
 
+	/// Top-k sampling: sample only from the k most likely tokens (if supported).
+	pub top_k: Option<u32>,
+
+	/// Min-p sampling: minimum token probability, relative to the most likely token (if supported).
+	pub min_p: Option<f64>,
+
+	/// Penalizes tokens by how often they already appeared (OpenAI-style, typically -2.0 to 2.0), if supported.
+	pub frequency_penalty: Option<f64>,
+
+	/// Penalizes tokens that already appeared (OpenAI-style, typically -2.0 to 2.0), if supported.
+	pub presence_penalty: Option<f64>,
+
+	/// Multiplicative penalty for repeated tokens (1.0 means none), as in vLLM, llama.cpp, or Ollama `repeat_penalty`.
+	pub repetition_penalty: Option<f64>,
+
+	/// How the adapters handle the sampling parameters above they do not support
+	/// (default `SamplingStrictness::Lenient`, which drops them).
+	pub sampling_strictness: Option<SamplingStrictness>,
+
 	// -- Stream Options
 	/// (streaming) Capture usage metadata; available in `StreamEnd.captured_usage`.
 	pub capture_usage: Option<bool>,
@@ -45,11 +68,52 @@ pub struct ChatOptions {
 	/// Capture the raw HTTP body (primarily for debugging/inspection).
 	pub capture_raw_body: Option<bool>,
 
+	/// Whether, and which, tools the model may call (only applied when the request has tools).
+	pub tool_choice: Option<ToolChoice>,
+
+	/// Allow (or not) several tool calls in one response (OpenAI `parallel_tool_calls`, Anthropic `disable_parallel_tool_use`).
+	/// Ignored by the adapters without this control.
+	pub parallel_tool_calls: Option<bool>,
+
+	/// Send the function tools with `strict: true` (OpenAI and OpenAI Responses only, ignored by the other adapters).
+	/// The tool schemas are then normalized with `SchemaDialect::OpenAIStrict`.
+	pub strict_tools: Option<bool>,
+
+	/// Normalize the `JsonSpec` schema with `SchemaDialect::OpenAIStrict` (OpenAI and OpenAI Responses only),
+	/// making all the properties required, and the optional ones nullable.
+	/// By default, only `"additionalProperties": false` is added to the objects.
+	pub strict_response_format: Option<bool>,
+
 	/// Desired response format (e.g., `ChatResponseFormat::JsonMode` for OpenAI-style JSON mode).
 	///
 	/// Note: Additional formats may be added in the future.
 	pub response_format: Option<ChatResponseFormat>,
 
+	/// Maximum re-prompts of `Client::exec_chat_typed` when the output does not parse (default: 1).
+	pub max_output_repairs: Option<u32>,
+
+	/// Maximum model calls of `Client::run_tools` (default: 10).
+	pub max_tool_iterations: Option<u32>,
+
+	/// Maximum total tokens (summed over the model calls) of `Client::run_tools` (no limit by default).
+	pub max_tool_run_tokens: Option<u32>,
+
+	/// Return the log-probabilities of the output tokens (`ChatResponse.logprobs` and stream chunks `logprobs`).
+	pub logprobs: Option<bool>,
+
+	/// Number of most likely tokens to return at each position, with their log-probabilities (implies `logprobs`).
+	pub top_logprobs: Option<u8>,
+
+	/// Number of candidate completions to generate (`ChatResponse.candidates`), e.g., for best-of-n or voting.
+	///
+	/// Mapped to OpenAI `n` and Gemini `candidateCount`. Only for `exec_chat` (streams return a single candidate).
+	/// Other adapters fail with `Error::AdapterNotSupported` unless `candidate_fan_out` is set.
+	pub candidate_count: Option<u32>,
+
+	/// When the adapter does not support `candidate_count` natively, send that many concurrent calls
+	/// and merge them into one `ChatResponse` (candidates in call order, usage summed).
+	pub candidate_fan_out: Option<bool>,
+
 	// -- Reasoning options
 	/// Extract -style reasoning blocks into `ChatResponse.reasoning_content` when present.
 	pub normalize_reasoning_content: Option<bool>,
@@ -68,6 +132,22 @@ pub struct ChatOptions {
 
 	/// Additional HTTP headers to include with the request.
 	pub extra_headers: Option<Headers>,
+
+	/// JSON deep-merged into the provider request payload, for the provider features not mapped by genai
+	/// (e.g., OpenAI `metadata`, OpenRouter `provider`, Gemini `safetySettings`).
+	///
+	/// Must be a JSON object. Objects are merged recursively, other values replace the payload ones,
+	/// and `null` removes the key.
+	pub extra_body: Option<Value>,
+
+	/// Like `extra_body`, but only for the requests of an adapter kind (merged after `extra_body`).
+	pub adapter_extra_bodies: Option<HashMap<AdapterKind, Value>>,
+
+	/// Retry policy for failed provider calls (overrides `ClientConfig` retry policy).
+	pub retry_policy: Option<RetryPolicy>,
+
+	/// Use the `ClientConfig` response cache, if any (default: true). Set to `false` to bypass it.
+	pub response_cache: Option<bool>,
 }
 
 /// Chainable Setters
@@ -90,6 +170,42 @@ impl ChatOptions {
 		self
 	}
 
+	/// Sets top-k sampling.
+	pub fn with_top_k(mut self, value: u32) -> Self {
+		self.top_k = Some(value);
+		self
+	}
+
+	/// Sets min-p sampling.
+	pub fn with_min_p(mut self, value: f64) -> Self {
+		self.min_p = Some(value);
+		self
+	}
+
+	/// Sets the frequency penalty.
+	pub fn with_frequency_penalty(mut self, value: f64) -> Self {
+		self.frequency_penalty = Some(value);
+		self
+	}
+
+	/// Sets the presence penalty.
+	pub fn with_presence_penalty(mut self, value: f64) -> Self {
+		self.presence_penalty = Some(value);
+		self
+	}
+
+	/// Sets the repetition penalty.
+	pub fn with_repetition_penalty(mut self, value: f64) -> Self {
+		self.repetition_penalty = Some(value);
+		self
+	}
+
+	/// Sets how the unsupported sampling parameters are handled.
+	pub fn with_sampling_strictness(mut self, value: SamplingStrictness) -> Self {
+		self.sampling_strictness = Some(value);
+		self
+	}
+
 	/// Enables or disables capturing usage in streaming mode.
 	pub fn with_capture_usage(mut self, value: bool) -> Self {
 		self.capture_usage = Some(value);
@@ -126,6 +242,54 @@ impl ChatOptions {
 		self
 	}
 
+	/// Sets `tool_choice`.
+	pub fn with_tool_choice(mut self, value: ToolChoice) -> Self {
+		self.tool_choice = Some(value);
+		self
+	}
+
+	/// Sets `parallel_tool_calls`.
+	pub fn with_parallel_tool_calls(mut self, value: bool) -> Self {
+		self.parallel_tool_calls = Some(value);
+		self
+	}
+
+	/// Sets `strict_tools`.
+	pub fn with_strict_tools(mut self, value: bool) -> Self {
+		self.strict_tools = Some(value);
+		self
+	}
+
+	/// Sets `strict_response_format`.
+	pub fn with_strict_response_format(mut self, value: bool) -> Self {
+		self.strict_response_format = Some(value);
+		self
+	}
+
+	/// Sets `logprobs`.
+	pub fn with_logprobs(mut self, value: bool) -> Self {
+		self.logprobs = Some(value);
+		self
+	}
+
+	/// Sets `top_logprobs`.
+	pub fn with_top_logprobs(mut self, value: u8) -> Self {
+		self.top_logprobs = Some(value);
+		self
+	}
+
+	/// Sets `candidate_count`.
+	pub fn with_candidate_count(mut self, value: u32) -> Self {
+		self.candidate_count = Some(value);
+		self
+	}
+
+	/// Sets `candidate_fan_out`.
+	pub fn with_candidate_fan_out(mut self, value: bool) -> Self {
+		self.candidate_fan_out = Some(value);
+		self
+	}
+
 	/// Enables or disables normalization of reasoning content (e.g., `<think>...</think>`).
 	pub fn with_normalize_reasoning_content(mut self, value: bool) -> Self {
 		self.normalize_reasoning_content = Some(value);
@@ -138,6 +302,24 @@ impl ChatOptions {
 		self
 	}
 
+	/// Sets the maximum re-prompts of `Client::exec_chat_typed`.
+	pub fn with_max_output_repairs(mut self, value: u32) -> Self {
+		self.max_output_repairs = Some(value);
+		self
+	}
+
+	/// Sets the maximum model calls of `Client::run_tools`.
+	pub fn with_max_tool_iterations(mut self, value: u32) -> Self {
+		self.max_tool_iterations = Some(value);
+		self
+	}
+
+	/// Sets the maximum total tokens of `Client::run_tools`.
+	pub fn with_max_tool_run_tokens(mut self, value: u32) -> Self {
+		self.max_tool_run_tokens = Some(value);
+		self
+	}
+
 	/// Sets the reasoning effort hint.
 	pub fn with_reasoning_effort(mut self, value: ReasoningEffort) -> Self {
 		self.reasoning_effort = Some(value);
@@ -168,6 +350,32 @@ impl ChatOptions {
 		self
 	}
 
+	/// Sets the JSON deep-merged into the request payload (see `extra_body`).
+	pub fn with_extra_body(mut self, extra_body: Value) -> Self {
+		self.extra_body = Some(extra_body);
+		self
+	}
+
+	/// Sets the JSON deep-merged into the request payload for one adapter kind only (see `adapter_extra_bodies`).
+	pub fn with_adapter_extra_body(mut self, adapter_kind: AdapterKind, extra_body: Value) -> Self {
+		self.adapter_extra_bodies
+			.get_or_insert_with(HashMap::new)
+			.insert(adapter_kind, extra_body);
+		self
+	}
+
+	/// Sets the retry policy.
+	pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = Some(retry_policy);
+		self
+	}
+
+	/// Enables or disables the use of the client response cache.
+	pub fn with_response_cache(mut self, value: bool) -> Self {
+		self.response_cache = Some(value);
+		self
+	}
+
 	// -- Deprecated
 
 	/// Deprecated: use `with_response_format(ChatResponseFormat::JsonMode)`.
@@ -421,6 +629,20 @@ impl std::str::FromStr for ServiceTier {
 
 // endregion: --- ServiceTier
 
+// region:    --- SamplingStrictness
+
+/// How an adapter handles the sampling parameters (`top_k`, `min_p`, penalties) it does not support.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SamplingStrictness {
+	/// Silently drop the unsupported parameters (default).
+	#[default]
+	Lenient,
+	/// Fail the request with `Error::AdapterNotSupported`.
+	Strict,
+}
+
+// endregion: --- SamplingStrictness
+
 // region:    --- ChatOptionsSet
 
 /// This is an internal crate struct to resolve the ChatOptions value in a cascading manner.
@@ -430,6 +652,8 @@ impl std::str::FromStr for ServiceTier {
 pub(crate) struct ChatOptionsSet<'a, 'b> {
 	client: Option<&'a ChatOptions>,
 	chat: Option<&'b ChatOptions>,
+	/// The `ClientConfig` retry policy, last fallback for `retry_policy()`
+	client_retry_policy: Option<&'a RetryPolicy>,
 }
 
 impl<'a, 'b> ChatOptionsSet<'a, 'b> {
@@ -441,6 +665,10 @@ impl<'a, 'b> ChatOptionsSet<'a, 'b> {
 		self.chat = options;
 		self
 	}
+	pub fn with_client_retry_policy(mut self, retry_policy: Option<&'a RetryPolicy>) -> Self {
+		self.client_retry_policy = retry_policy;
+		self
+	}
 }
 
 impl ChatOptionsSet<'_, '_> {
@@ -462,6 +690,43 @@ impl ChatOptionsSet<'_, '_> {
 			.or_else(|| self.client.and_then(|client| client.top_p))
 	}
 
+	pub fn top_k(&self) -> Option<u32> {
+		self.chat
+			.and_then(|chat| chat.top_k)
+			.or_else(|| self.client.and_then(|client| client.top_k))
+	}
+
+	pub fn min_p(&self) -> Option<f64> {
+		self.chat
+			.and_then(|chat| chat.min_p)
+			.or_else(|| self.client.and_then(|client| client.min_p))
+	}
+
+	pub fn frequency_penalty(&self) -> Option<f64> {
+		self.chat
+			.and_then(|chat| chat.frequency_penalty)
+			.or_else(|| self.client.and_then(|client| client.frequency_penalty))
+	}
+
+	pub fn presence_penalty(&self) -> Option<f64> {
+		self.chat
+			.and_then(|chat| chat.presence_penalty)
+			.or_else(|| self.client.and_then(|client| client.presence_penalty))
+	}
+
+	pub fn repetition_penalty(&self) -> Option<f64> {
+		self.chat
+			.and_then(|chat| chat.repetition_penalty)
+			.or_else(|| self.client.and_then(|client| client.repetition_penalty))
+	}
+
+	pub fn sampling_strictness(&self) -> SamplingStrictness {
+		self.chat
+			.and_then(|chat| chat.sampling_strictness)
+			.or_else(|| self.client.and_then(|client| client.sampling_strictness))
+			.unwrap_or_default()
+	}
+
 	pub fn stop_sequences(&self) -> &[String] {
 		self.chat
 			.map(|chat| chat.stop_sequences.deref())
@@ -505,6 +770,74 @@ impl ChatOptionsSet<'_, '_> {
 			.or_else(|| self.client.and_then(|client| client.response_format.as_ref()))
 	}
 
+	pub fn max_output_repairs(&self) -> Option<u32> {
+		self.chat
+			.and_then(|chat| chat.max_output_repairs)
+			.or_else(|| self.client.and_then(|client| client.max_output_repairs))
+	}
+
+	pub fn max_tool_iterations(&self) -> Option<u32> {
+		self.chat
+			.and_then(|chat| chat.max_tool_iterations)
+			.or_else(|| self.client.and_then(|client| client.max_tool_iterations))
+	}
+
+	pub fn max_tool_run_tokens(&self) -> Option<u32> {
+		self.chat
+			.and_then(|chat| chat.max_tool_run_tokens)
+			.or_else(|| self.client.and_then(|client| client.max_tool_run_tokens))
+	}
+
+	pub fn tool_choice(&self) -> Option<&ToolChoice> {
+		self.chat
+			.and_then(|chat| chat.tool_choice.as_ref())
+			.or_else(|| self.client.and_then(|client| client.tool_choice.as_ref()))
+	}
+
+	pub fn parallel_tool_calls(&self) -> Option<bool> {
+		self.chat
+			.and_then(|chat| chat.parallel_tool_calls)
+			.or_else(|| self.client.and_then(|client| client.parallel_tool_calls))
+	}
+
+	pub fn strict_tools(&self) -> Option<bool> {
+		self.chat
+			.and_then(|chat| chat.strict_tools)
+			.or_else(|| self.client.and_then(|client| client.strict_tools))
+	}
+
+	pub fn strict_response_format(&self) -> Option<bool> {
+		self.chat
+			.and_then(|chat| chat.strict_response_format)
+			.or_else(|| self.client.and_then(|client| client.strict_response_format))
+	}
+
+	/// Returns true when the log-probabilities are requested (`logprobs`, or `top_logprobs` set).
+	pub fn logprobs(&self) -> bool {
+		self.chat
+			.and_then(|chat| chat.logprobs)
+			.or_else(|| self.client.and_then(|client| client.logprobs))
+			.unwrap_or_else(|| self.top_logprobs().is_some())
+	}
+
+	pub fn top_logprobs(&self) -> Option<u8> {
+		self.chat
+			.and_then(|chat| chat.top_logprobs)
+			.or_else(|| self.client.and_then(|client| client.top_logprobs))
+	}
+
+	pub fn candidate_count(&self) -> Option<u32> {
+		self.chat
+			.and_then(|chat| chat.candidate_count)
+			.or_else(|| self.client.and_then(|client| client.candidate_count))
+	}
+
+	pub fn candidate_fan_out(&self) -> Option<bool> {
+		self.chat
+			.and_then(|chat| chat.candidate_fan_out)
+			.or_else(|| self.client.and_then(|client| client.candidate_fan_out))
+	}
+
 	pub fn normalize_reasoning_content(&self) -> Option<bool> {
 		self.chat
 			.and_then(|chat| chat.normalize_reasoning_content)
@@ -542,6 +875,34 @@ impl ChatOptionsSet<'_, '_> {
 			.or_else(|| self.client.and_then(|client| client.extra_headers.as_ref()))
 	}
 
+	pub fn extra_body(&self) -> Option<&Value> {
+		self.chat
+			.and_then(|chat| chat.extra_body.as_ref())
+			.or_else(|| self.client.and_then(|client| client.extra_body.as_ref()))
+	}
+
+	pub fn adapter_extra_body(&self, adapter_kind: AdapterKind) -> Option<&Value> {
+		self.chat
+			.and_then(|chat| chat.adapter_extra_bodies.as_ref()?.get(&adapter_kind))
+			.or_else(|| {
+				self.client
+					.and_then(|client| client.adapter_extra_bodies.as_ref()?.get(&adapter_kind))
+			})
+	}
+
+	pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+		self.chat
+			.and_then(|chat| chat.retry_policy.as_ref())
+			.or_else(|| self.client.and_then(|client| client.retry_policy.as_ref()))
+			.or(self.client_retry_policy)
+	}
+
+	pub fn response_cache(&self) -> Option<bool> {
+		self.chat
+			.and_then(|chat| chat.response_cache)
+			.or_else(|| self.client.and_then(|client| client.response_cache))
+	}
+
 	/// Returns true only if there is a ChatResponseFormat::JsonMode
 	#[deprecated(note = "Use .response_format()")]
 	#[allow(unused)]
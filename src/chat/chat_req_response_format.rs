@@ -1,3 +1,5 @@
+use crate::chat::validate_json_schema;
+use crate::{Error, Result};
 use derive_more::From;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -52,3 +54,21 @@ impl JsonSpec {
 		self
 	}
 }
+
+/// Validation
+impl JsonSpec {
+	/// Validates the value against the spec `schema` (see `validate_json_schema` for the supported subset).
+	///
+	/// Returns `Error::JsonSchemaViolations` with all the violations, located by their path in the value.
+	pub fn validate(&self, value: &Value) -> Result<()> {
+		let violations = validate_json_schema(&self.schema, value);
+		if violations.is_empty() {
+			Ok(())
+		} else {
+			Err(Error::JsonSchemaViolations {
+				target: format!("JSON output '{}'", self.name),
+				violations,
+			})
+		}
+	}
+}
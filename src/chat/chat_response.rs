@@ -2,8 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::ModelIden;
-use crate::chat::{ChatStream, MessageContent, ToolCall, Usage};
+use crate::chat::{
+	ChatMessage, ChatStream, JsonSpec, MessageContent, Pricing, SchemaViolation, TokenLogprob, ToolCall, ToolResponse,
+	Usage, strip_code_fences,
+};
+use crate::{Error, ModelIden, Result};
+use serde_json::Value;
 
 // region:    --- StopReason
 
@@ -96,6 +100,27 @@ impl std::fmt::Display for StopReason {
 
 // endregion: --- StopReason
 
+// region:    --- ChatCandidate
+
+/// One candidate completion of a chat request (see `ChatOptions::with_candidate_count`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCandidate {
+	/// Message content of this candidate.
+	pub content: MessageContent,
+
+	/// Optional reasoning content of this candidate.
+	pub reasoning_content: Option<String>,
+
+	/// Normalised stop reason of this candidate.
+	pub stop_reason: Option<StopReason>,
+
+	/// Log-probabilities of the output tokens of this candidate (only when requested with `ChatOptions::with_logprobs`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub logprobs: Option<Vec<TokenLogprob>>,
+}
+
+// endregion: --- ChatCandidate
+
 // region:    --- ChatResponse
 
 /// Response returned by a non-streaming chat request.
@@ -120,12 +145,30 @@ pub struct ChatResponse {
 	/// Normalised stop reason (see [`StopReason`]).
 	pub stop_reason: Option<StopReason>,
 
+	/// All the candidate completions, the first one being `content`, `reasoning_content`, and `stop_reason`.
+	///
+	/// Has more than one candidate only when requested with `ChatOptions::with_candidate_count`.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub candidates: Vec<ChatCandidate>,
+
 	/// Token usage reported by the provider.
 	pub usage: Usage,
 
+	/// Log-probabilities of the output tokens (only when requested with `ChatOptions::with_logprobs`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub logprobs: Option<Vec<TokenLogprob>>,
+
 	/// IMPORTANT: (since 0.5.3) This is populated at the client.exec_chat when the options capture_raw_body is set to true
 	/// Raw response body (only if asked via options.capture_raw_body)
 	pub captured_raw_body: Option<serde_json::Value>,
+
+	/// True when this response was served from the client response cache (no provider call).
+	#[serde(default)]
+	pub cache_hit: bool,
+
+	/// Pricing of the model, set by the client from its `PricingRegistry` (if any). See `ChatResponse::cost`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub pricing: Option<Pricing>,
 }
 
 // Getters
@@ -150,6 +193,22 @@ impl ChatResponse {
 		self.content.into_texts()
 	}
 
+	/// Returns the cost (USD) of this call from its `usage` and `pricing` (None when the pricing is unknown).
+	///
+	/// A response served from the client response cache costs nothing.
+	pub fn cost(&self) -> Option<f64> {
+		let pricing = self.pricing.as_ref()?;
+		if self.cache_hit {
+			return Some(0.);
+		}
+		Some(self.usage.cost(pricing))
+	}
+
+	/// Returns the first text segment of each candidate (None for a candidate without text).
+	pub fn candidate_texts(&self) -> Vec<Option<&str>> {
+		self.candidates.iter().map(|candidate| candidate.content.first_text()).collect()
+	}
+
 	/// Returns all captured tool calls.
 	pub fn tool_calls(&self) -> Vec<&ToolCall> {
 		self.content.tool_calls()
@@ -161,6 +220,96 @@ impl ChatResponse {
 	}
 }
 
+/// Candidates
+impl ChatResponse {
+	/// Returns the primary response (`content`, `reasoning_content`, `stop_reason`) as a candidate.
+	pub fn to_candidate(&self) -> ChatCandidate {
+		ChatCandidate {
+			content: self.content.clone(),
+			reasoning_content: self.reasoning_content.clone(),
+			stop_reason: self.stop_reason.clone(),
+			logprobs: self.logprobs.clone(),
+		}
+	}
+}
+
+/// Structured output
+impl ChatResponse {
+	/// Parses the first text as JSON (code fences stripped) and validates it against the spec schema.
+	///
+	/// Returns the parsed value, or `Error::JsonSchemaViolations` (a text that is not JSON is a violation at `$`).
+	pub fn validate_json(&self, spec: &JsonSpec) -> Result<Value> {
+		let text = strip_code_fences(self.first_text().unwrap_or_default());
+		let value: Value = serde_json::from_str(text).map_err(|err| Error::JsonSchemaViolations {
+			target: format!("JSON output '{}'", spec.name),
+			violations: vec![SchemaViolation {
+				path: "$".to_string(),
+				message: format!("invalid JSON: {err}"),
+			}],
+		})?;
+		spec.validate(&value)?;
+		Ok(value)
+	}
+}
+
+/// Tool use
+impl ChatResponse {
+	/// Pairs each tool call of this response with its tool response (by `call_id`), in the tool calls order.
+	///
+	/// Fails if a tool call has no response, or if a response does not match any tool call.
+	/// When the same `call_id` has several responses, the last one is used.
+	pub fn pair_tool_responses(
+		&self,
+		tool_responses: impl IntoIterator<Item = ToolResponse>,
+	) -> Result<Vec<(&ToolCall, ToolResponse)>> {
+		let tool_calls = self.tool_calls();
+		let mut slots: Vec<Option<ToolResponse>> = vec![None; tool_calls.len()];
+
+		for tool_response in tool_responses {
+			let idx = tool_calls
+				.iter()
+				.position(|call| call.call_id == tool_response.call_id)
+				.ok_or_else(|| Error::ToolResponseUnmatched {
+					call_id: tool_response.call_id.clone(),
+				})?;
+			slots[idx] = Some(tool_response);
+		}
+
+		tool_calls
+			.into_iter()
+			.zip(slots)
+			.map(|(call, response)| match response {
+				Some(response) => Ok((call, response)),
+				None => Err(Error::ToolResponseMissing {
+					call_id: call.call_id.clone(),
+					fn_name: call.fn_name.clone(),
+				}),
+			})
+			.collect()
+	}
+
+	/// Returns the two messages continuing a conversation after this tool-use response:
+	/// the assistant turn (this response content, including thought signatures and all the tool calls),
+	/// and the tool turn with every tool response, in the tool calls order.
+	///
+	/// To be appended with `ChatRequest::append_messages`. Fails like `pair_tool_responses`.
+	pub fn tool_use_messages(
+		&self,
+		tool_responses: impl IntoIterator<Item = ToolResponse>,
+	) -> Result<Vec<ChatMessage>> {
+		let tool_responses: Vec<ToolResponse> = self
+			.pair_tool_responses(tool_responses)?
+			.into_iter()
+			.map(|(_, response)| response)
+			.collect();
+
+		Ok(vec![
+			ChatMessage::assistant(self.content.clone()),
+			ChatMessage::from(tool_responses),
+		])
+	}
+}
+
 /// Deprecated Getters
 impl ChatResponse {
 	/// Deprecated: use `first_text` or `texts`.
@@ -1,8 +1,13 @@
 use crate::adapter::inter_stream::{InterStreamEnd, InterStreamEvent};
-use crate::chat::{ChatMessage, ContentPart, MessageContent, StopReason, ToolCall, Usage};
+use crate::chat::{
+	ChatMessage, ContentPart, MessageContent, Pricing, SpendTracker, StopReason, TokenLogprob, ToolCall, Usage,
+};
+use crate::client::RateLimitPermit;
+use crate::{ClientMiddleware, Error, ModelIden};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 type InterStreamType = Pin<Box<dyn Stream<Item = crate::Result<InterStreamEvent>> + Send>>;
@@ -10,11 +15,68 @@ type InterStreamType = Pin<Box<dyn Stream<Item = crate::Result<InterStreamEvent>
 /// A stream of chat events produced by a streaming chat request.
 pub struct ChatStream {
 	inter_stream: InterStreamType,
+	middlewares: Option<StreamMiddlewares>,
+	/// Reconciled with the `StreamEnd.captured_usage`, if any.
+	rate_limit_permit: Option<RateLimitPermit>,
+	/// Records the cost of the `StreamEnd.captured_usage`, if any.
+	stream_spend: Option<StreamSpend>,
+}
+
+/// The client spend tracker, with the model and pricing of the stream.
+struct StreamSpend {
+	model_iden: ModelIden,
+	pricing: Pricing,
+	spend_tracker: SpendTracker,
+}
+
+/// The client middlewares to call on each event, with the model of the stream.
+struct StreamMiddlewares {
+	model_iden: ModelIden,
+	middlewares: Vec<Arc<dyn ClientMiddleware>>,
 }
 
 impl ChatStream {
 	pub(crate) fn new(inter_stream: InterStreamType) -> Self {
-		ChatStream { inter_stream }
+		ChatStream {
+			inter_stream,
+			middlewares: None,
+			rate_limit_permit: None,
+			stream_spend: None,
+		}
+	}
+
+	/// Sets the client middlewares called (in order) on each event of this stream.
+	pub(crate) fn with_middlewares(
+		mut self,
+		model_iden: ModelIden,
+		middlewares: Vec<Arc<dyn ClientMiddleware>>,
+	) -> Self {
+		self.middlewares = (!middlewares.is_empty()).then_some(StreamMiddlewares {
+			model_iden,
+			middlewares,
+		});
+		self
+	}
+
+	/// Sets the client rate limiter permit of this stream request.
+	pub(crate) fn with_rate_limit_permit(mut self, rate_limit_permit: RateLimitPermit) -> Self {
+		self.rate_limit_permit = Some(rate_limit_permit);
+		self
+	}
+
+	/// Sets the client spend tracker, recording the cost of this stream at its end.
+	pub(crate) fn with_spend_tracker(
+		mut self,
+		model_iden: ModelIden,
+		pricing: Pricing,
+		spend_tracker: SpendTracker,
+	) -> Self {
+		self.stream_spend = Some(StreamSpend {
+			model_iden,
+			pricing,
+			spend_tracker,
+		});
+		self
 	}
 
 	pub(crate) fn from_inter_stream<T>(inter_stream: T) -> Self
@@ -36,20 +98,65 @@ impl Stream for ChatStream {
 
 		match Pin::new(&mut this.inter_stream).poll_next(cx) {
 			Poll::Ready(Some(Ok(event))) => {
-				let chat_event = match event {
+				let mut chat_event = match event {
 					InterStreamEvent::Start => ChatStreamEvent::Start,
-					InterStreamEvent::Chunk(content) => ChatStreamEvent::Chunk(StreamChunk { content }),
-					InterStreamEvent::ReasoningChunk(content) => {
-						ChatStreamEvent::ReasoningChunk(StreamChunk { content })
-					}
+					InterStreamEvent::Chunk(content) => ChatStreamEvent::Chunk(StreamChunk {
+						content,
+						logprobs: None,
+					}),
+					InterStreamEvent::ChunkWithLogprobs { content, logprobs } => ChatStreamEvent::Chunk(StreamChunk {
+						content,
+						logprobs: Some(logprobs),
+					}),
+					InterStreamEvent::ReasoningChunk(content) => ChatStreamEvent::ReasoningChunk(StreamChunk {
+						content,
+						logprobs: None,
+					}),
 					InterStreamEvent::ThoughtSignatureChunk(content) => {
-						ChatStreamEvent::ThoughtSignatureChunk(StreamChunk { content })
+						ChatStreamEvent::ThoughtSignatureChunk(StreamChunk {
+							content,
+							logprobs: None,
+						})
 					}
 					InterStreamEvent::ToolCallChunk(tool_call) => {
 						ChatStreamEvent::ToolCallChunk(ToolChunk { tool_call })
 					}
 					InterStreamEvent::End(inter_end) => ChatStreamEvent::End(inter_end.into()),
 				};
+
+				if let (ChatStreamEvent::End(stream_end), Some(permit)) = (&chat_event, &this.rate_limit_permit)
+					&& let Some(usage) = &stream_end.captured_usage
+				{
+					permit.record_usage(usage);
+				}
+
+				if let (ChatStreamEvent::End(stream_end), Some(stream_spend)) = (&chat_event, &this.stream_spend)
+					&& let Some(usage) = &stream_end.captured_usage
+				{
+					let StreamSpend {
+						model_iden,
+						pricing,
+						spend_tracker,
+					} = stream_spend;
+					spend_tracker.record(model_iden, usage.cost(pricing));
+				}
+
+				if let Some(StreamMiddlewares {
+					model_iden,
+					middlewares,
+				}) = &this.middlewares
+				{
+					for middleware in middlewares {
+						if let Err(error) = middleware.on_stream_event(model_iden, &mut chat_event) {
+							return Poll::Ready(Some(Err(Error::Middleware {
+								model_iden: model_iden.clone(),
+								cause: error.to_string(),
+								error,
+							})));
+						}
+					}
+				}
+
 				Poll::Ready(Some(Ok(chat_event)))
 			}
 			Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
@@ -86,18 +193,24 @@ pub enum ChatStreamEvent {
 	End(StreamEnd),
 }
 
-/// Content of `ChatStreamEvent::Chunk`.
-/// Currently text only.
+/// Content of `ChatStreamEvent::Chunk` (also used for the reasoning and thought signature chunks).
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StreamChunk {
 	/// Text content.
 	pub content: String,
+
+	/// Log-probabilities of the chunk tokens (only for `Chunk`, when requested with `ChatOptions::with_logprobs`).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub logprobs: Option<Vec<TokenLogprob>>,
 }
 
 /// Content of `ChatStreamEvent::ToolCallChunk`.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ToolChunk {
 	/// The tool call.
+	/// For the adapters streaming the arguments in fragments (e.g., OpenAI), `fn_arguments` is a JSON string
+	/// holding the raw arguments fragment of this chunk, or the arguments accumulated so far for this call
+	/// when `ChatOptions::with_capture_tool_calls` is enabled.
 	pub tool_call: ToolCall,
 }
 
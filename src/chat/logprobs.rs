@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Log-probability of a generated token (requested with `ChatOptions::with_logprobs`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenLogprob {
+	/// The token text.
+	pub token: String,
+	/// Log-probability of the token.
+	pub logprob: f64,
+	/// UTF-8 bytes of the token, when provided (OpenAI), useful when a character spans several tokens.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub bytes: Option<Vec<u8>>,
+	/// The most likely tokens at this position (requested with `ChatOptions::with_top_logprobs`).
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub top: Vec<TopLogprob>,
+}
+
+/// One of the most likely tokens at a position of a `TokenLogprob`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TopLogprob {
+	/// The token text.
+	pub token: String,
+	/// Log-probability of the token.
+	pub logprob: f64,
+	/// UTF-8 bytes of the token, when provided (OpenAI).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub bytes: Option<Vec<u8>>,
+}
@@ -11,8 +11,14 @@ mod chat_request;
 mod chat_response;
 mod chat_stream;
 mod content_part;
+mod logprobs;
 mod message_content;
+mod partial_json;
+mod pricing;
+mod schema;
+mod structured_stream;
 mod tool;
+mod typed_output;
 mod usage;
 
 // -- Flatten
@@ -24,8 +30,14 @@ pub use chat_request::*;
 pub use chat_response::*;
 pub use chat_stream::*;
 pub use content_part::*;
+pub use logprobs::*;
 pub use message_content::*;
+pub use partial_json::*;
+pub use pricing::*;
+pub use schema::*;
+pub use structured_stream::*;
 pub use tool::*;
+pub use typed_output::*;
 pub use usage::*;
 
 #[doc = "Printing helpers for chat requests and streaming output."]
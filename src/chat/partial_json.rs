@@ -0,0 +1,243 @@
+//! Best-effort parsing of incomplete JSON, for streamed structured outputs and tool call arguments.
+
+use serde_json::Value;
+
+/// Parses the beginning of a JSON object or array (e.g., the text streamed so far) into a best-effort partial value.
+///
+/// - The text before the first `{` or `[` (e.g., a markdown code fence) is skipped.
+/// - An unterminated string value is closed (so streamed string values grow), and the open containers are closed.
+/// - Incomplete trailing tokens (object key without value, partial `true`/`false`/`null`, dangling `,` or `:`) are dropped.
+///
+/// Returns `None` when no object or array has started yet.
+///
+/// ```
+/// use genai::chat::parse_partial_json;
+/// use serde_json::json;
+///
+/// let partial = parse_partial_json(r#"{"name": "Ada", "langs": ["en", "fr"#).unwrap();
+/// assert_eq!(partial, json!({"name": "Ada", "langs": ["en", "fr"]}));
+/// ```
+pub fn parse_partial_json(text: &str) -> Option<Value> {
+	let start = text.find(['{', '['])?;
+	let completed = complete_partial_json(&text[start..])?;
+	serde_json::from_str(&completed).ok()
+}
+
+// region:    --- Support
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+	/// In an object, `expect_key` is true before a key (after `{` or `,`).
+	Object {
+		expect_key: bool,
+	},
+	Array,
+}
+
+/// A cut point of the text which is valid JSON once the open containers are closed.
+struct SafePoint {
+	end: usize,
+	stack: Vec<Frame>,
+}
+
+/// Returns the JSON text completed from the partial text (starting with `{` or `[`), or None if nothing is usable.
+fn complete_partial_json(text: &str) -> Option<String> {
+	let bytes = text.as_bytes();
+	let mut stack: Vec<Frame> = Vec::new();
+	let mut safe: Option<SafePoint> = None;
+	let mut idx = 0;
+
+	while idx < bytes.len() {
+		match bytes[idx] {
+			b'{' | b'[' => {
+				stack.push(if bytes[idx] == b'{' {
+					Frame::Object { expect_key: true }
+				} else {
+					Frame::Array
+				});
+				idx += 1;
+				safe = Some(SafePoint {
+					end: idx,
+					stack: stack.clone(),
+				});
+			}
+			b'}' | b']' => {
+				stack.pop();
+				idx += 1;
+				if stack.is_empty() {
+					// Complete top-level value, ignore what follows (e.g., closing code fence).
+					return Some(text[..idx].to_string());
+				}
+				safe = Some(SafePoint {
+					end: idx,
+					stack: stack.clone(),
+				});
+			}
+			b'"' => {
+				let is_key = matches!(stack.last(), Some(Frame::Object { expect_key: true }));
+				match scan_string(bytes, idx) {
+					StringScan::Complete(end) => {
+						idx = end;
+						if is_key {
+							if let Some(Frame::Object { expect_key }) = stack.last_mut() {
+								*expect_key = false;
+							}
+						} else {
+							safe = Some(SafePoint {
+								end: idx,
+								stack: stack.clone(),
+							});
+						}
+					}
+					StringScan::Unterminated(valid_end) => {
+						if is_key {
+							break;
+						}
+						// Close the string value received so far.
+						let mut completed = text[..valid_end].to_string();
+						completed.push('"');
+						return Some(close_containers(completed, &stack));
+					}
+				}
+			}
+			b',' => {
+				if let Some(Frame::Object { expect_key }) = stack.last_mut() {
+					*expect_key = true;
+				}
+				idx += 1;
+			}
+			b'-' | b'0'..=b'9' => {
+				let end = scan_number(bytes, idx);
+				if end == bytes.len() {
+					// Trailing number, keep its valid prefix (e.g., `1.` -> `1`).
+					let number = text[idx..end].trim_end_matches(['.', 'e', 'E', '+', '-']);
+					if !number.is_empty() && number != "-" {
+						let completed = format!("{}{number}", &text[..idx]);
+						return Some(close_containers(completed, &stack));
+					}
+					break;
+				}
+				idx = end;
+				safe = Some(SafePoint {
+					end: idx,
+					stack: stack.clone(),
+				});
+			}
+			b't' | b'f' | b'n' => {
+				let literal: &[u8] = match bytes[idx] {
+					b't' => b"true",
+					b'f' => b"false",
+					_ => b"null",
+				};
+				if !bytes[idx..].starts_with(literal) {
+					break;
+				}
+				idx += literal.len();
+				safe = Some(SafePoint {
+					end: idx,
+					stack: stack.clone(),
+				});
+			}
+			_ => idx += 1,
+		}
+	}
+
+	let SafePoint { end, stack } = safe?;
+	Some(close_containers(text[..end].to_string(), &stack))
+}
+
+enum StringScan {
+	/// End index (after the closing quote).
+	Complete(usize),
+	/// End index of the content that can be safely closed (without a trailing partial escape).
+	Unterminated(usize),
+}
+
+/// Scans the string starting at the `start` quote.
+fn scan_string(bytes: &[u8], start: usize) -> StringScan {
+	let mut idx = start + 1;
+	while idx < bytes.len() {
+		match bytes[idx] {
+			b'"' => return StringScan::Complete(idx + 1),
+			b'\\' => {
+				let escape_len = if bytes.get(idx + 1) == Some(&b'u') { 6 } else { 2 };
+				if idx + escape_len > bytes.len() {
+					return StringScan::Unterminated(idx);
+				}
+				idx += escape_len;
+			}
+			_ => idx += 1,
+		}
+	}
+	StringScan::Unterminated(bytes.len())
+}
+
+/// Returns the end index of the number starting at `start`.
+fn scan_number(bytes: &[u8], start: usize) -> usize {
+	let mut idx = start;
+	while idx < bytes.len() && matches!(bytes[idx], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') {
+		idx += 1;
+	}
+	idx
+}
+
+/// Removes a trailing `,`, and closes the open containers.
+fn close_containers(mut json: String, stack: &[Frame]) -> String {
+	let trimmed_len = json.trim_end().trim_end_matches(',').len();
+	json.truncate(trimmed_len);
+
+	for frame in stack.iter().rev() {
+		json.push(match frame {
+			Frame::Object { .. } => '}',
+			Frame::Array => ']',
+		});
+	}
+	json
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_parse_partial_json_prefixes() {
+		let full = r#"{"name": "Ada", "age": 36, "tags": ["a", "b"], "ok": true, "addr": {"city": "London"}}"#;
+		let cases = [
+			(r#"{"#, json!({})),
+			(r#"{"na"#, json!({})),
+			(r#"{"name": "#, json!({})),
+			(r#"{"name": "A"#, json!({"name": "A"})),
+			(r#"{"name": "Ada", "age": 3"#, json!({"name": "Ada", "age": 3})),
+			(
+				r#"{"name": "Ada", "age": 36, "tags": ["a", "#,
+				json!({"name": "Ada", "age": 36, "tags": ["a"]}),
+			),
+			(
+				r#"{"name": "Ada", "age": 36, "tags": ["a", "b"], "ok": tr"#,
+				json!({"name": "Ada", "age": 36, "tags": ["a", "b"]}),
+			),
+			(full, serde_json::from_str(full).unwrap()),
+		];
+
+		for (text, expected) in cases {
+			assert_eq!(parse_partial_json(text), Some(expected), "text: {text}");
+		}
+	}
+
+	#[test]
+	fn test_parse_partial_json_code_fence_and_escapes() {
+		assert_eq!(parse_partial_json("```json\n{\"a\": \"x\\"), Some(json!({"a": "x"})));
+		assert_eq!(
+			parse_partial_json("```json\n{\"a\": \"\\u00e9\"}\n```"),
+			Some(json!({"a": "é"}))
+		);
+		assert_eq!(parse_partial_json("Here is"), None);
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,365 @@
+//! Model pricing, to compute the cost of a `Usage` (see `Usage::cost` and `ChatResponse::cost`),
+//! and the `SpendTracker` accumulating the cost of the client calls.
+
+use crate::adapter::AdapterKind;
+use crate::chat::Usage;
+use crate::{Error, ModelIden, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// region:    --- Pricing
+
+/// Prices of a model, in USD per million tokens.
+///
+/// Optional prices fall back to the base price of their kind when not set
+/// (`cached_input` and `cache_write_5m` to `input`, `cache_write_1h` to `cache_write_5m`, `reasoning` to `output`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pricing {
+	/// Non-cached input (prompt) tokens.
+	pub input: f64,
+	/// Output (completion) tokens.
+	pub output: f64,
+	/// Input tokens read from the prompt cache.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cached_input: Option<f64>,
+	/// Input tokens written to the 5-minute prompt cache (also used when the cache TTL is not reported).
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cache_write_5m: Option<f64>,
+	/// Input tokens written to the 1-hour prompt cache.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cache_write_1h: Option<f64>,
+	/// Reasoning (thinking) tokens, which are part of the output tokens.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub reasoning: Option<f64>,
+}
+
+/// Constructors & Setters
+impl Pricing {
+	/// Creates the pricing from the input and output prices (USD per million tokens).
+	pub fn new(input: f64, output: f64) -> Self {
+		Self {
+			input,
+			output,
+			..Default::default()
+		}
+	}
+
+	pub fn with_cached_input(mut self, price: f64) -> Self {
+		self.cached_input = Some(price);
+		self
+	}
+
+	pub fn with_cache_write_5m(mut self, price: f64) -> Self {
+		self.cache_write_5m = Some(price);
+		self
+	}
+
+	pub fn with_cache_write_1h(mut self, price: f64) -> Self {
+		self.cache_write_1h = Some(price);
+		self
+	}
+
+	pub fn with_reasoning(mut self, price: f64) -> Self {
+		self.reasoning = Some(price);
+		self
+	}
+}
+
+/// Effective prices (with the fallbacks applied)
+impl Pricing {
+	pub(crate) fn cached_input_price(&self) -> f64 {
+		self.cached_input.unwrap_or(self.input)
+	}
+
+	pub(crate) fn cache_write_5m_price(&self) -> f64 {
+		self.cache_write_5m.unwrap_or(self.input)
+	}
+
+	pub(crate) fn cache_write_1h_price(&self) -> f64 {
+		self.cache_write_1h.unwrap_or_else(|| self.cache_write_5m_price())
+	}
+
+	pub(crate) fn reasoning_price(&self) -> f64 {
+		self.reasoning.unwrap_or(self.output)
+	}
+}
+
+// endregion: --- Pricing
+
+// region:    --- PricingRegistry
+
+/// Registry of `Pricing` by `ModelIden` (set with `ClientBuilder::with_pricing_registry`).
+///
+/// Can be loaded from JSON, keyed by adapter kind (lowercase) and then model name:
+///
+/// ```json
+/// {
+///   "openai": {
+///     "gpt-4o-mini": { "input": 0.15, "output": 0.6, "cached_input": 0.075 }
+///   },
+///   "anthropic": {
+///     "claude-sonnet-4-5": { "input": 3, "output": 15, "cached_input": 0.3, "cache_write_5m": 3.75, "cache_write_1h": 6 }
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PricingRegistry {
+	/// Keyed by the model name string (a `ModelName` hashes differently whether static or shared).
+	prices: HashMap<(AdapterKind, String), Pricing>,
+}
+
+/// Constructors & Setters
+impl PricingRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Parses the registry from a JSON string (see the format above).
+	pub fn from_json(json: &str) -> Result<Self> {
+		let value: Value = serde_json::from_str(json)?;
+		Self::from_json_value(value)
+	}
+
+	/// Builds the registry from a JSON value (see the format above).
+	pub fn from_json_value(value: Value) -> Result<Self> {
+		let Value::Object(adapters) = value else {
+			return Err(Error::PricingParsing {
+				cause: "the pricing JSON must be an object keyed by adapter kind".to_string(),
+			});
+		};
+
+		let mut registry = Self::new();
+		for (adapter_name, models) in adapters {
+			let adapter_kind = AdapterKind::from_lower_str(&adapter_name).ok_or_else(|| Error::PricingParsing {
+				cause: format!("unknown adapter kind '{adapter_name}'"),
+			})?;
+			let models: HashMap<String, Pricing> =
+				serde_json::from_value(models).map_err(|err| Error::PricingParsing {
+					cause: format!("invalid pricing for adapter '{adapter_name}': {err}"),
+				})?;
+			for (model_name, pricing) in models {
+				registry.prices.insert((adapter_kind, model_name), pricing);
+			}
+		}
+
+		Ok(registry)
+	}
+
+	/// Sets (or replaces) the pricing of a model.
+	pub fn with_pricing(mut self, model_iden: ModelIden, pricing: Pricing) -> Self {
+		self.prices.insert(model_key(&model_iden), pricing);
+		self
+	}
+}
+
+/// Getters
+impl PricingRegistry {
+	/// Returns the pricing of the model, if any.
+	pub fn get(&self, model_iden: &ModelIden) -> Option<&Pricing> {
+		self.prices.get(&model_key(model_iden))
+	}
+
+	/// Returns the cost (USD) of the usage for the model, if its pricing is known.
+	pub fn cost(&self, model_iden: &ModelIden, usage: &Usage) -> Option<f64> {
+		self.get(model_iden).map(|pricing| usage.cost(pricing))
+	}
+}
+
+// endregion: --- PricingRegistry
+
+// region:    --- SpendTracker
+
+/// Accumulated spend (USD) of the client calls (set with `ClientBuilder::with_spend_tracker`).
+///
+/// The client records the cost of each call of a priced model (see `ChatResponse::cost`),
+/// including the calls made by `run_tools`, `exec_chat_with_fallbacks`, and `exec_chat_typed`.
+/// Streams are recorded at their end from `StreamEnd.captured_usage`
+/// (requires `ChatOptions::with_capture_usage(true)`).
+/// Response cache hits and calls of models without a pricing are not recorded.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct SpendTracker {
+	state: Arc<Mutex<SpendState>>,
+}
+
+#[derive(Debug, Default)]
+struct SpendState {
+	total: f64,
+	call_count: u64,
+	/// Keyed by the model name string (see `PricingRegistry`).
+	models: HashMap<(AdapterKind, String), f64>,
+}
+
+/// Constructors & Recording
+impl SpendTracker {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds the cost (USD) of one call of the model.
+	pub fn record(&self, model_iden: &ModelIden, cost: f64) {
+		let Ok(mut state) = self.state.lock() else {
+			return;
+		};
+		state.total += cost;
+		state.call_count += 1;
+		*state.models.entry(model_key(model_iden)).or_default() += cost;
+	}
+
+	/// Resets the accumulated spend.
+	pub fn reset(&self) {
+		if let Ok(mut state) = self.state.lock() {
+			*state = SpendState::default();
+		}
+	}
+}
+
+/// Getters
+impl SpendTracker {
+	/// Returns the total spend (USD) since the creation (or the last reset).
+	pub fn total(&self) -> f64 {
+		self.state.lock().map(|state| state.total).unwrap_or_default()
+	}
+
+	/// Returns the number of recorded calls.
+	pub fn call_count(&self) -> u64 {
+		self.state.lock().map(|state| state.call_count).unwrap_or_default()
+	}
+
+	/// Returns the spend (USD) of the model.
+	pub fn model_total(&self, model_iden: &ModelIden) -> f64 {
+		self.state
+			.lock()
+			.ok()
+			.and_then(|state| state.models.get(&model_key(model_iden)).copied())
+			.unwrap_or_default()
+	}
+}
+
+// endregion: --- SpendTracker
+
+// region:    --- Support
+
+/// The map key of a model: its adapter kind and model name string.
+fn model_key(model_iden: &ModelIden) -> (AdapterKind, String) {
+	(model_iden.adapter_kind, model_iden.model_name.to_string())
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ModelName;
+	use crate::chat::{CacheCreationDetails, CompletionTokensDetails, PromptTokensDetails};
+
+	#[test]
+	fn test_pricing_registry_from_json() {
+		let registry = PricingRegistry::from_json(
+			r#"{
+				"openai": { "gpt-4o-mini": { "input": 0.15, "output": 0.6, "cached_input": 0.075 } },
+				"anthropic": { "claude-sonnet-4-5": { "input": 3, "output": 15, "cache_write_1h": 6 } }
+			}"#,
+		)
+		.unwrap();
+
+		let pricing = registry
+			.get(&ModelIden::new(
+				AdapterKind::OpenAI,
+				ModelName::from_static("gpt-4o-mini"),
+			))
+			.unwrap();
+		assert_eq!(pricing, &Pricing::new(0.15, 0.6).with_cached_input(0.075));
+
+		let pricing = registry
+			.get(&ModelIden::new(AdapterKind::Anthropic, "claude-sonnet-4-5"))
+			.unwrap();
+		assert_eq!(pricing.cache_write_5m_price(), 3.0);
+		assert_eq!(pricing.cache_write_1h_price(), 6.0);
+		assert_eq!(pricing.reasoning_price(), 15.0);
+	}
+
+	#[test]
+	fn test_pricing_usage_cost_with_cache_breakdown() {
+		// Anthropic-like usage: 1000 prompt tokens = 100 input + 600 cache read + 200 (5m) + 100 (1h) cache creation.
+		let usage = Usage {
+			prompt_tokens: Some(1000),
+			prompt_tokens_details: Some(PromptTokensDetails {
+				cache_creation_tokens: Some(300),
+				cache_creation_details: Some(CacheCreationDetails {
+					ephemeral_5m_tokens: Some(200),
+					ephemeral_1h_tokens: Some(100),
+				}),
+				cached_tokens: Some(600),
+				audio_tokens: None,
+			}),
+			completion_tokens: Some(100),
+			..Default::default()
+		};
+		let pricing = Pricing::new(3., 15.)
+			.with_cached_input(0.3)
+			.with_cache_write_5m(3.75)
+			.with_cache_write_1h(6.);
+
+		let expected = (100. * 3. + 600. * 0.3 + 200. * 3.75 + 100. * 6. + 100. * 15.) / 1_000_000.;
+		assert!((usage.cost(&pricing) - expected).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_pricing_usage_cost_with_reasoning() {
+		// Gemini-like normalized usage: 300 completion tokens, including 200 reasoning tokens.
+		let usage = Usage {
+			prompt_tokens: Some(1000),
+			completion_tokens: Some(300),
+			completion_tokens_details: Some(CompletionTokensDetails {
+				reasoning_tokens: Some(200),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+
+		let expected = (1000. * 1. + 100. * 4. + 200. * 8.) / 1_000_000.;
+		assert!((usage.cost(&Pricing::new(1., 4.).with_reasoning(8.)) - expected).abs() < 1e-12);
+		// Without a reasoning price, reasoning tokens are priced as output.
+		let expected = (1000. * 1. + 300. * 4.) / 1_000_000.;
+		assert!((usage.cost(&Pricing::new(1., 4.)) - expected).abs() < 1e-12);
+	}
+
+	#[test]
+	fn test_pricing_spend_tracker_accumulates() {
+		let tracker = SpendTracker::new();
+		let shared = tracker.clone();
+		let gpt = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+		let claude = ModelIden::new(AdapterKind::Anthropic, "claude-sonnet-4-5");
+
+		tracker.record(&gpt, 0.25);
+		shared.record(&gpt, 0.5);
+		shared.record(&claude, 1.);
+
+		assert_eq!(tracker.total(), 1.75);
+		assert_eq!(tracker.call_count(), 3);
+		assert_eq!(tracker.model_total(&gpt), 0.75);
+		assert_eq!(tracker.model_total(&ModelIden::new(AdapterKind::OpenAI, "o4-mini")), 0.);
+		// A static model name matches the same shared one.
+		let static_gpt = ModelIden::new(AdapterKind::OpenAI, ModelName::from_static("gpt-4o-mini"));
+		assert_eq!(tracker.model_total(&static_gpt), 0.75);
+
+		shared.reset();
+		assert_eq!(tracker.total(), 0.);
+		assert_eq!(tracker.call_count(), 0);
+	}
+
+	#[test]
+	fn test_pricing_registry_from_json_unknown_adapter() {
+		let res = PricingRegistry::from_json(r#"{ "nope": {} }"#);
+
+		assert!(matches!(res, Err(Error::PricingParsing { .. })));
+	}
+}
+
+// endregion: --- Tests
@@ -84,7 +84,7 @@ async fn print_chat_stream_inner(
 						}
 					}
 
-					ChatStreamEvent::Chunk(StreamChunk { content }) => {
+					ChatStreamEvent::Chunk(StreamChunk { content, .. }) => {
 						if print_events && first_chunk {
 							first_chunk = false;
 							(
@@ -97,7 +97,7 @@ async fn print_chat_stream_inner(
 						}
 					}
 
-					ChatStreamEvent::ReasoningChunk(StreamChunk { content }) => {
+					ChatStreamEvent::ReasoningChunk(StreamChunk { content, .. }) => {
 						if print_events && first_reasoning_chunk {
 							first_reasoning_chunk = false;
 							(
@@ -110,7 +110,7 @@ async fn print_chat_stream_inner(
 						}
 					}
 
-					ChatStreamEvent::ThoughtSignatureChunk(StreamChunk { content }) => {
+					ChatStreamEvent::ThoughtSignatureChunk(StreamChunk { content, .. }) => {
 						if print_events && first_thought_signature_chunk {
 							first_thought_signature_chunk = false;
 							(
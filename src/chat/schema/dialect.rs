@@ -0,0 +1,115 @@
+//! Provider JSON Schema dialects, used by the adapters to normalize `Tool.schema` and `JsonSpec.schema`.
+
+use super::openai_strict::to_openai_strict_schema;
+use super::openapi::to_openapi_schema;
+use serde_json::Value;
+
+/// JSON Schema meta keywords, removed by all the dialects (some providers reject them).
+const META_KEYWORDS: &[&str] = &["$schema", "$id"];
+
+/// The JSON Schema flavor accepted by a provider, for tool parameters and structured outputs.
+///
+/// Each adapter applies its dialect to the `Tool.schema` and `JsonSpec.schema` before sending them,
+/// so the same schema (e.g., generated by `schemars`) can be used with all the providers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaDialect {
+	/// Standard JSON Schema (OpenAI non-strict tools, Ollama, ...), only the meta keywords are removed.
+	JsonSchema,
+
+	/// OpenAI strict mode (structured outputs and `strict: true` function tools):
+	/// `"additionalProperties": false` on all objects, and all properties `required` (optional ones made nullable).
+	OpenAIStrict,
+
+	/// OpenAPI 3.0.3 Schema Object subset (Gemini):
+	/// `$ref` inlined, composites flattened, nullable types normalized, and no `additionalProperties`.
+	OpenApi,
+
+	/// Anthropic and Bedrock tool input schemas:
+	/// the root must be an `"type": "object"` schema, without `allOf`/`anyOf`/`oneOf`.
+	ToolInputObject,
+}
+
+impl SchemaDialect {
+	/// Normalizes the JSON schema in-place for this dialect.
+	pub fn normalize(self, schema: &mut Value) {
+		if let Value::Object(map) = schema {
+			for keyword in META_KEYWORDS {
+				map.remove(*keyword);
+			}
+		}
+
+		match self {
+			SchemaDialect::JsonSchema => (),
+			SchemaDialect::OpenAIStrict => to_openai_strict_schema(schema),
+			SchemaDialect::OpenApi => to_openapi_schema(schema),
+			SchemaDialect::ToolInputObject => to_tool_input_object(schema),
+		}
+	}
+
+	/// Returns the normalized JSON schema for this dialect.
+	pub fn normalized(self, mut schema: Value) -> Value {
+		self.normalize(&mut schema);
+		schema
+	}
+}
+
+// region:    --- Support
+
+/// Flattens a single variant root composite (e.g., the schemars `Option<T>` idiom), and sets the root `type`.
+fn to_tool_input_object(schema: &mut Value) {
+	let Value::Object(map) = schema else {
+		return;
+	};
+
+	for keyword in ["allOf", "anyOf", "oneOf"] {
+		let Some(Value::Array(variants)) = map.get(keyword) else {
+			continue;
+		};
+		let mut non_null = variants
+			.iter()
+			.filter(|v| v.get("type").and_then(Value::as_str) != Some("null"));
+		if let (Some(Value::Object(inner)), None) = (non_null.next(), non_null.next()) {
+			let inner = inner.clone();
+			map.remove(keyword);
+			for (k, v) in inner {
+				map.entry(k).or_insert(v);
+			}
+		}
+	}
+
+	if !map.contains_key("type") {
+		map.insert("type".to_string(), "object".into());
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_schema_dialect_tool_input_object() {
+		let schema = json!({
+			"$schema": "https://json-schema.org/draft/2020-12/schema",
+			"anyOf": [
+				{ "properties": { "city": { "type": "string" } }, "required": ["city"] },
+				{ "type": "null" }
+			]
+		});
+
+		let schema = SchemaDialect::ToolInputObject.normalized(schema);
+
+		let expected = json!({
+			"type": "object",
+			"properties": { "city": { "type": "string" } },
+			"required": ["city"]
+		});
+		assert_eq!(schema, expected);
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,176 @@
+//! `GenaiSchema`, the JSON Schema of a Rust type, for tool parameters and structured outputs.
+
+use super::openai_strict::make_nullable;
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+/// A Rust type with a JSON Schema, for `Tool::with_schema` and `JsonSpec::new`.
+///
+/// The schema is standard JSON Schema (no `$ref`), normalized by each adapter for its provider dialect
+/// (see `SchemaDialect`).
+///
+/// Usually derived with `#[derive(genai::GenaiSchema)]` (`macros` feature), which supports:
+/// - Structs, with the doc comments as descriptions, and the `Option` (or `#[serde(default)]`) fields not required.
+/// - Enums, unit-only as string enums, and the serde externally tagged, internally tagged (`tag`),
+///   and `untagged` representations.
+/// - The serde `rename`, `rename_all`, and `skip` attributes.
+///
+/// NOTE: Recursive types are not supported (the schemas are inlined).
+pub trait GenaiSchema {
+	/// Returns the JSON Schema of this type.
+	fn genai_schema() -> Value;
+}
+
+// region:    --- Std Impls
+
+impl GenaiSchema for String {
+	fn genai_schema() -> Value {
+		json!({"type": "string"})
+	}
+}
+
+impl GenaiSchema for str {
+	fn genai_schema() -> Value {
+		json!({"type": "string"})
+	}
+}
+
+impl GenaiSchema for char {
+	fn genai_schema() -> Value {
+		json!({"type": "string", "minLength": 1, "maxLength": 1})
+	}
+}
+
+impl GenaiSchema for bool {
+	fn genai_schema() -> Value {
+		json!({"type": "boolean"})
+	}
+}
+
+macro_rules! impl_genai_schema_integer {
+	($($ty:ty),*) => {
+		$(impl GenaiSchema for $ty {
+			fn genai_schema() -> Value {
+				json!({"type": "integer"})
+			}
+		})*
+	};
+}
+
+macro_rules! impl_genai_schema_unsigned {
+	($($ty:ty),*) => {
+		$(impl GenaiSchema for $ty {
+			fn genai_schema() -> Value {
+				json!({"type": "integer", "minimum": 0})
+			}
+		})*
+	};
+}
+
+impl_genai_schema_integer!(i8, i16, i32, i64, i128, isize);
+impl_genai_schema_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl GenaiSchema for f32 {
+	fn genai_schema() -> Value {
+		json!({"type": "number"})
+	}
+}
+
+impl GenaiSchema for f64 {
+	fn genai_schema() -> Value {
+		json!({"type": "number"})
+	}
+}
+
+impl GenaiSchema for () {
+	fn genai_schema() -> Value {
+		json!({"type": "null"})
+	}
+}
+
+/// Any JSON value.
+impl GenaiSchema for Value {
+	fn genai_schema() -> Value {
+		json!({})
+	}
+}
+
+/// Nullable schema (`"type": ["T", "null"]`, or a `{"type": "null"}` `anyOf` variant).
+impl<T: GenaiSchema> GenaiSchema for Option<T> {
+	fn genai_schema() -> Value {
+		let mut schema = T::genai_schema();
+		make_nullable(&mut schema);
+		schema
+	}
+}
+
+impl<T: GenaiSchema + ?Sized> GenaiSchema for Box<T> {
+	fn genai_schema() -> Value {
+		T::genai_schema()
+	}
+}
+
+impl<T: GenaiSchema + ?Sized> GenaiSchema for Arc<T> {
+	fn genai_schema() -> Value {
+		T::genai_schema()
+	}
+}
+
+impl<T: GenaiSchema + ?Sized> GenaiSchema for &T {
+	fn genai_schema() -> Value {
+		T::genai_schema()
+	}
+}
+
+impl<T: GenaiSchema> GenaiSchema for Vec<T> {
+	fn genai_schema() -> Value {
+		json!({"type": "array", "items": T::genai_schema()})
+	}
+}
+
+impl<T: GenaiSchema> GenaiSchema for [T] {
+	fn genai_schema() -> Value {
+		json!({"type": "array", "items": T::genai_schema()})
+	}
+}
+
+impl<T: GenaiSchema, const N: usize> GenaiSchema for [T; N] {
+	fn genai_schema() -> Value {
+		json!({"type": "array", "items": T::genai_schema(), "minItems": N, "maxItems": N})
+	}
+}
+
+impl<T: GenaiSchema> GenaiSchema for VecDeque<T> {
+	fn genai_schema() -> Value {
+		json!({"type": "array", "items": T::genai_schema()})
+	}
+}
+
+impl<T: GenaiSchema, S> GenaiSchema for HashSet<T, S> {
+	fn genai_schema() -> Value {
+		json!({"type": "array", "items": T::genai_schema(), "uniqueItems": true})
+	}
+}
+
+impl<T: GenaiSchema> GenaiSchema for BTreeSet<T> {
+	fn genai_schema() -> Value {
+		json!({"type": "array", "items": T::genai_schema(), "uniqueItems": true})
+	}
+}
+
+/// Object with any property names (`additionalProperties`).
+impl<V: GenaiSchema, S> GenaiSchema for HashMap<String, V, S> {
+	fn genai_schema() -> Value {
+		json!({"type": "object", "additionalProperties": V::genai_schema()})
+	}
+}
+
+/// Object with any property names (`additionalProperties`).
+impl<V: GenaiSchema> GenaiSchema for BTreeMap<String, V> {
+	fn genai_schema() -> Value {
+		json!({"type": "object", "additionalProperties": V::genai_schema()})
+	}
+}
+
+// endregion: --- Std Impls
@@ -0,0 +1,15 @@
+//! JSON Schema support for tool schemas and structured outputs.
+
+// region:    --- Modules
+
+mod dialect;
+mod genai_schema;
+mod openai_strict;
+mod openapi;
+mod validation;
+
+pub use dialect::*;
+pub use genai_schema::*;
+pub use validation::*;
+
+// endregion: --- Modules
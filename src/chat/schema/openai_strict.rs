@@ -0,0 +1,201 @@
+//! Convert JSON Schema to the OpenAI strict mode subset (structured outputs and `strict: true` function tools).
+//!
+//! In strict mode, OpenAI requires every object to have `"additionalProperties": false`
+//! and every property to be listed in `required`. Optional properties are expressed
+//! by making their schema nullable instead.
+//!
+//! [`to_openai_strict_schema`] rewrites a JSON Schema in-place accordingly.
+
+use serde_json::{Map, Value, json};
+
+/// Convert a JSON Schema value in-place to the OpenAI strict mode subset.
+///
+/// The following transformations are applied, recursively:
+///
+/// 1. Rename `oneOf` to `anyOf` (`oneOf` is not supported in strict mode).
+/// 2. Set `"additionalProperties": false` on all the object schemas.
+/// 3. List all the `properties` in `required`, and make the previously optional ones nullable
+///    (`"type": "T"` → `"type": ["T", "null"]`, or a `{"type": "null"}` `anyOf` variant).
+/// 4. Recurse into `properties`, `items`, `prefixItems`, `anyOf`/`allOf`, and `$defs`/`definitions`.
+pub(super) fn to_openai_strict_schema(schema: &mut Value) {
+	if let Value::Object(map) = schema {
+		strictify_object(map);
+	}
+}
+
+// -- Private helpers
+
+fn strictify_object(map: &mut Map<String, Value>) {
+	// Step 1: oneOf -> anyOf (only when there is no anyOf already, to not lose variants)
+	if !map.contains_key("anyOf")
+		&& let Some(one_of) = map.remove("oneOf")
+	{
+		map.insert("anyOf".to_string(), one_of);
+	}
+
+	// Steps 2 + 3: object constraints
+	let is_object = map.get("type").and_then(Value::as_str) == Some("object") || map.contains_key("properties");
+	if is_object {
+		map.insert("additionalProperties".to_string(), false.into());
+
+		let required: Vec<String> = map
+			.get("required")
+			.and_then(Value::as_array)
+			.map(|names| names.iter().filter_map(Value::as_str).map(String::from).collect())
+			.unwrap_or_default();
+
+		if let Some(Value::Object(props)) = map.get_mut("properties") {
+			for (name, prop_schema) in props.iter_mut() {
+				if !required.contains(name) {
+					make_nullable(prop_schema);
+				}
+			}
+			let all_names: Vec<Value> = props.keys().cloned().map(Value::from).collect();
+			map.insert("required".to_string(), Value::Array(all_names));
+		}
+	}
+
+	// Step 4: recurse into sub-schemas
+	recurse_into_children(map);
+}
+
+/// Makes the schema accept `null` (keeping the original constraints for the non-null values).
+pub(super) fn make_nullable(schema: &mut Value) {
+	let Value::Object(map) = schema else {
+		return;
+	};
+
+	match map.get_mut("type") {
+		Some(Value::String(typ)) => {
+			if typ != "null" {
+				let typ = std::mem::take(typ);
+				map.insert("type".to_string(), json!([typ, "null"]));
+			}
+		}
+		Some(Value::Array(types)) => {
+			if !types.iter().any(|t| t.as_str() == Some("null")) {
+				types.push("null".into());
+			}
+		}
+		_ => {
+			if let Some(Value::Array(variants)) = map.get_mut("anyOf") {
+				if !variants.iter().any(is_null_schema) {
+					variants.push(json!({"type": "null"}));
+				}
+			} else {
+				// e.g., `$ref` or untyped schema, wrap it in a nullable `anyOf`
+				let inner = std::mem::take(map);
+				map.insert("anyOf".to_string(), json!([inner, {"type": "null"}]));
+			}
+			return;
+		}
+	}
+
+	// A nullable `enum` must list `null` as well.
+	if let Some(Value::Array(values)) = map.get_mut("enum")
+		&& !values.contains(&Value::Null)
+	{
+		values.push(Value::Null);
+	}
+}
+
+fn is_null_schema(v: &Value) -> bool {
+	v.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn recurse_into_children(map: &mut Map<String, Value>) {
+	for key in ["properties", "$defs", "definitions"] {
+		if let Some(Value::Object(children)) = map.get_mut(key) {
+			for child in children.values_mut() {
+				if let Value::Object(inner) = child {
+					strictify_object(inner);
+				}
+			}
+		}
+	}
+
+	for key in ["items", "prefixItems", "anyOf", "allOf"] {
+		match map.get_mut(key) {
+			Some(Value::Object(inner)) => strictify_object(inner),
+			Some(Value::Array(arr)) => {
+				for item in arr.iter_mut() {
+					if let Value::Object(inner) = item {
+						strictify_object(inner);
+					}
+				}
+			}
+			_ => {}
+		}
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_openai_strict_optional_properties_nullable() {
+		let mut schema = json!({
+			"type": "object",
+			"properties": {
+				"name": { "type": "string" },
+				"unit": { "type": "string", "enum": ["C", "F"] },
+				"tags": { "type": "array", "items": { "type": "object", "properties": { "k": { "type": "string" } } } }
+			},
+			"required": ["name"]
+		});
+
+		to_openai_strict_schema(&mut schema);
+
+		let expected = json!({
+			"type": "object",
+			"properties": {
+				"name": { "type": "string" },
+				"unit": { "type": ["string", "null"], "enum": ["C", "F", null] },
+				"tags": {
+					"type": ["array", "null"],
+					"items": {
+						"type": "object",
+						"properties": { "k": { "type": ["string", "null"] } },
+						"required": ["k"],
+						"additionalProperties": false
+					}
+				}
+			},
+			"required": ["name", "tags", "unit"],
+			"additionalProperties": false
+		});
+		assert_eq!(schema, expected);
+	}
+
+	#[test]
+	fn test_openai_strict_ref_and_one_of() {
+		let mut schema = json!({
+			"type": "object",
+			"properties": {
+				"addr": { "$ref": "#/$defs/Addr" },
+				"id": { "oneOf": [{ "type": "string" }, { "type": "integer" }] }
+			},
+			"required": ["id"],
+			"$defs": {
+				"Addr": { "type": "object", "properties": { "city": { "type": "string" } }, "required": ["city"] }
+			}
+		});
+
+		to_openai_strict_schema(&mut schema);
+
+		assert_eq!(
+			schema["properties"]["addr"],
+			json!({ "anyOf": [{ "$ref": "#/$defs/Addr" }, { "type": "null" }] })
+		);
+		assert_eq!(
+			schema["properties"]["id"],
+			json!({ "anyOf": [{ "type": "string" }, { "type": "integer" }] })
+		);
+		assert_eq!(schema["$defs"]["Addr"]["additionalProperties"], json!(false));
+	}
+}
+
+// endregion: --- Tests
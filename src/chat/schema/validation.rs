@@ -0,0 +1,405 @@
+//! Local JSON Schema validation of the model outputs (tool call arguments and structured outputs).
+//!
+//! Covers the JSON Schema subset accepted by the providers:
+//! - `type` (including `["T", "null"]` and the OpenAPI `nullable`), `enum`, `const`,
+//! - `properties`, `required`, `additionalProperties`, `items`, `prefixItems`,
+//! - `anyOf`, `oneOf`, `allOf`, and local `$ref` (e.g., `#/$defs/Name` or `#/definitions/Name`),
+//! - `minimum`, `maximum`, `exclusiveMinimum`, `exclusiveMaximum`, `minLength`, `maxLength`, `minItems`, `maxItems`.
+//!
+//! Other keywords (e.g., `format`, `pattern`, `description`) are ignored.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Maximum number of nested `$ref` resolutions (guards against reference cycles).
+const MAX_REF_DEPTH: usize = 64;
+
+// region:    --- SchemaViolation
+
+/// One validation error, located by its path in the validated value (e.g., `$.items[0].name`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+	/// Path of the invalid value, `$` being the root.
+	pub path: String,
+	/// What is wrong with the value.
+	pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}", self.path, self.message)
+	}
+}
+
+// endregion: --- SchemaViolation
+
+/// Validates the value against the JSON schema, and returns all the violations (empty when valid).
+pub fn validate_json_schema(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+	let mut validator = Validator::new(schema);
+	validator.validate(schema, value, "$", 0);
+	validator.violations
+}
+
+// region:    --- Validator
+
+struct Validator<'a> {
+	root: &'a Value,
+	violations: Vec<SchemaViolation>,
+}
+
+impl<'a> Validator<'a> {
+	fn new(root: &'a Value) -> Self {
+		Self {
+			root,
+			violations: Vec::new(),
+		}
+	}
+
+	fn push(&mut self, path: &str, message: impl Into<String>) {
+		self.violations.push(SchemaViolation {
+			path: path.to_string(),
+			message: message.into(),
+		});
+	}
+
+	fn validate(&mut self, schema: &'a Value, value: &Value, path: &str, ref_depth: usize) {
+		let Value::Object(schema) = schema else {
+			// `false` rejects any value, `true` (or any other non-object) accepts any value.
+			if schema == &Value::Bool(false) {
+				self.push(path, "no value is allowed");
+			}
+			return;
+		};
+
+		// -- $ref (the sibling keywords still apply)
+		if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+			match self.resolve_ref(reference) {
+				Some(_) if ref_depth >= MAX_REF_DEPTH => self.push(path, format!("'$ref' {reference} nested too deep")),
+				Some(target) => self.validate(target, value, path, ref_depth + 1),
+				None => self.push(path, format!("unresolved '$ref' {reference}")),
+			}
+		}
+
+		if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+			return;
+		}
+
+		// -- type (stop on mismatch, the other keywords would only add noise)
+		let types = schema_types(schema);
+		if !types.is_empty() && !types.iter().any(|name| type_matches(name, value)) {
+			self.push(
+				path,
+				format!("expected {}, found {}", types.join(" or "), json_type_name(value)),
+			);
+			return;
+		}
+
+		// -- enum & const
+		if let Some(Value::Array(options)) = schema.get("enum")
+			&& !options.contains(value)
+		{
+			let options: Vec<String> = options.iter().map(Value::to_string).collect();
+			self.push(path, format!("{value} is not one of [{}]", options.join(", ")));
+		}
+		if let Some(expected) = schema.get("const")
+			&& expected != value
+		{
+			self.push(path, format!("expected {expected}, found {value}"));
+		}
+
+		// -- Composites
+		if let Some(Value::Array(variants)) = schema.get("allOf") {
+			for variant in variants {
+				self.validate(variant, value, path, ref_depth);
+			}
+		}
+		if let Some(Value::Array(variants)) = schema.get("anyOf")
+			&& self.count_matching(variants, value, ref_depth) == 0
+		{
+			self.push(path, "does not match any of the 'anyOf' schemas");
+		}
+		if let Some(Value::Array(variants)) = schema.get("oneOf") {
+			let matching = self.count_matching(variants, value, ref_depth);
+			if matching != 1 {
+				self.push(
+					path,
+					format!("matches {matching} of the 'oneOf' schemas (expected exactly one)"),
+				);
+			}
+		}
+
+		// -- Value kind specific keywords
+		match value {
+			Value::Object(map) => self.validate_object(schema, map, path, ref_depth),
+			Value::Array(items) => self.validate_array(schema, items, path, ref_depth),
+			Value::String(text) => {
+				let len = text.chars().count() as u64;
+				if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+					&& len < min
+				{
+					self.push(path, format!("length {len} is less than minLength {min}"));
+				}
+				if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+					&& len > max
+				{
+					self.push(path, format!("length {len} is greater than maxLength {max}"));
+				}
+			}
+			Value::Number(number) => {
+				let Some(number) = number.as_f64() else { return };
+				let bound = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+				if let Some(min) = bound("minimum")
+					&& number < min
+				{
+					self.push(path, format!("{number} is less than minimum {min}"));
+				}
+				if let Some(max) = bound("maximum")
+					&& number > max
+				{
+					self.push(path, format!("{number} is greater than maximum {max}"));
+				}
+				if let Some(min) = bound("exclusiveMinimum")
+					&& number <= min
+				{
+					self.push(path, format!("{number} is not greater than exclusiveMinimum {min}"));
+				}
+				if let Some(max) = bound("exclusiveMaximum")
+					&& number >= max
+				{
+					self.push(path, format!("{number} is not less than exclusiveMaximum {max}"));
+				}
+			}
+			Value::Bool(_) | Value::Null => {}
+		}
+	}
+
+	fn validate_object(&mut self, schema: &'a Map<String, Value>, map: &Map<String, Value>, path: &str, depth: usize) {
+		let required: Vec<&str> = schema
+			.get("required")
+			.and_then(Value::as_array)
+			.map(|required| required.iter().filter_map(Value::as_str).collect())
+			.unwrap_or_default();
+		for name in &required {
+			if !map.contains_key(*name) {
+				self.push(path, format!("missing required property '{name}'"));
+			}
+		}
+
+		let properties = schema.get("properties").and_then(Value::as_object);
+		let additional = schema.get("additionalProperties");
+		for (key, property_value) in map {
+			// A `null` optional property is read as absent
+			// (the OpenAI strict mode makes all the properties required, and the optional ones nullable).
+			if property_value.is_null()
+				&& !required.contains(&key.as_str())
+				&& properties.is_some_and(|props| props.contains_key(key))
+			{
+				continue;
+			}
+			let property_path = key_path(path, key);
+			match (properties.and_then(|props| props.get(key)), additional) {
+				(Some(property_schema), _) => self.validate(property_schema, property_value, &property_path, depth),
+				(None, Some(Value::Bool(false))) => self.push(&property_path, "property is not allowed"),
+				(None, Some(additional_schema @ Value::Object(_))) => {
+					self.validate(additional_schema, property_value, &property_path, depth)
+				}
+				(None, _) => {}
+			}
+		}
+	}
+
+	fn validate_array(&mut self, schema: &'a Map<String, Value>, items: &[Value], path: &str, depth: usize) {
+		let len = items.len() as u64;
+		if let Some(min) = schema.get("minItems").and_then(Value::as_u64)
+			&& len < min
+		{
+			self.push(path, format!("{len} items is less than minItems {min}"));
+		}
+		if let Some(max) = schema.get("maxItems").and_then(Value::as_u64)
+			&& len > max
+		{
+			self.push(path, format!("{len} items is greater than maxItems {max}"));
+		}
+
+		let prefix_items: &[Value] = schema
+			.get("prefixItems")
+			.and_then(Value::as_array)
+			.map(Vec::as_slice)
+			.unwrap_or_default();
+		for (idx, item) in items.iter().enumerate() {
+			let item_path = format!("{path}[{idx}]");
+			if let Some(item_schema) = prefix_items.get(idx) {
+				self.validate(item_schema, item, &item_path, depth);
+			} else if let Some(item_schema) = schema.get("items") {
+				self.validate(item_schema, item, &item_path, depth);
+			}
+		}
+	}
+
+	/// Number of the variants the value is valid against.
+	fn count_matching(&self, variants: &'a [Value], value: &Value, ref_depth: usize) -> usize {
+		variants
+			.iter()
+			.filter(|variant| {
+				let mut validator = Validator::new(self.root);
+				validator.validate(variant, value, "$", ref_depth);
+				validator.violations.is_empty()
+			})
+			.count()
+	}
+
+	/// Resolves a local reference (`#` or `#/json/pointer`) in the root schema.
+	fn resolve_ref(&self, reference: &str) -> Option<&'a Value> {
+		let pointer = reference.strip_prefix('#')?;
+		self.root.pointer(pointer)
+	}
+}
+
+// endregion: --- Validator
+
+// region:    --- Support
+
+fn schema_types(schema: &Map<String, Value>) -> Vec<&str> {
+	match schema.get("type") {
+		Some(Value::String(name)) => vec![name.as_str()],
+		Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+		_ => Vec::new(),
+	}
+}
+
+fn type_matches(type_name: &str, value: &Value) -> bool {
+	match type_name {
+		"null" => value.is_null(),
+		"boolean" => value.is_boolean(),
+		"string" => value.is_string(),
+		"number" => value.is_number(),
+		"integer" => value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.),
+		"object" => value.is_object(),
+		"array" => value.is_array(),
+		// Unknown type names are not ours to reject.
+		_ => true,
+	}
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+	match value {
+		Value::Null => "null",
+		Value::Bool(_) => "boolean",
+		Value::Number(_) => "number",
+		Value::String(_) => "string",
+		Value::Array(_) => "array",
+		Value::Object(_) => "object",
+	}
+}
+
+/// `$.key` for identifier-like keys, `$["some key"]` otherwise.
+fn key_path(path: &str, key: &str) -> String {
+	let is_identifier = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+	if is_identifier {
+		format!("{path}.{key}")
+	} else {
+		format!("{path}[{}]", Value::String(key.to_string()))
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn person_schema() -> Value {
+		json!({
+			"type": "object",
+			"properties": {
+				"name": { "type": "string", "minLength": 1 },
+				"age": { "type": "integer", "minimum": 0 },
+				"role": { "enum": ["admin", "user"] },
+				"tags": { "type": "array", "items": { "type": "string" } },
+				"address": { "$ref": "#/$defs/Address" }
+			},
+			"required": ["name", "age"],
+			"additionalProperties": false,
+			"$defs": {
+				"Address": {
+					"type": "object",
+					"properties": { "city": { "type": "string" } },
+					"required": ["city"]
+				}
+			}
+		})
+	}
+
+	#[test]
+	fn test_validate_json_schema_valid() {
+		let value = json!({"name": "Mike", "age": 30, "role": "admin", "tags": ["a"], "address": {"city": "Paris"}});
+
+		assert_eq!(validate_json_schema(&person_schema(), &value), Vec::new());
+	}
+
+	#[test]
+	fn test_validate_json_schema_paths() {
+		let value = json!({"name": "", "age": 1.5, "role": "root", "tags": ["a", 2], "address": {}, "extra": true});
+
+		// Note: The object properties are validated in the key order (sorted by serde_json).
+		let violations: Vec<String> = validate_json_schema(&person_schema(), &value)
+			.into_iter()
+			.map(|violation| violation.to_string())
+			.collect();
+
+		assert_eq!(
+			violations,
+			vec![
+				"$.address: missing required property 'city'",
+				"$.age: expected integer, found number",
+				"$.extra: property is not allowed",
+				"$.name: length 0 is less than minLength 1",
+				r#"$.role: "root" is not one of ["admin", "user"]"#,
+				"$.tags[1]: expected string, found number",
+			]
+		);
+	}
+
+	#[test]
+	fn test_validate_json_schema_any_of_and_nullable() {
+		let schema = json!({
+			"type": "object",
+			"properties": {
+				"label": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+				"count": { "type": "integer", "nullable": true },
+				"kind": { "type": ["string", "null"] }
+			}
+		});
+
+		assert!(validate_json_schema(&schema, &json!({"label": null, "count": null, "kind": null})).is_empty());
+		let violations = validate_json_schema(&schema, &json!({"label": 1}));
+		assert_eq!(violations.len(), 1);
+		assert_eq!(violations[0].path, "$.label");
+	}
+
+	#[test]
+	fn test_validate_json_schema_null_optional_property() {
+		let value = json!({"name": "Mike", "age": 30, "role": null, "address": null});
+
+		assert_eq!(validate_json_schema(&person_schema(), &value), Vec::new());
+		let violations = validate_json_schema(&person_schema(), &json!({"name": "Mike", "age": null}));
+		assert_eq!(violations.len(), 1);
+		assert_eq!(violations[0].path, "$.age");
+	}
+
+	#[test]
+	fn test_validate_json_schema_ref_cycle() {
+		let schema = json!({ "$ref": "#/$defs/Loop", "$defs": { "Loop": { "$ref": "#/$defs/Loop" } } });
+
+		let violations = validate_json_schema(&schema, &json!(1));
+
+		assert_eq!(violations.len(), 1);
+		assert!(violations[0].message.contains("nested too deep"));
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,135 @@
+//! Streaming of structured outputs, with best-effort partial values parsed while the JSON text is streamed.
+
+use crate::chat::{ChatStream, ChatStreamEvent, StreamEnd, ToolCall, parse_partial_json, strip_code_fences};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+// region:    --- StructuredStream
+
+/// A `ChatStream` adapter for structured outputs (e.g., `ChatResponseFormat::JsonSpec`), created with `ChatStream::structured`.
+///
+/// The text received so far is parsed (see `parse_partial_json`) after each chunk,
+/// and a `PartialObject` event is emitted each time the partial value changes.
+pub struct StructuredStream<T> {
+	chat_stream: ChatStream,
+	/// The text received so far.
+	text: String,
+	/// The last emitted partial value.
+	last_partial: Option<Value>,
+	/// Events to return before polling the chat stream again.
+	pending: VecDeque<StructuredStreamEvent<T>>,
+}
+
+// NOTE: `T` values are only moved in and out of the pending queue, never pinned.
+impl<T> Unpin for StructuredStream<T> {}
+
+impl ChatStream {
+	/// Returns a stream emitting the best-effort partial values of type `T` (e.g., `serde_json::Value`)
+	/// parsed from the streamed text, and the tool calls with their partial arguments.
+	///
+	/// NOTE: The partial tool call arguments require `ChatOptions::with_capture_tool_calls(true)`, so that the adapters
+	///       streaming argument fragments (e.g., OpenAI) give the arguments received so far in each `ToolCallChunk`.
+	pub fn structured<T: DeserializeOwned>(self) -> StructuredStream<T> {
+		StructuredStream {
+			chat_stream: self,
+			text: String::new(),
+			last_partial: None,
+			pending: VecDeque::new(),
+		}
+	}
+}
+
+impl<T: DeserializeOwned> StructuredStream<T> {
+	fn process_event(&mut self, event: ChatStreamEvent) -> StructuredStreamEvent<T> {
+		match event {
+			ChatStreamEvent::Chunk(chunk) => {
+				self.text.push_str(&chunk.content);
+				if let Some(partial) = parse_partial_json(&self.text)
+					&& self.last_partial.as_ref() != Some(&partial)
+				{
+					// NOTE: A partial value missing required fields of `T` is skipped (it will be complete later).
+					if let Ok(value) = serde_json::from_value::<T>(partial.clone()) {
+						self.pending.push_back(StructuredStreamEvent::PartialObject(value));
+					}
+					self.last_partial = Some(partial);
+				}
+				StructuredStreamEvent::Chat(ChatStreamEvent::Chunk(chunk))
+			}
+			ChatStreamEvent::ToolCallChunk(tool_chunk) => {
+				let mut tool_call = tool_chunk.tool_call;
+				// Adapters streaming argument fragments give the arguments received so far as a string.
+				if let Value::String(arguments) = &tool_call.fn_arguments
+					&& let Some(partial) = parse_partial_json(arguments)
+				{
+					tool_call.fn_arguments = partial;
+				}
+				StructuredStreamEvent::PartialToolCall(tool_call)
+			}
+			ChatStreamEvent::End(stream_end) => {
+				let text = strip_code_fences(&self.text);
+				let value = if text.is_empty() {
+					None
+				} else {
+					serde_json::from_str::<T>(text).ok()
+				};
+				StructuredStreamEvent::End(StructuredStreamEnd { value, stream_end })
+			}
+			other => StructuredStreamEvent::Chat(other),
+		}
+	}
+}
+
+impl<T: DeserializeOwned> Stream for StructuredStream<T> {
+	type Item = crate::Result<StructuredStreamEvent<T>>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let this = self.get_mut();
+
+		if let Some(event) = this.pending.pop_front() {
+			return Poll::Ready(Some(Ok(event)));
+		}
+
+		match Pin::new(&mut this.chat_stream).poll_next(cx) {
+			Poll::Ready(Some(Ok(event))) => Poll::Ready(Some(Ok(this.process_event(event)))),
+			Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err))),
+			Poll::Ready(None) => Poll::Ready(None),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+}
+
+// endregion: --- StructuredStream
+
+// region:    --- StructuredStreamEvent
+
+/// Events of a `StructuredStream`.
+#[derive(Debug)]
+pub enum StructuredStreamEvent<T> {
+	/// Event of the underlying chat stream (all but `ToolCallChunk` and `End`).
+	Chat(ChatStreamEvent),
+
+	/// New best-effort partial value, parsed from the text received so far (emitted after its `Chunk`).
+	PartialObject(T),
+
+	/// Tool call, with the best-effort partial arguments received so far for this call.
+	PartialToolCall(ToolCall),
+
+	/// End of stream, with the final value.
+	End(StructuredStreamEnd<T>),
+}
+
+/// Content of `StructuredStreamEvent::End`.
+#[derive(Debug)]
+pub struct StructuredStreamEnd<T> {
+	/// The value parsed from the full text (None when there is no text, or it is not a valid `T`).
+	pub value: Option<T>,
+
+	/// The end event of the underlying chat stream.
+	pub stream_end: StreamEnd,
+}
+
+// endregion: --- StructuredStreamEvent
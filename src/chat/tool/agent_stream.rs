@@ -0,0 +1,80 @@
+use crate::chat::{ChatRequest, StreamEnd, ToolCall, ToolResponse, ToolRunStop};
+use futures::Stream;
+use futures::channel::mpsc::UnboundedReceiver;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::JoinHandle;
+
+// region:    --- AgentStream
+
+/// The unified event stream of `Client::run_tools_stream`, over all the turns of the agent loop.
+///
+/// The loop runs in a background task, which is aborted when this stream is dropped.
+pub struct AgentStream {
+	rx: UnboundedReceiver<crate::Result<AgentEvent>>,
+	task: JoinHandle<()>,
+}
+
+impl AgentStream {
+	pub(crate) fn new(rx: UnboundedReceiver<crate::Result<AgentEvent>>, task: JoinHandle<()>) -> Self {
+		Self { rx, task }
+	}
+}
+
+impl Stream for AgentStream {
+	type Item = crate::Result<AgentEvent>;
+
+	fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		Pin::new(&mut self.get_mut().rx).poll_next(cx)
+	}
+}
+
+impl Drop for AgentStream {
+	fn drop(&mut self) {
+		self.task.abort();
+	}
+}
+
+// endregion: --- AgentStream
+
+// region:    --- AgentEvent
+
+/// Events of an `AgentStream`.
+#[derive(Debug)]
+pub enum AgentEvent {
+	/// Assistant text chunk.
+	Text(String),
+
+	/// Reasoning content chunk.
+	Reasoning(String),
+
+	/// A complete tool call was dispatched to its handler (while the turn may still be streaming).
+	ToolStarted(ToolCall),
+
+	/// A tool handler finished (or the `ToolPolicy` denied the call), with the response sent back to the model.
+	ToolFinished(ToolResponse),
+
+	/// The `ToolPolicy` paused the tool call (the loop stops with `ToolRunStop::Paused` at the end of the turn).
+	ToolPaused(ToolCall),
+
+	/// End of a model turn (after all its tool calls finished).
+	TurnEnd(AgentTurnEnd),
+}
+
+/// Content of `AgentEvent::TurnEnd`.
+#[derive(Debug)]
+pub struct AgentTurnEnd {
+	/// Turn number, starting at 1 (0 when a resumed run paused again before any model call).
+	pub iteration: u32,
+
+	/// The end of the turn chat stream (content, tool calls, reasoning, and usage are captured).
+	pub stream_end: StreamEnd,
+
+	/// Why the loop stopped, on the last turn only.
+	pub stop: Option<ToolRunStop>,
+
+	/// The full transcript (see `ToolRunResponse.chat_req`), on the last turn only.
+	pub chat_req: Option<ChatRequest>,
+}
+
+// endregion: --- AgentEvent
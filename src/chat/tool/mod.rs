@@ -7,15 +7,25 @@
 
 // region:    --- Modules
 
+mod agent_stream;
 mod tool_base;
 mod tool_call;
+mod tool_choice;
+mod tool_policy;
+mod tool_registry;
 mod tool_response;
+mod tool_run;
 mod tool_types;
 mod web_search_config;
 
+pub use agent_stream::*;
 pub use tool_base::*;
 pub use tool_call::*;
+pub use tool_choice::*;
+pub use tool_policy::*;
+pub use tool_registry::*;
 pub use tool_response::*;
+pub use tool_run::*;
 pub use tool_types::*;
 pub use web_search_config::*;
 
@@ -1,3 +1,5 @@
+use crate::chat::{SchemaViolation, Tool, validate_json_schema};
+use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -46,3 +48,34 @@ impl ToolCall {
 		size
 	}
 }
+
+/// Validation
+impl ToolCall {
+	/// Validates this tool call against the tool: same function name, and arguments valid against the tool `schema`
+	/// (see `validate_json_schema` for the supported subset).
+	///
+	/// Returns `Error::JsonSchemaViolations` with all the violations, located by their path in the arguments.
+	pub fn validate_against(&self, tool: &Tool) -> Result<()> {
+		let target = format!("Tool call '{}' arguments", self.fn_name);
+
+		if self.fn_name != tool.name.as_str() {
+			return Err(Error::JsonSchemaViolations {
+				target,
+				violations: vec![SchemaViolation {
+					path: "$".to_string(),
+					message: format!("tool call is for '{}', not for the tool '{}'", self.fn_name, tool.name),
+				}],
+			});
+		}
+
+		let Some(schema) = tool.schema.as_ref() else {
+			return Ok(());
+		};
+		let violations = validate_json_schema(schema, &self.fn_arguments);
+		if violations.is_empty() {
+			Ok(())
+		} else {
+			Err(Error::JsonSchemaViolations { target, violations })
+		}
+	}
+}
@@ -0,0 +1,26 @@
+use crate::chat::ToolName;
+use serde::{Deserialize, Serialize};
+
+/// Controls whether, and which, tools the model may call (see `ChatOptions::with_tool_choice`).
+///
+/// Only applied when the request has tools. Adapters that cannot honor a choice
+/// return `Error::AdapterNotSupported`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToolChoice {
+	/// The model decides whether to call tools (provider default).
+	Auto,
+	/// The model must not call any tool.
+	None,
+	/// The model must call at least one tool.
+	Required,
+	/// The model must call this tool.
+	Tool(ToolName),
+}
+
+/// Constructors
+impl ToolChoice {
+	/// Forces the call of the tool with this name.
+	pub fn tool(name: impl Into<ToolName>) -> Self {
+		Self::Tool(name.into())
+	}
+}
@@ -0,0 +1,190 @@
+use crate::chat::ToolCall;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The future returned by a `ToolPolicyHook`.
+pub type ToolDecisionFuture = Pin<Box<dyn Future<Output = ToolDecision> + Send>>;
+
+// region:    --- ToolDecision
+
+/// Decision of a `ToolPolicy` for a tool call, before its execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ToolDecision {
+	/// Execute the tool call.
+	Approve,
+
+	/// Do not execute the tool call, and return a `{"error": "tool call denied: <reason>"}` tool response to the model.
+	Deny(String),
+
+	/// Execute the tool call with these arguments instead.
+	Rewrite(Value),
+
+	/// Do not execute the tool call, and stop the agent loop (`ToolRunStop::Paused`).
+	/// The call stays pending at the end of the transcript, and is decided again when the transcript is run again
+	/// (e.g., after a human approval, set with `ToolPolicy::with_call_decision`).
+	Pause,
+}
+
+// endregion: --- ToolDecision
+
+// region:    --- ToolPolicyHook
+
+/// Async hook deciding each tool call (e.g., asking a human), see `ToolPolicy::with_hook`.
+pub trait ToolPolicyHook: Send + Sync {
+	fn decide(&self, tool_call: ToolCall) -> ToolDecisionFuture;
+}
+
+impl<F, Fut> ToolPolicyHook for F
+where
+	F: Fn(ToolCall) -> Fut + Send + Sync,
+	Fut: Future<Output = ToolDecision> + Send + 'static,
+{
+	fn decide(&self, tool_call: ToolCall) -> ToolDecisionFuture {
+		Box::pin(self(tool_call))
+	}
+}
+
+// endregion: --- ToolPolicyHook
+
+// region:    --- ToolPolicy
+
+/// Policy applied by the agent loops (`Client::run_tools` and `Client::run_tools_stream`) before executing a tool call,
+/// set with `ToolRegistry::with_policy`.
+///
+/// The checks are applied in order:
+/// 1. The denied tools, then the allowed tools (when set), are denied.
+/// 2. The tools over their max calls (per run, a resumed run included) are denied.
+/// 3. The decision set for the call id (`with_call_decision`), if any.
+/// 4. The hook decision (`with_hook`), if any.
+/// 5. Otherwise, the call is approved.
+#[derive(Clone, Default)]
+pub struct ToolPolicy {
+	allowed_tools: Option<Vec<String>>,
+	denied_tools: Vec<String>,
+	max_calls_per_tool: Option<u32>,
+	tool_max_calls: HashMap<String, u32>,
+	call_decisions: HashMap<String, ToolDecision>,
+	hook: Option<Arc<dyn ToolPolicyHook>>,
+}
+
+/// Constructors & Setters
+impl ToolPolicy {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Only allows these tools (by function name), all the others are denied.
+	pub fn with_allowed_tools<I>(mut self, fn_names: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<String>,
+	{
+		self.allowed_tools = Some(fn_names.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Denies these tools (by function name).
+	pub fn with_denied_tools<I>(mut self, fn_names: I) -> Self
+	where
+		I: IntoIterator,
+		I::Item: Into<String>,
+	{
+		self.denied_tools = fn_names.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Sets the maximum calls of each tool in one run (the calls over it are denied).
+	pub fn with_max_calls_per_tool(mut self, max_calls: u32) -> Self {
+		self.max_calls_per_tool = Some(max_calls);
+		self
+	}
+
+	/// Sets the maximum calls of this tool in one run (overrides `with_max_calls_per_tool`).
+	pub fn with_tool_max_calls(mut self, fn_name: impl Into<String>, max_calls: u32) -> Self {
+		self.tool_max_calls.insert(fn_name.into(), max_calls);
+		self
+	}
+
+	/// Sets the decision for a tool call id (e.g., the human approval of a paused call, when resuming).
+	pub fn with_call_decision(mut self, call_id: impl Into<String>, decision: ToolDecision) -> Self {
+		self.call_decisions.insert(call_id.into(), decision);
+		self
+	}
+
+	/// Sets the async hook deciding the tool calls not decided by the lists, budget, or call decisions.
+	pub fn with_hook<F, Fut>(mut self, hook: F) -> Self
+	where
+		F: Fn(ToolCall) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = ToolDecision> + Send + 'static,
+	{
+		self.hook = Some(Arc::new(hook));
+		self
+	}
+}
+
+/// Decisions
+impl ToolPolicy {
+	/// Returns the decision from the lists, the max calls, and the call decisions (None to ask the hook).
+	///
+	/// Counts the call in `call_counts` (by function name), which must be kept for the whole run
+	/// (a resumed run starts from the calls already responded in its transcript).
+	pub(crate) fn precheck(
+		&self,
+		tool_call: &ToolCall,
+		call_counts: &mut HashMap<String, u32>,
+	) -> Option<ToolDecision> {
+		let fn_name = tool_call.fn_name.as_str();
+
+		// -- Lists
+		if self.denied_tools.iter().any(|name| name == fn_name) {
+			return Some(ToolDecision::Deny(format!("tool '{fn_name}' is denied")));
+		}
+		if let Some(allowed_tools) = &self.allowed_tools
+			&& !allowed_tools.iter().any(|name| name == fn_name)
+		{
+			return Some(ToolDecision::Deny(format!("tool '{fn_name}' is not allowed")));
+		}
+
+		// -- Max calls
+		let count = call_counts.entry(fn_name.to_string()).or_default();
+		*count += 1;
+		let max_calls = self.tool_max_calls.get(fn_name).copied().or(self.max_calls_per_tool);
+		if let Some(max_calls) = max_calls
+			&& *count > max_calls
+		{
+			return Some(ToolDecision::Deny(format!(
+				"tool '{fn_name}' reached its maximum of {max_calls} calls"
+			)));
+		}
+
+		// -- Call decisions
+		self.call_decisions.get(&tool_call.call_id).cloned()
+	}
+
+	/// Returns the hook decision (approve when there is no hook).
+	pub(crate) async fn hook_decision(&self, tool_call: &ToolCall) -> ToolDecision {
+		match &self.hook {
+			Some(hook) => hook.decide(tool_call.clone()).await,
+			None => ToolDecision::Approve,
+		}
+	}
+}
+
+impl std::fmt::Debug for ToolPolicy {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ToolPolicy")
+			.field("allowed_tools", &self.allowed_tools)
+			.field("denied_tools", &self.denied_tools)
+			.field("max_calls_per_tool", &self.max_calls_per_tool)
+			.field("tool_max_calls", &self.tool_max_calls)
+			.field("call_decisions", &self.call_decisions)
+			.field("hook", &self.hook.is_some())
+			.finish()
+	}
+}
+
+// endregion: --- ToolPolicy
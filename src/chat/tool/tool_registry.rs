@@ -0,0 +1,195 @@
+use crate::BoxError;
+use crate::chat::{Tool, ToolCall, ToolDecision, ToolPolicy, ToolResponse};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The future returned by a `ToolHandler`.
+pub type ToolHandlerFuture = Pin<Box<dyn Future<Output = Result<Value, BoxError>> + Send>>;
+
+// region:    --- ToolHandler
+
+/// Async handler executing a tool call, from its arguments (see `ToolRegistry::with_tool`).
+pub trait ToolHandler: Send + Sync {
+	fn call(&self, arguments: Value) -> ToolHandlerFuture;
+}
+
+impl<F, Fut> ToolHandler for F
+where
+	F: Fn(Value) -> Fut + Send + Sync,
+	Fut: Future<Output = Result<Value, BoxError>> + Send + 'static,
+{
+	fn call(&self, arguments: Value) -> ToolHandlerFuture {
+		Box::pin(self(arguments))
+	}
+}
+
+// endregion: --- ToolHandler
+
+// region:    --- ToolRegistry
+
+/// The `Tool` definitions with their handlers, executed by `Client::run_tools`.
+///
+/// ```
+/// use genai::chat::{Tool, ToolRegistry};
+/// use serde_json::{Value, json};
+///
+/// let registry = ToolRegistry::new().with_tool(Tool::new("get_weather"), |args: Value| async move {
+///     Ok(json!({"city": args["city"], "temperature": 21}))
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+	tools: Vec<RegisteredTool>,
+	policy: ToolPolicy,
+}
+
+#[derive(Clone)]
+struct RegisteredTool {
+	tool: Tool,
+	handler: Arc<dyn ToolHandler>,
+}
+
+/// Constructors & Setters
+impl ToolRegistry {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers (or replaces, by name) a tool with its async handler.
+	pub fn with_tool<F, Fut>(mut self, tool: Tool, handler: F) -> Self
+	where
+		F: Fn(Value) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = Result<Value, BoxError>> + Send + 'static,
+	{
+		let registered = RegisteredTool {
+			tool,
+			handler: Arc::new(handler),
+		};
+		match self.tools.iter_mut().find(|t| t.tool.name == registered.tool.name) {
+			Some(existing) => *existing = registered,
+			None => self.tools.push(registered),
+		}
+		self
+	}
+
+	/// Sets the policy applied before executing the tool calls in the agent loops.
+	pub fn with_policy(mut self, policy: ToolPolicy) -> Self {
+		self.policy = policy;
+		self
+	}
+}
+
+/// Getters
+impl ToolRegistry {
+	/// Returns the tool definitions, in registration order.
+	pub fn tools(&self) -> impl Iterator<Item = &Tool> {
+		self.tools.iter().map(|t| &t.tool)
+	}
+
+	pub fn policy(&self) -> &ToolPolicy {
+		&self.policy
+	}
+
+	/// Returns the tool definition for this function name, if registered.
+	pub fn get_tool(&self, fn_name: &str) -> Option<&Tool> {
+		self.find(fn_name).map(|t| &t.tool)
+	}
+
+	fn find(&self, fn_name: &str) -> Option<&RegisteredTool> {
+		self.tools.iter().find(|t| t.tool.name.as_str() == fn_name)
+	}
+}
+
+/// Execution
+impl ToolRegistry {
+	/// Executes the tool call with its handler, and returns the tool response for the model.
+	///
+	/// The arguments are first validated against the tool schema (see `ToolCall::validate_against`).
+	/// An unknown tool, invalid arguments, or a handler error is returned to the model as a
+	/// `{"error": "..."}` tool response (with `is_error`), so it can correct the call.
+	/// A string value is returned as a text content, the other values as a JSON content.
+	pub async fn execute(&self, tool_call: &ToolCall) -> ToolResponse {
+		let result = match self.find(&tool_call.fn_name) {
+			None => Err(format!("unknown tool '{}'", tool_call.fn_name)),
+			Some(registered) => match tool_call.validate_against(&registered.tool) {
+				Err(err) => Err(err.to_string()),
+				Ok(()) => registered
+					.handler
+					.call(tool_call.fn_arguments.clone())
+					.await
+					.map_err(|err| err.to_string()),
+			},
+		};
+
+		match result {
+			Ok(Value::String(text)) => ToolResponse::new(tool_call.call_id.clone(), text),
+			Ok(value) => ToolResponse::from_json(tool_call.call_id.clone(), value),
+			Err(cause) => error_response(&tool_call.call_id, cause),
+		}
+	}
+
+	/// Returns the policy decision from its lists, max calls, and call decisions (None to ask its hook).
+	/// See `ToolPolicy::precheck`.
+	pub(crate) fn precheck(
+		&self,
+		tool_call: &ToolCall,
+		call_counts: &mut HashMap<String, u32>,
+	) -> Option<ToolDecision> {
+		self.policy.precheck(tool_call, call_counts)
+	}
+
+	/// Decides (with the `precheck` decision, or the policy hook) and executes the tool call.
+	pub(crate) async fn decide_and_execute(
+		&self,
+		tool_call: ToolCall,
+		precheck: Option<ToolDecision>,
+	) -> ToolCallOutcome {
+		let decision = match precheck {
+			Some(decision) => decision,
+			None => self.policy.hook_decision(&tool_call).await,
+		};
+
+		match decision {
+			ToolDecision::Approve => ToolCallOutcome::Done(self.execute(&tool_call).await),
+			ToolDecision::Rewrite(fn_arguments) => {
+				let tool_call = ToolCall {
+					fn_arguments,
+					..tool_call
+				};
+				let tool_response = self.execute(&tool_call).await;
+				ToolCallOutcome::Rewritten(tool_call, tool_response)
+			}
+			ToolDecision::Deny(reason) => ToolCallOutcome::Done(error_response(
+				&tool_call.call_id,
+				format!("tool call denied: {reason}"),
+			)),
+			ToolDecision::Pause => ToolCallOutcome::Paused(tool_call),
+		}
+	}
+}
+
+/// Outcome of a tool call in the agent loops.
+pub(crate) enum ToolCallOutcome {
+	Done(ToolResponse),
+	/// Executed with the rewritten arguments (the tool call to record in the transcript).
+	Rewritten(ToolCall, ToolResponse),
+	Paused(ToolCall),
+}
+
+fn error_response(call_id: &str, cause: String) -> ToolResponse {
+	ToolResponse::from_json(call_id, json!({ "error": cause })).with_is_error(true)
+}
+
+impl std::fmt::Debug for ToolRegistry {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("ToolRegistry")
+			.field("tools", &self.tools().map(|t| t.name.as_str()).collect::<Vec<_>>())
+			.field("policy", &self.policy)
+			.finish()
+	}
+}
+
+// endregion: --- ToolRegistry
@@ -1,45 +1,160 @@
-use serde::{Deserialize, Serialize};
+use crate::chat::Binary;
+use derive_more::From;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 
 /// Response produced by a tool invocation, paired with the originating tool call ID.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolResponse {
 	/// Identifier of the originating tool call.
 	pub call_id: String,
-	/// Tool output payload as a string. Providers may use JSON-serialized content.
-	// For now, just a string (would probably be serialized JSON)
-	pub content: String,
+
+	/// Tool output parts (text, JSON, or binary such as screenshots).
+	///
+	/// NOTE: Also deserializes from a plain string (the former format), as a single text part.
+	#[serde(deserialize_with = "deserialize_content")]
+	pub content: Vec<ToolResponsePart>,
+
+	/// True if the tool failed, the content describing the error.
+	/// Mapped to the provider error flag when supported (Anthropic `is_error`, Bedrock `status`, Gemini `error`).
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub is_error: bool,
 }
 
-/// Constructor
+/// Constructors
 impl ToolResponse {
-	/// Creates a new ToolResponse with the provided tool_call_id and content.
+	/// Creates a new ToolResponse with the provided tool_call_id and text content.
 	pub fn new(tool_call_id: impl Into<String>, content: impl Into<String>) -> Self {
+		Self::from_parts(tool_call_id, vec![ToolResponsePart::Text(content.into())])
+	}
+
+	/// Creates a new ToolResponse with a JSON value content.
+	pub fn from_json(tool_call_id: impl Into<String>, value: Value) -> Self {
+		Self::from_parts(tool_call_id, vec![ToolResponsePart::Json(value)])
+	}
+
+	/// Creates a new ToolResponse with the provided content parts.
+	pub fn from_parts(tool_call_id: impl Into<String>, parts: impl Into<Vec<ToolResponsePart>>) -> Self {
 		Self {
 			call_id: tool_call_id.into(),
-			content: content.into(),
+			content: parts.into(),
+			is_error: false,
 		}
 	}
 }
 
+/// Setters
+impl ToolResponse {
+	/// Appends a content part (e.g., a `Binary` screenshot).
+	pub fn append(mut self, part: impl Into<ToolResponsePart>) -> Self {
+		self.content.push(part.into());
+		self
+	}
+
+	/// Marks this response as a tool error.
+	pub fn with_is_error(mut self, is_error: bool) -> Self {
+		self.is_error = is_error;
+		self
+	}
+}
+
 /// Computed accessors
 impl ToolResponse {
 	/// Returns an approximate in-memory size of this `ToolResponse`, in bytes,
-	/// computed as the sum of the UTF-8 lengths of:
-	/// - `call_id`
-	/// - `content`
+	/// computed as the sum of:
+	/// - the UTF-8 length of `call_id`
+	/// - the size of each content part (see `ToolResponsePart::size`)
 	pub fn size(&self) -> usize {
-		self.call_id.len() + self.content.len()
+		self.call_id.len() + self.content.iter().map(ToolResponsePart::size).sum::<usize>()
+	}
+
+	/// Returns the text and JSON parts as text (JSON serialized), joined with new lines.
+	///
+	/// This is the content sent to the providers without structured tool results (e.g., OpenAI),
+	/// the binary parts being sent separately.
+	///
+	/// NOTE: Replaces the former `content: String` field (for a text response, the same text).
+	pub fn content_text(&self) -> String {
+		let texts: Vec<String> = self
+			.content
+			.iter()
+			.filter_map(|part| match part {
+				ToolResponsePart::Text(text) => Some(text.clone()),
+				ToolResponsePart::Json(value) => Some(value.to_string()),
+				ToolResponsePart::Binary(_) => None,
+			})
+			.collect();
+		texts.join("\n")
+	}
+
+	/// Returns the JSON value if it is the only part besides the binaries.
+	pub fn json_content(&self) -> Option<&Value> {
+		let mut non_binaries = self.content.iter().filter(|part| !matches!(part, ToolResponsePart::Binary(_)));
+		match (non_binaries.next(), non_binaries.next()) {
+			(Some(ToolResponsePart::Json(value)), None) => Some(value),
+			_ => None,
+		}
+	}
+
+	/// Returns references to the binary parts.
+	pub fn binaries(&self) -> Vec<&Binary> {
+		self.content
+			.iter()
+			.filter_map(|part| match part {
+				ToolResponsePart::Binary(binary) => Some(binary),
+				_ => None,
+			})
+			.collect()
 	}
 }
 
-/// Getters
-#[allow(unused)]
-impl ToolResponse {
-	fn tool_call_id(&self) -> &str {
-		&self.call_id
+// region:    --- ToolResponsePart
+
+/// A content part of a `ToolResponse`.
+#[derive(Debug, Clone, Serialize, Deserialize, From)]
+pub enum ToolResponsePart {
+	#[from(String, &String, &str)]
+	Text(String),
+
+	#[from]
+	Json(Value),
+
+	#[from]
+	Binary(Binary),
+}
+
+/// Computed accessors
+impl ToolResponsePart {
+	/// Returns an approximate in-memory size of this part, in bytes
+	/// (the JSON values are measured serialized, see `Binary::size` for the binaries).
+	pub fn size(&self) -> usize {
+		match self {
+			ToolResponsePart::Text(text) => text.len(),
+			ToolResponsePart::Json(value) => value.to_string().len(),
+			ToolResponsePart::Binary(binary) => binary.size(),
+		}
 	}
+}
 
-	fn content(&self) -> &str {
-		&self.content
+// endregion: --- ToolResponsePart
+
+// region:    --- Support
+
+fn deserialize_content<'de, D>(deserializer: D) -> Result<Vec<ToolResponsePart>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Content {
+		Text(String),
+		Parts(Vec<ToolResponsePart>),
 	}
+
+	Ok(match Content::deserialize(deserializer)? {
+		Content::Text(text) => vec![ToolResponsePart::Text(text)],
+		Content::Parts(parts) => parts,
+	})
 }
+
+// endregion: --- Support
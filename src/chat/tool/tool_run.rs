@@ -0,0 +1,42 @@
+use crate::chat::{ChatRequest, ChatResponse, ToolCall, Usage};
+use serde::{Deserialize, Serialize};
+
+/// Response of `Client::run_tools`: the full transcript and the last chat response.
+#[derive(Debug, Clone)]
+pub struct ToolRunResponse {
+	/// The initial request with all the assistant and tool messages appended, including the final assistant message.
+	/// Can be continued with a new user message.
+	pub chat_req: ChatRequest,
+
+	/// Chat response of the last model call (None when the run paused again before any model call).
+	pub chat_response: Option<ChatResponse>,
+
+	/// Usage summed over all the model calls.
+	pub usage: Usage,
+
+	/// Number of model calls.
+	pub iterations: u32,
+
+	/// Why the run stopped.
+	pub stop: ToolRunStop,
+
+	/// The tool calls paused by the `ToolPolicy` (when `stop` is `ToolRunStop::Paused`),
+	/// pending at the end of `chat_req`.
+	pub paused_tool_calls: Vec<ToolCall>,
+}
+
+/// Why `Client::run_tools` stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ToolRunStop {
+	/// The model answered without calling tools.
+	Completed,
+	/// `ChatOptions::max_tool_iterations` was reached, with tool calls still pending in the last chat response
+	/// (they are not part of `chat_req`).
+	MaxIterations,
+	/// `ChatOptions::max_tool_run_tokens` was reached, with tool calls still pending in the last chat response
+	/// (they are not part of `chat_req`).
+	MaxTokens,
+	/// A `ToolPolicy` paused tool calls (see `ToolDecision::Pause`).
+	/// Running the serialized `chat_req` again (e.g., after an approval) resumes with the pending tool calls.
+	Paused,
+}
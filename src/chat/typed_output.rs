@@ -0,0 +1,116 @@
+//! Typed structured output support (see `Client::exec_chat_typed`).
+
+use crate::chat::{ChatResponse, JsonSpec, validate_json_schema};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde_json::Value;
+
+// region:    --- TypedChatResponse
+
+/// Response of `Client::exec_chat_typed`: the deserialized value and the chat response it was parsed from.
+#[derive(Debug, Clone)]
+pub struct TypedChatResponse<T> {
+	/// Value deserialized from the response text.
+	pub value: T,
+
+	/// Chat response of the last (successful) call.
+	pub chat_response: ChatResponse,
+
+	/// Number of re-prompts needed to get a valid output (0 when the first output was valid).
+	pub repair_attempts: u32,
+}
+
+// endregion: --- TypedChatResponse
+
+// region:    --- Support
+
+/// Returns the content of the first markdown code fence of the text (e.g., ```` ```json ... ``` ````),
+/// or the trimmed text when there is no code fence.
+///
+/// NOTE: A text which is valid JSON (and does not start with a fence) is returned as is,
+///       as its string values can contain code fences.
+pub fn strip_code_fences(text: &str) -> &str {
+	let text = text.trim();
+	if !text.starts_with("```") && serde_json::from_str::<IgnoredAny>(text).is_ok() {
+		return text;
+	}
+	let Some(fence_start) = text.find("```") else {
+		return text;
+	};
+
+	// Skip the opening fence line (with its eventual language tag).
+	let after_fence = &text[fence_start + 3..];
+	let content = match after_fence.find('\n') {
+		Some(idx) => &after_fence[idx + 1..],
+		None => after_fence,
+	};
+
+	// The closing fence is the first one ending a valid JSON content (the JSON strings can contain fences),
+	// or the first one when none does.
+	let contents: Vec<&str> = content.match_indices("```").map(|(idx, _)| content[..idx].trim()).collect();
+	contents
+		.iter()
+		.find(|content| serde_json::from_str::<IgnoredAny>(content).is_ok())
+		.or(contents.first())
+		.copied()
+		.unwrap_or_else(|| content.trim())
+}
+
+/// Parses the response text (after stripping the code fences), validates it against the spec schema,
+/// and deserializes it into `T`.
+/// Returns the cause as a string, to be sent back to the model for a repair.
+pub(crate) fn parse_typed_output<T: DeserializeOwned>(text: &str, spec: &JsonSpec) -> core::result::Result<T, String> {
+	let value: Value = serde_json::from_str(strip_code_fences(text)).map_err(|err| err.to_string())?;
+
+	let violations = validate_json_schema(&spec.schema, &value);
+	if !violations.is_empty() {
+		let violations: Vec<String> = violations.iter().map(ToString::to_string).collect();
+		return Err(format!("schema violations: {}", violations.join("; ")));
+	}
+
+	serde_json::from_value(value).map_err(|err| err.to_string())
+}
+
+/// Re-prompt sent to the model when its output could not be parsed.
+pub(crate) fn typed_output_repair_prompt(cause: &str) -> String {
+	format!(
+		"Your previous response is not a valid JSON value for the requested schema.\nError: {cause}\n\
+		Reply again with only the JSON value matching the schema, without code fences or comments."
+	)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_strip_code_fences_json_fence() {
+		let text = "Here it is:\n```json\n{\"a\": 1}\n```\nDone.";
+
+		assert_eq!(strip_code_fences(text), r#"{"a": 1}"#);
+	}
+
+	#[test]
+	fn test_strip_code_fences_no_fence() {
+		assert_eq!(strip_code_fences("  {\"a\": 1}\n"), r#"{"a": 1}"#);
+	}
+
+	#[test]
+	fn test_strip_code_fences_fence_in_json_string() {
+		let text = "{\"code\":\"```rust\\nfn main() {}\\n```\"}";
+
+		assert_eq!(strip_code_fences(text), text);
+		let fenced = format!("```json\n{text}\n```");
+		assert_eq!(strip_code_fences(&fenced), text);
+	}
+
+	#[test]
+	fn test_strip_code_fences_unclosed_fence() {
+		assert_eq!(strip_code_fences("```\n[1, 2]"), "[1, 2]");
+	}
+}
+
+// endregion: --- Tests
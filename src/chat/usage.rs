@@ -1,3 +1,4 @@
+use crate::chat::Pricing;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, skip_serializing_none};
 
@@ -27,6 +28,87 @@ pub struct Usage {
 	pub total_tokens: Option<i32>,
 }
 
+/// Cost
+impl Usage {
+	/// Returns the cost (USD) of this usage with the given pricing (prices per million tokens).
+	///
+	/// - Input: `prompt_tokens` includes the cached and cache creation tokens, which are priced
+	///   at `cached_input` and `cache_write_5m` / `cache_write_1h` (creation tokens without a TTL breakdown are priced as 5m).
+	/// - Output: `completion_tokens` includes the reasoning tokens (already normalized for Gemini),
+	///   which are priced at `reasoning`.
+	pub fn cost(&self, pricing: &Pricing) -> f64 {
+		let tokens = |value: Option<i32>| f64::from(value.unwrap_or(0).max(0));
+
+		// -- Input
+		let prompt_details = self.prompt_tokens_details.as_ref();
+		let cached = tokens(prompt_details.and_then(|d| d.cached_tokens));
+		let cache_creation = tokens(prompt_details.and_then(|d| d.cache_creation_tokens));
+		let creation_details = prompt_details.and_then(|d| d.cache_creation_details.as_ref());
+		let cache_write_1h = tokens(creation_details.and_then(|d| d.ephemeral_1h_tokens));
+		let cache_write_5m = tokens(creation_details.and_then(|d| d.ephemeral_5m_tokens))
+			.max(cache_creation - cache_write_1h)
+			.max(0.);
+		let input = (tokens(self.prompt_tokens) - cached - cache_write_5m - cache_write_1h).max(0.);
+
+		// -- Output
+		let reasoning = tokens(self.completion_tokens_details.as_ref().and_then(|d| d.reasoning_tokens));
+		let output = (tokens(self.completion_tokens) - reasoning).max(0.);
+
+		let cost = input * pricing.input
+			+ cached * pricing.cached_input_price()
+			+ cache_write_5m * pricing.cache_write_5m_price()
+			+ cache_write_1h * pricing.cache_write_1h_price()
+			+ output * pricing.output
+			+ reasoning * pricing.reasoning_price();
+
+		cost / 1_000_000.
+	}
+}
+
+/// Aggregation
+impl Usage {
+	/// Adds the token counts of `other` to this usage (e.g., to sum the usage of several calls).
+	///
+	/// A counter stays `None` only when it is `None` on both sides.
+	pub fn accumulate(&mut self, other: &Usage) {
+		add_tokens(&mut self.prompt_tokens, other.prompt_tokens);
+		add_tokens(&mut self.completion_tokens, other.completion_tokens);
+		add_tokens(&mut self.total_tokens, other.total_tokens);
+
+		if let Some(other_details) = other.prompt_tokens_details.as_ref() {
+			let details = self.prompt_tokens_details.get_or_insert_with(Default::default);
+			add_tokens(&mut details.cache_creation_tokens, other_details.cache_creation_tokens);
+			add_tokens(&mut details.cached_tokens, other_details.cached_tokens);
+			add_tokens(&mut details.audio_tokens, other_details.audio_tokens);
+			if let Some(other_creation) = other_details.cache_creation_details.as_ref() {
+				let creation = details.cache_creation_details.get_or_insert_with(Default::default);
+				add_tokens(&mut creation.ephemeral_5m_tokens, other_creation.ephemeral_5m_tokens);
+				add_tokens(&mut creation.ephemeral_1h_tokens, other_creation.ephemeral_1h_tokens);
+			}
+		}
+
+		if let Some(other_details) = other.completion_tokens_details.as_ref() {
+			let details = self.completion_tokens_details.get_or_insert_with(Default::default);
+			add_tokens(
+				&mut details.accepted_prediction_tokens,
+				other_details.accepted_prediction_tokens,
+			);
+			add_tokens(
+				&mut details.rejected_prediction_tokens,
+				other_details.rejected_prediction_tokens,
+			);
+			add_tokens(&mut details.reasoning_tokens, other_details.reasoning_tokens);
+			add_tokens(&mut details.audio_tokens, other_details.audio_tokens);
+		}
+	}
+}
+
+fn add_tokens(value: &mut Option<i32>, other: Option<i32>) {
+	if let Some(other) = other {
+		*value = Some(value.unwrap_or(0) + other);
+	}
+}
+
 impl Usage {
 	/// Remove detail objects that contain only `None` fields.
 	pub fn compact_details(&mut self) {
@@ -0,0 +1,449 @@
+//! Support of the agent loops (`Client::run_tools` and `Client::run_tools_stream`).
+
+use crate::chat::{
+	AgentEvent, AgentTurnEnd, ChatMessage, ChatOptions, ChatOptionsSet, ChatRequest, ChatRole, ChatStreamEvent,
+	ContentPart, StreamEnd, ToolCall, ToolCallOutcome, ToolRegistry, ToolResponse, ToolRunStop, Usage,
+};
+use crate::{Client, Result, ServiceTarget};
+use futures::StreamExt as _;
+use futures::channel::mpsc::UnboundedSender;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Default of `ChatOptions::max_tool_iterations`.
+pub(super) const DEFAULT_MAX_TOOL_ITERATIONS: u32 = 10;
+
+/// Returns the registry tools added to the request tools (unless already there, by name).
+pub(super) fn with_registry_tools(mut chat_req: ChatRequest, registry: &ToolRegistry) -> ChatRequest {
+	for tool in registry.tools() {
+		let exists = chat_req.tools.iter().flatten().any(|t| t.name == tool.name);
+		if !exists {
+			chat_req = chat_req.append_tool(tool.clone());
+		}
+	}
+	chat_req
+}
+
+/// Returns why the agent loop stops after this model call, if it does.
+pub(super) fn tool_run_stop(
+	has_tool_calls: bool,
+	iterations: u32,
+	options_set: &ChatOptionsSet<'_, '_>,
+	usage: &Usage,
+) -> Option<ToolRunStop> {
+	let max_iterations = options_set.max_tool_iterations().unwrap_or(DEFAULT_MAX_TOOL_ITERATIONS);
+
+	if !has_tool_calls {
+		Some(ToolRunStop::Completed)
+	} else if iterations >= max_iterations {
+		Some(ToolRunStop::MaxIterations)
+	} else if tool_run_tokens_reached(options_set, usage) {
+		Some(ToolRunStop::MaxTokens)
+	} else {
+		None
+	}
+}
+
+/// Returns true when the `max_tool_run_tokens` budget (if any) is reached by the run usage.
+fn tool_run_tokens_reached(options_set: &ChatOptionsSet<'_, '_>, usage: &Usage) -> bool {
+	options_set
+		.max_tool_run_tokens()
+		.is_some_and(|max_tokens| i64::from(usage.total_tokens.unwrap_or_default()) >= i64::from(max_tokens))
+}
+
+// region:    --- Tool Calls
+
+/// Returns the tool calls of the last assistant message without a tool response after it
+/// (e.g., the calls paused by the `ToolPolicy`, when resuming a run).
+pub(super) fn pending_tool_calls(chat_req: &ChatRequest) -> Vec<ToolCall> {
+	let mut responded_ids: Vec<&str> = Vec::new();
+	for msg in chat_req.messages.iter().rev() {
+		match msg.role {
+			ChatRole::Tool => responded_ids.extend(msg.content.tool_responses().iter().map(|res| res.call_id.as_str())),
+			ChatRole::Assistant => {
+				return msg
+					.content
+					.tool_calls()
+					.into_iter()
+					.filter(|call| !responded_ids.contains(&call.call_id.as_str()))
+					.cloned()
+					.collect();
+			}
+			_ => break,
+		}
+	}
+	Vec::new()
+}
+
+/// Returns the calls count by function name of the tool calls already responded in the request,
+/// so the `ToolPolicy` max calls hold across a paused and resumed run.
+pub(super) fn responded_tool_call_counts(chat_req: &ChatRequest) -> HashMap<String, u32> {
+	let responded_ids: HashSet<&str> = chat_req
+		.messages
+		.iter()
+		.flat_map(|msg| msg.content.tool_responses())
+		.map(|res| res.call_id.as_str())
+		.collect();
+	let mut call_counts = HashMap::new();
+	for tool_call in chat_req.messages.iter().flat_map(|msg| msg.content.tool_calls()) {
+		if responded_ids.contains(tool_call.call_id.as_str()) {
+			*call_counts.entry(tool_call.fn_name.clone()).or_default() += 1;
+		}
+	}
+	call_counts
+}
+
+/// Replaces the tool calls of the last assistant message by their rewritten version (same call id),
+/// so the transcript has the arguments actually executed.
+fn record_rewritten_tool_calls(chat_req: &mut ChatRequest, rewritten_calls: &[ToolCall]) {
+	if rewritten_calls.is_empty() {
+		return;
+	}
+	let Some(assistant_msg) = chat_req.messages.iter_mut().rev().find(|msg| msg.role == ChatRole::Assistant) else {
+		return;
+	};
+	for part in &mut assistant_msg.content {
+		if let ContentPart::ToolCall(tool_call) = part
+			&& let Some(rewritten) = rewritten_calls.iter().find(|call| call.call_id == tool_call.call_id)
+		{
+			*tool_call = rewritten.clone();
+		}
+	}
+}
+
+/// Returns the tool responses appended to the request, merged into its last message if it is a tool message
+/// (so a resumed run does not add consecutive tool messages).
+fn append_tool_responses(mut chat_req: ChatRequest, tool_responses: Vec<ToolResponse>) -> ChatRequest {
+	if tool_responses.is_empty() {
+		return chat_req;
+	}
+	match chat_req.messages.last_mut() {
+		Some(last_msg) if last_msg.role == ChatRole::Tool => {
+			last_msg
+				.content
+				.extend(tool_responses.into_iter().map(ContentPart::ToolResponse));
+			chat_req
+		}
+		_ => chat_req.append_message(ChatMessage::from(tool_responses)),
+	}
+}
+
+/// Decides (with the registry `ToolPolicy`) and executes the tool calls concurrently.
+///
+/// Returns the request with the tool responses (and the rewritten tool calls), and the paused tool calls,
+/// in the tool calls order.
+pub(super) async fn run_tool_calls(
+	registry: &ToolRegistry,
+	mut chat_req: ChatRequest,
+	tool_calls: Vec<ToolCall>,
+	call_counts: &mut HashMap<String, u32>,
+) -> (ChatRequest, Vec<ToolCall>) {
+	// NOTE: The prechecks are sequential, so the max calls count in the tool calls order.
+	let prechecks: Vec<_> = tool_calls.iter().map(|call| registry.precheck(call, call_counts)).collect();
+	let outcomes = futures::future::join_all(
+		tool_calls
+			.into_iter()
+			.zip(prechecks)
+			.map(|(call, precheck)| registry.decide_and_execute(call, precheck)),
+	)
+	.await;
+
+	let mut tool_responses = Vec::new();
+	let mut rewritten_calls = Vec::new();
+	let mut paused_calls = Vec::new();
+	for outcome in outcomes {
+		match outcome {
+			ToolCallOutcome::Done(tool_response) => tool_responses.push(tool_response),
+			ToolCallOutcome::Rewritten(tool_call, tool_response) => {
+				rewritten_calls.push(tool_call);
+				tool_responses.push(tool_response);
+			}
+			ToolCallOutcome::Paused(tool_call) => paused_calls.push(tool_call),
+		}
+	}
+	record_rewritten_tool_calls(&mut chat_req, &rewritten_calls);
+	(append_tool_responses(chat_req, tool_responses), paused_calls)
+}
+
+// endregion: --- Tool Calls
+
+// region:    --- Streaming Agent Loop
+
+/// Runs the streaming agent loop, sending the events to `tx` (see `Client::run_tools_stream`).
+pub(super) async fn run_agent_stream(
+	client: Client,
+	target: ServiceTarget,
+	chat_req: ChatRequest,
+	registry: ToolRegistry,
+	options: ChatOptions,
+	tx: UnboundedSender<Result<AgentEvent>>,
+) {
+	if let Err(err) = run_agent_turns(&client, target, chat_req, &registry, &options, &tx).await {
+		let _ = tx.unbounded_send(Err(err));
+	}
+}
+
+async fn run_agent_turns(
+	client: &Client,
+	target: ServiceTarget,
+	mut chat_req: ChatRequest,
+	registry: &ToolRegistry,
+	options: &ChatOptions,
+	tx: &UnboundedSender<Result<AgentEvent>>,
+) -> Result<()> {
+	let options_set = ChatOptionsSet::default()
+		.with_chat_options(Some(options))
+		.with_client_options(client.config().chat_options());
+	let mut usage = Usage::default();
+	let mut iterations = 0;
+	let mut call_counts = responded_tool_call_counts(&chat_req);
+	let max_iterations = options_set.max_tool_iterations().unwrap_or(DEFAULT_MAX_TOOL_ITERATIONS);
+
+	// -- Resume the pending tool calls, if any
+	let pending_calls = pending_tool_calls(&chat_req);
+	if !pending_calls.is_empty() {
+		let mut turn_tools = TurnTools::new(registry, tx, &mut call_counts);
+		turn_tools.dispatch_all(pending_calls);
+		turn_tools.finish().await;
+		let paused = !turn_tools.paused.is_empty();
+		record_rewritten_tool_calls(&mut chat_req, &turn_tools.rewritten);
+		chat_req = append_tool_responses(chat_req, turn_tools.into_ordered_responses());
+
+		if paused {
+			let turn_end = AgentTurnEnd {
+				iteration: 0,
+				stream_end: StreamEnd::default(),
+				stop: Some(ToolRunStop::Paused),
+				chat_req: Some(chat_req),
+			};
+			send(tx, AgentEvent::TurnEnd(turn_end));
+			return Ok(());
+		}
+	}
+
+	loop {
+		let mut stream = client
+			.exec_chat_stream(target.clone(), chat_req.clone(), Some(options))
+			.await?
+			.stream;
+		iterations += 1;
+
+		let mut turn_tools = TurnTools::new(registry, tx, &mut call_counts);
+		// NOTE: As with `run_tools`, the tool calls of the last allowed iteration are not run,
+		//       nor the ones of the turn reaching the tokens budget (so they wait for the turn usage).
+		turn_tools.hold = iterations >= max_iterations;
+		turn_tools.deferred = options_set.max_tool_run_tokens().is_some();
+		let mut streaming_call: Option<ToolCall> = None;
+		let mut stream_end: Option<StreamEnd> = None;
+
+		// -- Stream the turn, dispatching each tool call as soon as it is complete
+		while stream_end.is_none() || !turn_tools.running.is_empty() {
+			tokio::select! {
+				event = stream.next(), if stream_end.is_none() => match event {
+					Some(Ok(ChatStreamEvent::Chunk(chunk))) => send(tx, AgentEvent::Text(chunk.content)),
+					Some(Ok(ChatStreamEvent::ReasoningChunk(chunk))) => send(tx, AgentEvent::Reasoning(chunk.content)),
+					Some(Ok(ChatStreamEvent::ToolCallChunk(tool_chunk))) => {
+						// NOTE: Tool calls are streamed one after the other, so a new call completes the previous one.
+						let tool_call = tool_chunk.tool_call;
+						if let Some(previous) = streaming_call.take_if(|call| call.call_id != tool_call.call_id) {
+							turn_tools.dispatch(parsed_tool_call(previous));
+						}
+						streaming_call = Some(tool_call);
+					}
+					Some(Ok(ChatStreamEvent::End(end))) => {
+						// The captured tool calls are the complete ones.
+						let captured_calls: Vec<ToolCall> =
+							end.captured_tool_calls().unwrap_or_default().into_iter().cloned().collect();
+						if captured_calls.is_empty() {
+							turn_tools.dispatch_all(streaming_call.take().map(parsed_tool_call));
+						} else {
+							turn_tools.dispatch_all(captured_calls);
+						}
+						let mut run_usage = usage.clone();
+						if let Some(turn_usage) = &end.captured_usage {
+							run_usage.accumulate(turn_usage);
+						}
+						turn_tools.release(tool_run_tokens_reached(&options_set, &run_usage));
+						stream_end = Some(end);
+					}
+					Some(Ok(_)) => (),
+					Some(Err(err)) => return Err(err),
+					None => {
+						turn_tools.dispatch_all(streaming_call.take().map(parsed_tool_call));
+						turn_tools.release(tool_run_tokens_reached(&options_set, &usage));
+						stream_end = Some(StreamEnd::default());
+					}
+				},
+				Some(outcome) = turn_tools.running.next(), if !turn_tools.running.is_empty() => {
+					turn_tools.on_outcome(outcome);
+				}
+			}
+		}
+
+		// -- Append the turn to the transcript
+		let stream_end = stream_end.unwrap_or_default();
+		if let Some(turn_usage) = &stream_end.captured_usage {
+			usage.accumulate(turn_usage);
+		}
+		let has_tool_calls = !turn_tools.dispatched.is_empty();
+		let paused = !turn_tools.paused.is_empty();
+		let stop = match tool_run_stop(has_tool_calls, iterations, &options_set, &usage) {
+			// NOTE: When paused, the transcript ends with the pending tool calls, to resume them.
+			_ if paused => Some(ToolRunStop::Paused),
+			stop => stop,
+		};
+
+		// NOTE: The held tool calls are not appended, so the transcript stays valid to continue.
+		if !matches!(stop, Some(ToolRunStop::MaxIterations | ToolRunStop::MaxTokens)) {
+			if let Some(content) = &stream_end.captured_content {
+				let assistant_msg = ChatMessage::assistant(content.clone())
+					.with_reasoning_content(stream_end.captured_reasoning_content.clone());
+				chat_req = chat_req.append_message(assistant_msg);
+			}
+			record_rewritten_tool_calls(&mut chat_req, &turn_tools.rewritten);
+			chat_req = append_tool_responses(chat_req, turn_tools.into_ordered_responses());
+		}
+
+		// -- End the turn
+		let turn_end = AgentTurnEnd {
+			iteration: iterations,
+			stream_end,
+			stop,
+			chat_req: stop.map(|_| chat_req.clone()),
+		};
+		send(tx, AgentEvent::TurnEnd(turn_end));
+
+		if stop.is_some() {
+			return Ok(());
+		}
+	}
+}
+
+/// The tool calls of a turn, running concurrently.
+struct TurnTools<'a> {
+	registry: &'a ToolRegistry,
+	tx: &'a UnboundedSender<Result<AgentEvent>>,
+	/// The tool calls count of the run, by function name (see `ToolPolicy`).
+	call_counts: &'a mut HashMap<String, u32>,
+	/// The dispatched tool calls, in order.
+	dispatched: Vec<ToolCall>,
+	running: FuturesUnordered<BoxFuture<'a, ToolCallOutcome>>,
+	/// The finished tool responses, by call id.
+	responses: HashMap<String, ToolResponse>,
+	/// When true, the tool calls are only recorded in `dispatched`, not run.
+	hold: bool,
+	/// When true, the tool calls are recorded in `dispatched`, and run on `release` (unless held).
+	deferred: bool,
+	/// The tool calls rewritten by the `ToolPolicy` (as executed).
+	rewritten: Vec<ToolCall>,
+	/// The tool calls paused by the `ToolPolicy`.
+	paused: Vec<ToolCall>,
+}
+
+impl<'a> TurnTools<'a> {
+	fn new(
+		registry: &'a ToolRegistry,
+		tx: &'a UnboundedSender<Result<AgentEvent>>,
+		call_counts: &'a mut HashMap<String, u32>,
+	) -> Self {
+		Self {
+			registry,
+			tx,
+			call_counts,
+			hold: false,
+			deferred: false,
+			dispatched: Vec::new(),
+			running: FuturesUnordered::new(),
+			responses: HashMap::new(),
+			rewritten: Vec::new(),
+			paused: Vec::new(),
+		}
+	}
+
+	/// Starts the policy decision and the tool call handler (once per call id).
+	fn dispatch(&mut self, tool_call: ToolCall) {
+		if self.dispatched.iter().any(|call| call.call_id == tool_call.call_id) {
+			return;
+		}
+		self.dispatched.push(tool_call.clone());
+		if !self.hold && !self.deferred {
+			self.start(tool_call);
+		}
+	}
+
+	/// Runs the deferred tool calls, unless `hold` (the calls are then held as well).
+	fn release(&mut self, hold: bool) {
+		self.hold |= hold;
+		if !std::mem::take(&mut self.deferred) || self.hold {
+			return;
+		}
+		for tool_call in self.dispatched.clone() {
+			self.start(tool_call);
+		}
+	}
+
+	fn start(&mut self, tool_call: ToolCall) {
+		send(self.tx, AgentEvent::ToolStarted(tool_call.clone()));
+		let registry = self.registry;
+		let precheck = registry.precheck(&tool_call, self.call_counts);
+		self.running.push(Box::pin(registry.decide_and_execute(tool_call, precheck)));
+	}
+
+	fn on_outcome(&mut self, outcome: ToolCallOutcome) {
+		match outcome {
+			ToolCallOutcome::Done(tool_response) => {
+				send(self.tx, AgentEvent::ToolFinished(tool_response.clone()));
+				self.responses.insert(tool_response.call_id.clone(), tool_response);
+			}
+			ToolCallOutcome::Rewritten(tool_call, tool_response) => {
+				self.rewritten.push(tool_call);
+				self.on_outcome(ToolCallOutcome::Done(tool_response));
+			}
+			ToolCallOutcome::Paused(tool_call) => {
+				send(self.tx, AgentEvent::ToolPaused(tool_call.clone()));
+				self.paused.push(tool_call);
+			}
+		}
+	}
+
+	/// Waits for all the running tool calls.
+	async fn finish(&mut self) {
+		while let Some(outcome) = self.running.next().await {
+			self.on_outcome(outcome);
+		}
+	}
+
+	fn dispatch_all(&mut self, tool_calls: impl IntoIterator<Item = ToolCall>) {
+		for tool_call in tool_calls {
+			self.dispatch(tool_call);
+		}
+	}
+
+	/// Returns the tool responses in the tool calls order.
+	fn into_ordered_responses(mut self) -> Vec<ToolResponse> {
+		self.dispatched
+			.iter()
+			.filter_map(|call| self.responses.remove(&call.call_id))
+			.collect()
+	}
+}
+
+/// Returns the streamed tool call with its arguments parsed
+/// (the adapters streaming argument fragments give the accumulated arguments as a string).
+fn parsed_tool_call(mut tool_call: ToolCall) -> ToolCall {
+	if let Value::String(arguments) = &tool_call.fn_arguments
+		&& let Ok(arguments) = serde_json::from_str(arguments)
+	{
+		tool_call.fn_arguments = arguments;
+	}
+	tool_call
+}
+
+/// Sends the event (ignoring a dropped receiver, the task being aborted with the `AgentStream`).
+fn send(tx: &UnboundedSender<Result<AgentEvent>>, event: AgentEvent) {
+	let _ = tx.unbounded_send(Ok(event));
+}
+
+// endregion: --- Streaming Agent Loop
@@ -1,10 +1,10 @@
-use crate::chat::ChatOptions;
+use crate::chat::{ChatOptions, PricingRegistry, SpendTracker};
 use crate::resolver::{
 	AuthResolver, IntoAuthResolverFn, IntoModelMapperFn, IntoServiceTargetResolverFn, ModelMapper,
 	ServiceTargetResolver,
 };
-use crate::webc::WebClient;
-use crate::{Client, ClientConfig, WebConfig};
+use crate::webc::{Cassette, Transport, WebClient};
+use crate::{Client, ClientConfig, ClientMiddleware, RateLimiter, ResponseCache, RetryPolicy, WebConfig};
 use std::sync::Arc;
 
 /// Builder for `Client`.
@@ -16,16 +16,27 @@ use std::sync::Arc;
 pub struct ClientBuilder {
 	web_client: Option<WebClient>,
 	config: Option<ClientConfig>,
+	cassette: Option<Cassette>,
 }
 
 /// Builder methods
 impl ClientBuilder {
 	/// Use a custom `reqwest::Client`.
+	///
+	/// Takes precedence over a `WebConfig`, which is then ignored (see `build`).
 	pub fn with_reqwest(mut self, reqwest_client: reqwest::Client) -> Self {
 		self.web_client = Some(WebClient::from_reqwest_client(reqwest_client));
 		self
 	}
 
+	/// Use a custom `Transport` for all the web calls (e.g., `webc::InMemoryTransport` for tests).
+	///
+	/// Takes precedence over a `WebConfig`, which is then ignored (see `build`).
+	pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+		self.web_client = Some(WebClient::from_transport(Arc::new(transport)));
+		self
+	}
+
 	/// Set a `ClientConfig`.
 	pub fn with_config(mut self, config: ClientConfig) -> Self {
 		self.config = Some(config);
@@ -33,11 +44,19 @@ impl ClientBuilder {
 	}
 
 	/// Set `WebConfig` used to build the internal `reqwest::Client` (creates `ClientConfig` if absent).
+	///
+	/// Ignored when a custom `reqwest::Client` or `Transport` is set (`with_reqwest` / `with_transport`).
 	pub fn with_web_config(mut self, req_options: WebConfig) -> Self {
 		let client_config = self.config.get_or_insert_with(ClientConfig::default);
 		client_config.web_config = Some(req_options);
 		self
 	}
+
+	/// Record or replay the web calls with a `Cassette` (e.g., for offline, deterministic tests).
+	pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+		self.cassette = Some(cassette);
+		self
+	}
 }
 
 /// Builder ClientConfig passthrough convenient setters.
@@ -50,6 +69,48 @@ impl ClientBuilder {
 		self
 	}
 
+	/// Set `RetryPolicy` on `ClientConfig` (creates it if absent).
+	pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.retry_policy = Some(retry_policy);
+		self
+	}
+
+	/// Append a `ClientMiddleware` on `ClientConfig` (creates it if absent).
+	pub fn with_middleware(mut self, middleware: impl ClientMiddleware + 'static) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.middlewares.push(Arc::new(middleware));
+		self
+	}
+
+	/// Set a `ResponseCache` on `ClientConfig` (creates it if absent).
+	pub fn with_response_cache(mut self, response_cache: impl ResponseCache + 'static) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.response_cache = Some(Arc::new(response_cache));
+		self
+	}
+
+	/// Set a `RateLimiter` on `ClientConfig` (creates it if absent).
+	pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.rate_limiter = Some(rate_limiter);
+		self
+	}
+
+	/// Set a `PricingRegistry` on `ClientConfig` (creates it if absent).
+	pub fn with_pricing_registry(mut self, pricing_registry: PricingRegistry) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.pricing_registry = Some(pricing_registry);
+		self
+	}
+
+	/// Set a `SpendTracker` on `ClientConfig` (creates it if absent).
+	pub fn with_spend_tracker(mut self, spend_tracker: SpendTracker) -> Self {
+		let client_config = self.config.get_or_insert_with(ClientConfig::default);
+		client_config.spend_tracker = Some(spend_tracker);
+		self
+	}
+
 	/// Set `AuthResolver` on `ClientConfig` (creates it if absent).
 	pub fn with_auth_resolver(mut self, auth_resolver: AuthResolver) -> Self {
 		let client_config = self.config.get_or_insert_with(ClientConfig::default);
@@ -98,8 +159,15 @@ impl ClientBuilder {
 
 impl ClientBuilder {
 	/// Build a `Client`.
+	///
+	/// A custom `reqwest::Client` or `Transport` takes precedence over the `WebConfig` (logged as a warning).
 	pub fn build(self) -> Client {
 		let config = self.config.unwrap_or_default();
+		if self.web_client.is_some() && config.web_config().is_some() {
+			tracing::warn!(
+				"genai - WebConfig ignored, as a custom reqwest::Client or Transport is set on the ClientBuilder"
+			);
+		}
 
 		// Create WebClient based on configuration
 		let web_client = if let Some(web_client) = self.web_client {
@@ -115,6 +183,7 @@ impl ClientBuilder {
 			// Use default WebClient
 			WebClient::default()
 		};
+		let web_client = web_client.with_cassette(self.cassette);
 
 		let inner = super::ClientInner { web_client, config };
 		Client { inner: Arc::new(inner) }
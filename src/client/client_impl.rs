@@ -1,9 +1,25 @@
 use crate::adapter::{AdapterDispatcher, AdapterKind, ServiceType, WebRequestData};
-use crate::chat::{ChatOptions, ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse};
-use crate::client::ModelSpec;
+use crate::chat::{
+	AgentStream, ChatMessage, ChatOptions, ChatOptionsSet, ChatRequest, ChatResponse, ChatStreamResponse, JsonSpec,
+	Pricing, ToolCall, ToolRegistry, ToolRunResponse, ToolRunStop, TypedChatResponse, Usage, parse_typed_output,
+	typed_output_repair_prompt,
+};
+use crate::client::agent_loop::{
+	pending_tool_calls, responded_tool_call_counts, run_agent_stream, run_tool_calls, tool_run_stop,
+	with_registry_tools,
+};
+use crate::client::{
+	ModelSpec, RateLimitPermit, ResponseCache, RetryPolicy, chat_cache_key, embed_cache_key, estimate_request_tokens,
+};
 use crate::embed::{EmbedOptions, EmbedOptionsSet, EmbedRequest, EmbedResponse};
 use crate::resolver::AuthData;
-use crate::{Client, Error, ModelIden, Result, ServiceTarget};
+use crate::support::merge_json;
+use crate::webc::WebResponse;
+use crate::{BoxError, Client, Error, Headers, ModelIden, Result, ServiceTarget, webc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::sync::Arc;
 
 /// High-level client APIs.
 impl Client {
@@ -67,59 +83,230 @@ impl Client {
 	) -> Result<ChatResponse> {
 		let options_set = ChatOptionsSet::default()
 			.with_chat_options(options)
-			.with_client_options(self.config().chat_options());
+			.with_client_options(self.config().chat_options())
+			.with_client_retry_policy(self.config().retry_policy());
 
 		let target = self.config().resolve_model_spec(model.into()).await?;
 		let model = target.model.clone();
-		let auth_data = target.auth.clone();
-
-		let WebRequestData {
-			mut url,
-			mut headers,
-			payload,
-		} = AdapterDispatcher::to_web_request_data(target, ServiceType::Chat, chat_req, options_set.clone())?;
 
-		if let Some(extra_headers) = options.and_then(|o| o.extra_headers.as_ref()) {
-			headers.merge_with(extra_headers);
+		// -- Response cache lookup
+		let cache = self.response_cache(options_set.response_cache().unwrap_or(true));
+		let cache_key = cache.map(|_| chat_cache_key(&model, &chat_req, &options_set));
+		if let Some((cache, key)) = cache.zip(cache_key.as_deref())
+			&& let Some(mut chat_res) = cache_get::<ChatResponse>(cache.as_ref(), key)
+		{
+			chat_res.cache_hit = true;
+			chat_res.pricing = self.model_pricing(&chat_res);
+			return Ok(chat_res);
 		}
 
-		if let AuthData::RequestOverride {
-			url: override_url,
-			headers: override_headers,
-		} = auth_data
-		{
-			url = override_url;
-			headers = override_headers;
+		// -- Several candidates, natively or by fanning out the calls
+		let candidate_count = options_set.candidate_count().unwrap_or(1);
+		let mut chat_res = if candidate_count > 1 && !model.adapter_kind.supports_candidate_count() {
+			if !options_set.candidate_fan_out().unwrap_or_default() {
+				return Err(Error::AdapterNotSupported {
+					adapter_kind: model.adapter_kind,
+					feature: "candidate_count (enable ChatOptions::with_candidate_fan_out)".to_string(),
+				});
+			}
+			self.exec_chat_fan_out(&target, chat_req, &options_set, candidate_count).await?
+		} else {
+			self.exec_chat_call(&target, chat_req, &options_set).await?
 		};
 
-		let web_res = self
-			.web_client()
-			.do_post(&url, &headers, &payload)
-			.await
-			.map_err(|webc_error| Error::WebModelCall {
-				model_iden: model.clone(),
-				webc_error,
-			})?;
+		chat_res.pricing = self.model_pricing(&chat_res);
+		if let (Some(spend_tracker), Some(cost)) = (self.config().spend_tracker(), chat_res.cost()) {
+			spend_tracker.record(&chat_res.model_iden, cost);
+		}
+		if let Some((cache, key)) = cache.zip(cache_key.as_deref()) {
+			cache_put(cache.as_ref(), key, &chat_res);
+		}
 
-		// Note: here we capture/clone the raw body if set in the options_set
-		let captured_raw_body = options_set.capture_raw_body().unwrap_or_default().then(|| web_res.body.clone());
+		Ok(chat_res)
+	}
 
-		match AdapterDispatcher::to_chat_response(model.clone(), web_res, options_set) {
-			Ok(mut chat_res) => {
-				chat_res.captured_raw_body = captured_raw_body;
-				Ok(chat_res)
+	/// Sends a chat request to each model in order until one succeeds.
+	///
+	/// Each candidate goes through the same resolution and execution as [`Client::exec_chat`]
+	/// (including the retry policy, if any).
+	///
+	/// Falls back to the next model only on errors that another provider/model may not have
+	/// (transient `ErrorKind`s, connect errors, `Error::AdapterNotSupported`).
+	/// Any other error (e.g., bad request, auth) is returned immediately.
+	/// When all candidates fail, the last error is returned.
+	///
+	/// The `ChatResponse.model_iden` tells which model actually served the answer.
+	pub async fn exec_chat_with_fallbacks<M>(
+		&self,
+		models: impl IntoIterator<Item = M>,
+		chat_req: ChatRequest,
+		options: Option<&ChatOptions>,
+	) -> Result<ChatResponse>
+	where
+		M: Into<ModelSpec>,
+	{
+		let mut last_err: Option<Error> = None;
+
+		for model in models {
+			match self.exec_chat(model, chat_req.clone(), options).await {
+				Ok(chat_res) => return Ok(chat_res),
+				Err(err) if is_fallback_error(&err) => {
+					tracing::warn!("genai - model call failed, falling back to next model. Cause: {err}");
+					last_err = Some(err);
+				}
+				Err(err) => return Err(err),
 			}
-			Err(err) => {
-				let response_body = captured_raw_body.unwrap_or_else(|| {
-					"Raw response not captured. Use the ChatOptions.capturre_raw_body flag to see raw response in this error".into()
+		}
+
+		Err(last_err.unwrap_or(Error::FallbackModelsEmpty))
+	}
+
+	/// Sends a chat request with a structured output spec, and deserializes the response text into `T`.
+	///
+	/// The code fences around the JSON are stripped, and the value is validated against the spec schema
+	/// (see `chat::validate_json_schema`). When the output is invalid, the model is re-prompted
+	/// with the error, up to `ChatOptions::max_output_repairs` times (default: 1),
+	/// then `Error::TypedOutputInvalid` is returned.
+	///
+	/// The other `options` (and the client ones) apply as with [`Client::exec_chat`].
+	pub async fn exec_chat_typed<T: DeserializeOwned>(
+		&self,
+		model: impl Into<ModelSpec>,
+		chat_req: ChatRequest,
+		spec: JsonSpec,
+		options: Option<&ChatOptions>,
+	) -> Result<TypedChatResponse<T>> {
+		let options = options.cloned().unwrap_or_default().with_response_format(spec.clone());
+		let max_repairs = ChatOptionsSet::default()
+			.with_chat_options(Some(&options))
+			.with_client_options(self.config().chat_options())
+			.max_output_repairs()
+			.unwrap_or(1);
+
+		// Resolve once, so the repairs go to the same target.
+		let target = self.resolve_service_target(model).await?;
+		let mut chat_req = chat_req;
+		let mut repair_attempts = 0;
+
+		loop {
+			let chat_res = self.exec_chat(target.clone(), chat_req.clone(), Some(&options)).await?;
+			let text = chat_res.first_text().unwrap_or_default().to_string();
+
+			match parse_typed_output::<T>(&text, &spec) {
+				Ok(value) => {
+					return Ok(TypedChatResponse {
+						value,
+						chat_response: chat_res,
+						repair_attempts,
+					});
+				}
+				Err(cause) if repair_attempts < max_repairs => {
+					repair_attempts += 1;
+					chat_req = chat_req
+						.append_message(ChatMessage::assistant(text))
+						.append_message(ChatMessage::user(typed_output_repair_prompt(&cause)));
+				}
+				Err(cause) => {
+					return Err(Error::TypedOutputInvalid {
+						model_iden: chat_res.model_iden,
+						repair_attempts,
+						cause,
+						text,
+					});
+				}
+			}
+		}
+	}
+
+	/// Runs the agent loop: calls the model, executes its tool calls with the `registry` handlers,
+	/// appends the tool responses, and calls the model again, until it answers without calling tools.
+	///
+	/// - The registry tools are added to the request tools (unless already there, by name).
+	/// - The tool calls of one model response are executed concurrently.
+	/// - The assistant messages are appended with their full content (thought signatures included)
+	///   and reasoning content, so the providers requiring them (e.g., Gemini) can continue.
+	/// - Stops with pending tool calls when `ChatOptions::max_tool_iterations` (default: 10) model calls
+	///   or `ChatOptions::max_tool_run_tokens` total tokens are reached (see `ToolRunResponse.stop`).
+	/// - The registry `ToolPolicy` decides each tool call first (approve, deny, rewrite, or pause).
+	///   When calls are paused, it stops with them pending, and running the returned `chat_req` again resumes them.
+	///
+	/// The other `options` (and the client ones) apply to each call as with [`Client::exec_chat`].
+	pub async fn run_tools(
+		&self,
+		model: impl Into<ModelSpec>,
+		chat_req: ChatRequest,
+		registry: &ToolRegistry,
+		options: Option<&ChatOptions>,
+	) -> Result<ToolRunResponse> {
+		let options_set = ChatOptionsSet::default()
+			.with_chat_options(options)
+			.with_client_options(self.config().chat_options());
+		let mut chat_req = with_registry_tools(chat_req, registry);
+
+		// Resolve once, so all the calls go to the same target.
+		let target = self.resolve_service_target(model).await?;
+		let mut usage = Usage::default();
+		let mut iterations = 0;
+		let mut call_counts = responded_tool_call_counts(&chat_req);
+
+		// -- Resume the pending tool calls, if any
+		let pending_calls = pending_tool_calls(&chat_req);
+		if !pending_calls.is_empty() {
+			let (next_chat_req, paused_tool_calls) =
+				run_tool_calls(registry, chat_req, pending_calls, &mut call_counts).await;
+			chat_req = next_chat_req;
+			if !paused_tool_calls.is_empty() {
+				return Ok(ToolRunResponse {
+					chat_req,
+					chat_response: None,
+					usage,
+					iterations,
+					stop: ToolRunStop::Paused,
+					paused_tool_calls,
+				});
+			}
+		}
+
+		loop {
+			let chat_res = self.exec_chat(target.clone(), chat_req.clone(), options).await?;
+			usage.accumulate(&chat_res.usage);
+			iterations += 1;
+
+			let tool_calls: Vec<ToolCall> = chat_res.tool_calls().into_iter().cloned().collect();
+			let stop = tool_run_stop(!tool_calls.is_empty(), iterations, &options_set, &usage);
+
+			// NOTE: Pending tool calls are not appended, so the transcript stays valid to continue.
+			if !matches!(stop, Some(ToolRunStop::MaxIterations | ToolRunStop::MaxTokens)) {
+				let assistant_msg = ChatMessage::assistant(chat_res.content.clone())
+					.with_reasoning_content(chat_res.reasoning_content.clone());
+				chat_req = chat_req.append_message(assistant_msg);
+			}
+
+			if let Some(stop) = stop {
+				return Ok(ToolRunResponse {
+					chat_req,
+					chat_response: Some(chat_res),
+					usage,
+					iterations,
+					stop,
+					paused_tool_calls: Vec::new(),
+				});
+			}
+
+			let (next_chat_req, paused_tool_calls) =
+				run_tool_calls(registry, chat_req, tool_calls, &mut call_counts).await;
+			chat_req = next_chat_req;
+
+			// NOTE: The paused tool calls stay pending at the end of the transcript, to resume them.
+			if !paused_tool_calls.is_empty() {
+				return Ok(ToolRunResponse {
+					chat_req,
+					chat_response: Some(chat_res),
+					usage,
+					iterations,
+					stop: ToolRunStop::Paused,
+					paused_tool_calls,
 				});
-				let err = Error::ChatResponseGeneration {
-					model_iden: model,
-					request_payload: Box::new(payload),
-					response_body: Box::new(response_body),
-					cause: err.to_string(),
-				};
-				Err(err)
 			}
 		}
 	}
@@ -138,47 +325,87 @@ impl Client {
 	) -> Result<ChatStreamResponse> {
 		let options_set = ChatOptionsSet::default()
 			.with_chat_options(options)
-			.with_client_options(self.config().chat_options());
+			.with_client_options(self.config().chat_options())
+			.with_client_retry_policy(self.config().retry_policy());
 
 		let target = self.config().resolve_model_spec(model.into()).await?;
 		let model = target.model.clone();
-		let auth_data = target.auth.clone();
-
-		let WebRequestData {
-			mut url,
-			mut headers,
-			payload,
-		} = AdapterDispatcher::to_web_request_data(target, ServiceType::ChatStream, chat_req, options_set.clone())?;
-
-		if let Some(extra_headers) = options.and_then(|o| o.extra_headers.as_ref()) {
-			headers.merge_with(extra_headers);
-		}
 
-		// TODO: Need to check this.
-		//       This was part of the 429c5cee2241dbef9f33699b9c91202233c22816 commit
-		//       But now it is missing in the the exec_chat(..) above, which is probably an issue.
-		if let AuthData::RequestOverride {
-			url: override_url,
-			headers: override_headers,
-		} = auth_data
-		{
-			url = override_url;
-			headers = override_headers;
-		};
+		let WebRequestData { url, headers, payload } =
+			self.build_chat_web_request(&target, ServiceType::ChatStream, chat_req, &options_set)?;
 
-		let reqwest_builder = self
+		let stream_request = self
 			.web_client()
-			.new_req_builder(&url, &headers, &payload)
+			.new_stream_request(&url, &headers, &payload)
 			.map_err(|webc_error| Error::WebModelCall {
 				model_iden: model.clone(),
 				webc_error,
 			})?;
 
-		let res = AdapterDispatcher::to_chat_stream(model, reqwest_builder, options_set)?;
+		let estimated_tokens = estimate_request_tokens(&payload, options_set.max_tokens());
+		let rate_limit_permit = self.acquire_rate_limit(&model, estimated_tokens).await;
+		let mut res = AdapterDispatcher::to_chat_stream(model, stream_request, options_set)?;
+		res.stream = res
+			.stream
+			.with_middlewares(res.model_iden.clone(), self.config().middlewares().to_vec())
+			.with_rate_limit_permit(rate_limit_permit);
+		if let (Some(spend_tracker), Some(pricing)) = (
+			self.config().spend_tracker(),
+			self.config()
+				.pricing_registry()
+				.and_then(|registry| registry.get(&res.model_iden)),
+		) {
+			res.stream = res
+				.stream
+				.with_spend_tracker(res.model_iden.clone(), pricing.clone(), spend_tracker.clone());
+		}
 
 		Ok(res)
 	}
 
+	/// Runs the agent loop of [`Client::run_tools`] over streamed model turns, as one event stream.
+	///
+	/// - The text and reasoning chunks are forwarded as they arrive.
+	/// - Each tool call is dispatched to its handler as soon as it is complete (when the next tool call
+	///   starts streaming, or at the end of the turn), while the turn keeps streaming.
+	/// - Once the turn stream ended and all its tool calls finished, a `TurnEnd` is emitted,
+	///   and the conversation resumes with a new stream if there were tool calls.
+	/// - As with [`Client::run_tools`], the tool calls of the last `max_tool_iterations` turn are not run,
+	///   and that turn is not in the transcript of the last `TurnEnd`.
+	/// - Likewise, when the `max_tool_run_tokens` budget is reached, the tool calls of that turn are not run
+	///   (with a budget set, the tool calls of each turn wait for its end, where its usage is known).
+	///
+	/// The content, tool calls, reasoning, and usage of each turn are always captured (see `AgentTurnEnd.stream_end`).
+	pub async fn run_tools_stream(
+		&self,
+		model: impl Into<ModelSpec>,
+		chat_req: ChatRequest,
+		registry: &ToolRegistry,
+		options: Option<&ChatOptions>,
+	) -> Result<AgentStream> {
+		let options = options
+			.cloned()
+			.unwrap_or_default()
+			.with_capture_content(true)
+			.with_capture_tool_calls(true)
+			.with_capture_reasoning_content(true)
+			.with_capture_usage(true);
+		let chat_req = with_registry_tools(chat_req, registry);
+		let target = self.resolve_service_target(model).await?;
+
+		let (tx, rx) = futures::channel::mpsc::unbounded();
+		let task = tokio::spawn(run_agent_stream(
+			self.clone(),
+			target,
+			chat_req,
+			registry.clone(),
+			options,
+			tx,
+		));
+
+		Ok(AgentStream::new(rx, task))
+	}
+
 	/// Creates embeddings for a single input string.
 	///
 	/// Accepts any type that implements `Into<ModelSpec>` for the model parameter.
@@ -224,20 +451,369 @@ impl Client {
 		let target = self.config().resolve_model_spec(model.into()).await?;
 		let model = target.model.clone();
 
-		let WebRequestData { headers, payload, url } =
-			AdapterDispatcher::to_embed_request_data(target, embed_req, options_set.clone())?;
+		// -- Response cache lookup
+		let cache = self.response_cache(options_set.response_cache());
+		let cache_key = cache.map(|_| embed_cache_key(&model, &embed_req, &options_set));
+		if let Some((cache, key)) = cache.zip(cache_key.as_deref())
+			&& let Some(mut embed_res) = cache_get::<EmbedResponse>(cache.as_ref(), key)
+		{
+			embed_res.cache_hit = true;
+			return Ok(embed_res);
+		}
 
-		let web_res = self
-			.web_client()
-			.do_post(&url, &headers, &payload)
-			.await
-			.map_err(|webc_error| Error::WebModelCall {
-				model_iden: model.clone(),
-				webc_error,
-			})?;
+		let web_req_data = self.build_embed_web_request(&target, embed_req, &options_set)?;
+
+		let retry_policy = options_set.retry_policy().or(self.config().retry_policy());
+
+		let estimated_tokens = estimate_request_tokens(&web_req_data.payload, None);
+		let (mut web_res, rate_limit_permit) = self
+			.do_post_rate_limited(&model, &web_req_data, retry_policy, estimated_tokens)
+			.await?;
+		self.run_response_middlewares(&model, ServiceType::Embed, &mut web_res)?;
 
 		let res = AdapterDispatcher::to_embed_response(model, web_res, options_set)?;
+		rate_limit_permit.record_usage(&res.usage);
+		if let Some((cache, key)) = cache.zip(cache_key.as_deref()) {
+			cache_put(cache.as_ref(), key, &res);
+		}
 
 		Ok(res)
 	}
 }
+
+/// Dry-run APIs (render the provider wire request without sending it).
+impl Client {
+	/// Renders the provider request that `exec_chat` would send, without any network call.
+	///
+	/// Runs the same model mapping, auth resolution, adapter request building, extra headers,
+	/// and client middlewares as `exec_chat`, and returns the exact URL, headers, and JSON payload.
+	///
+	/// The auth secrets are redacted from the headers and URL (e.g., `Authorization: Bearer REDACTED`).
+	///
+	/// Note: `exec_chat_stream` sends the same request with the streaming flags of the provider.
+	pub async fn render_chat_request(
+		&self,
+		model: impl Into<ModelSpec>,
+		chat_req: ChatRequest,
+		options: Option<&ChatOptions>,
+	) -> Result<WebRequestData> {
+		let options_set = ChatOptionsSet::default()
+			.with_chat_options(options)
+			.with_client_options(self.config().chat_options())
+			.with_client_retry_policy(self.config().retry_policy());
+
+		let target = self.config().resolve_model_spec(model.into()).await?;
+
+		let mut web_req_data = self.build_chat_web_request(&target, ServiceType::Chat, chat_req, &options_set)?;
+		redact_auth(&mut web_req_data, &target.auth);
+
+		Ok(web_req_data)
+	}
+
+	/// Renders the provider request that `exec_embed` would send, without any network call.
+	///
+	/// Same as [`Client::render_chat_request`], for embeddings.
+	pub async fn render_embed_request(
+		&self,
+		model: impl Into<ModelSpec>,
+		embed_req: EmbedRequest,
+		options: Option<&EmbedOptions>,
+	) -> Result<WebRequestData> {
+		let options_set = EmbedOptionsSet::new()
+			.with_request_options(options)
+			.with_client_options(self.config().embed_options());
+
+		let target = self.config().resolve_model_spec(model.into()).await?;
+
+		let mut web_req_data = self.build_embed_web_request(&target, embed_req, &options_set)?;
+		redact_auth(&mut web_req_data, &target.auth);
+
+		Ok(web_req_data)
+	}
+}
+
+/// Execution pipeline support (shared by `exec_chat`, `exec_chat_stream`, and `exec_embed`).
+impl Client {
+	/// Executes one chat web call (rate limit, request, middlewares, and adapter response).
+	async fn exec_chat_call(
+		&self,
+		target: &ServiceTarget,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Result<ChatResponse> {
+		let model = target.model.clone();
+		let web_req_data = self.build_chat_web_request(target, ServiceType::Chat, chat_req, options_set)?;
+
+		let estimated_tokens = estimate_request_tokens(&web_req_data.payload, options_set.max_tokens());
+		let (mut web_res, rate_limit_permit) = self
+			.do_post_rate_limited(&model, &web_req_data, options_set.retry_policy(), estimated_tokens)
+			.await?;
+		let WebRequestData { payload, .. } = web_req_data;
+		self.run_response_middlewares(&model, ServiceType::Chat, &mut web_res)?;
+
+		// Note: here we capture/clone the raw body if set in the options_set
+		let captured_raw_body = options_set.capture_raw_body().unwrap_or_default().then(|| web_res.body.clone());
+
+		match AdapterDispatcher::to_chat_response(model.clone(), web_res, options_set.clone()) {
+			Ok(mut chat_res) => {
+				rate_limit_permit.record_usage(&chat_res.usage);
+				chat_res.captured_raw_body = captured_raw_body;
+				if chat_res.candidates.is_empty() {
+					chat_res.candidates.push(chat_res.to_candidate());
+				}
+				Ok(chat_res)
+			}
+			Err(err) => {
+				let response_body = captured_raw_body.unwrap_or_else(|| {
+					"Raw response not captured. Use the ChatOptions.capturre_raw_body flag to see raw response in this error".into()
+				});
+				let err = Error::ChatResponseGeneration {
+					model_iden: model,
+					request_payload: Box::new(payload),
+					response_body: Box::new(response_body),
+					cause: err.to_string(),
+				};
+				Err(err)
+			}
+		}
+	}
+
+	/// Executes `candidate_count` concurrent chat calls and merges them into one response
+	/// (the first call is the primary response, candidates are in call order with their own logprobs,
+	/// and the usage is summed).
+	async fn exec_chat_fan_out(
+		&self,
+		target: &ServiceTarget,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+		candidate_count: u32,
+	) -> Result<ChatResponse> {
+		let calls = (0..candidate_count).map(|_| self.exec_chat_call(target, chat_req.clone(), options_set));
+		let mut chat_responses = futures::future::try_join_all(calls).await?.into_iter();
+
+		let mut chat_res = chat_responses
+			.next()
+			.ok_or(Error::Internal("no fan-out response".to_string()))?;
+		for other in chat_responses {
+			chat_res.usage.accumulate(&other.usage);
+			chat_res.candidates.extend(other.candidates);
+		}
+
+		Ok(chat_res)
+	}
+
+	/// Builds the final chat request (adapter request + extra bodies + `prepare_web_request`).
+	fn build_chat_web_request(
+		&self,
+		target: &ServiceTarget,
+		service_type: ServiceType,
+		chat_req: ChatRequest,
+		options_set: &ChatOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let mut web_req_data =
+			AdapterDispatcher::to_web_request_data(target.clone(), service_type, chat_req, options_set.clone())?;
+		let adapter_extra_body = options_set.adapter_extra_body(target.model.adapter_kind);
+		for extra_body in [options_set.extra_body(), adapter_extra_body].into_iter().flatten() {
+			merge_extra_body(&target.model, &mut web_req_data.payload, extra_body)?;
+		}
+		self.prepare_web_request(target, service_type, web_req_data, options_set.extra_headers())
+	}
+
+	/// Builds the final embed request (adapter request + extra bodies + `prepare_web_request`).
+	fn build_embed_web_request(
+		&self,
+		target: &ServiceTarget,
+		embed_req: EmbedRequest,
+		options_set: &EmbedOptionsSet<'_, '_>,
+	) -> Result<WebRequestData> {
+		let mut web_req_data =
+			AdapterDispatcher::to_embed_request_data(target.clone(), embed_req, options_set.clone())?;
+		let adapter_extra_body = options_set.adapter_extra_body(target.model.adapter_kind);
+		for extra_body in [options_set.extra_body(), adapter_extra_body].into_iter().flatten() {
+			merge_extra_body(&target.model, &mut web_req_data.payload, extra_body)?;
+		}
+		self.prepare_web_request(target, ServiceType::Embed, web_req_data, options_set.headers())
+	}
+
+	/// Finalizes the adapter request before sending:
+	/// - `AuthData::RequestOverride` replaces the url and headers,
+	/// - `extra_headers` (from the options) are merged on top,
+	/// - the client middlewares `on_request` hooks run, in order.
+	fn prepare_web_request(
+		&self,
+		target: &ServiceTarget,
+		service_type: ServiceType,
+		mut web_req_data: WebRequestData,
+		extra_headers: Option<&Headers>,
+	) -> Result<WebRequestData> {
+		if let AuthData::RequestOverride { url, headers } = &target.auth {
+			web_req_data.url = url.clone();
+			web_req_data.headers = headers.clone();
+		}
+
+		if let Some(extra_headers) = extra_headers {
+			web_req_data.headers.merge_with(extra_headers);
+		}
+
+		for middleware in self.config().middlewares() {
+			middleware
+				.on_request(target, service_type, &mut web_req_data)
+				.map_err(|error| middleware_error(&target.model, error))?;
+		}
+
+		Ok(web_req_data)
+	}
+
+	/// Returns the client response cache, if any and `enabled` for this call.
+	fn response_cache(&self, enabled: bool) -> Option<&Arc<dyn ResponseCache>> {
+		self.config().response_cache().filter(|_| enabled)
+	}
+
+	/// Returns the pricing of the response model (requested/mapped model first, then provider model), if known.
+	fn model_pricing(&self, chat_res: &ChatResponse) -> Option<Pricing> {
+		let registry = self.config().pricing_registry()?;
+		registry
+			.get(&chat_res.model_iden)
+			.or_else(|| registry.get(&chat_res.provider_model_iden))
+			.cloned()
+	}
+
+	/// Waits for the client rate limiter capacity for the model (if a rate limiter is set).
+	async fn acquire_rate_limit(&self, model_iden: &ModelIden, estimated_tokens: u64) -> RateLimitPermit {
+		match self.config().rate_limiter() {
+			Some(rate_limiter) => rate_limiter.acquire(model_iden, estimated_tokens).await,
+			None => RateLimitPermit::default(),
+		}
+	}
+
+	/// Posts the request with the retry policy (if any), admitting each attempt through the client rate limiter.
+	///
+	/// Returns the web response with the permit of its attempt (to settle from the response usage).
+	async fn do_post_rate_limited(
+		&self,
+		model_iden: &ModelIden,
+		WebRequestData { url, headers, payload }: &WebRequestData,
+		retry_policy: Option<&RetryPolicy>,
+		estimated_tokens: u64,
+	) -> Result<(WebResponse, RateLimitPermit)> {
+		let mut attempt: u32 = 1;
+		loop {
+			let rate_limit_permit = self.acquire_rate_limit(model_iden, estimated_tokens).await;
+			let webc_error = match self.web_client().do_post(url, headers, payload).await {
+				Ok(web_res) => return Ok((web_res, rate_limit_permit)),
+				Err(webc_error) => webc_error,
+			};
+			rate_limit_permit.release();
+
+			let Some(delay) = retry_policy.and_then(|retry_policy| retry_policy.retry_delay(attempt, &webc_error))
+			else {
+				return Err(Error::WebModelCall {
+					model_iden: model_iden.clone(),
+					webc_error,
+				});
+			};
+
+			tracing::debug!("genai - retrying POST {url} in {delay:?} (attempt {attempt} failed). Cause: {webc_error}");
+			tokio::time::sleep(delay).await;
+			attempt += 1;
+		}
+	}
+
+	/// Runs the client middlewares `on_response` hooks, in order.
+	fn run_response_middlewares(
+		&self,
+		model_iden: &ModelIden,
+		service_type: ServiceType,
+		web_res: &mut WebResponse,
+	) -> Result<()> {
+		for middleware in self.config().middlewares() {
+			middleware
+				.on_response(model_iden, service_type, web_res)
+				.map_err(|error| middleware_error(model_iden, error))?;
+		}
+		Ok(())
+	}
+}
+
+// region:    --- Support
+
+/// Redacts the auth secrets from the request headers and url.
+///
+/// - Any occurrence of the resolved api key is replaced by `REDACTED`.
+/// - The well-known auth headers are redacted regardless (keeping the `Bearer` scheme).
+fn redact_auth(web_req_data: &mut WebRequestData, auth: &AuthData) {
+	const REDACTED: &str = "REDACTED";
+	const AUTH_HEADER_NAMES: &[&str] =
+		&["authorization", "proxy-authorization", "x-api-key", "x-goog-api-key", "api-key"];
+
+	let secret = auth.single_key_value().ok().filter(|secret| !secret.is_empty());
+
+	for (name, value) in web_req_data.headers.iter_mut() {
+		if let Some(secret) = secret.as_deref()
+			&& value.contains(secret)
+		{
+			*value = value.replace(secret, REDACTED);
+		} else if AUTH_HEADER_NAMES.contains(&name.to_lowercase().as_str()) {
+			*value = match value.split_once(' ') {
+				Some((scheme, _)) if scheme.eq_ignore_ascii_case("bearer") => format!("{scheme} {REDACTED}"),
+				_ => REDACTED.to_string(),
+			};
+		}
+	}
+
+	if let Some(secret) = secret.as_deref() {
+		web_req_data.url = web_req_data.url.replace(secret, REDACTED);
+	}
+}
+
+/// Deep-merges the extra body into the request payload (only a JSON object can patch the payload root).
+fn merge_extra_body(model_iden: &ModelIden, payload: &mut Value, extra_body: &Value) -> Result<()> {
+	if !extra_body.is_object() {
+		return Err(Error::ExtraBodyNotObject {
+			model_iden: model_iden.clone(),
+			actual: extra_body.to_string(),
+		});
+	}
+	merge_json(payload, extra_body);
+	Ok(())
+}
+
+fn cache_get<T: DeserializeOwned>(cache: &dyn ResponseCache, key: &str) -> Option<T> {
+	let value = cache.get(key)?;
+	match serde_json::from_value(value) {
+		Ok(res) => Some(res),
+		Err(err) => {
+			tracing::warn!("genai - ignoring invalid response cache entry '{key}'. Cause: {err}");
+			None
+		}
+	}
+}
+
+fn cache_put<T: Serialize>(cache: &dyn ResponseCache, key: &str, res: &T) {
+	match serde_json::to_value(res) {
+		Ok(value) => cache.put(key, value),
+		Err(err) => tracing::warn!("genai - cannot serialize response for the response cache. Cause: {err}"),
+	}
+}
+
+fn middleware_error(model_iden: &ModelIden, error: BoxError) -> Error {
+	Error::Middleware {
+		model_iden: model_iden.clone(),
+		cause: error.to_string(),
+		error,
+	}
+}
+
+/// Returns true if the error is one that another model/provider of a fallback chain might not have.
+fn is_fallback_error(err: &Error) -> bool {
+	match err {
+		Error::WebModelCall {
+			webc_error: webc::Error::Reqwest(reqwest_error),
+			..
+		} => reqwest_error.is_timeout() || reqwest_error.is_connect(),
+		Error::AdapterNotSupported { .. } => true,
+		_ => err.kind().is_transient(),
+	}
+}
+
+// endregion: --- Support
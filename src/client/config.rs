@@ -1,9 +1,10 @@
 use crate::adapter::{AdapterDispatcher, AdapterKind};
-use crate::chat::ChatOptions;
+use crate::chat::{ChatOptions, PricingRegistry, SpendTracker};
 use crate::client::{ModelSpec, ServiceTarget};
 use crate::embed::EmbedOptions;
 use crate::resolver::{AuthData, AuthResolver, Endpoint, ModelMapper, ServiceTargetResolver};
-use crate::{Error, ModelIden, Result, WebConfig};
+use crate::{ClientMiddleware, Error, ModelIden, RateLimiter, ResponseCache, Result, RetryPolicy, WebConfig};
+use std::sync::Arc;
 
 /// Configuration for building and customizing a `Client`.
 #[derive(Debug, Default, Clone)]
@@ -14,6 +15,12 @@ pub struct ClientConfig {
 	pub(super) web_config: Option<WebConfig>,
 	pub(super) chat_options: Option<ChatOptions>,
 	pub(super) embed_options: Option<EmbedOptions>,
+	pub(super) retry_policy: Option<RetryPolicy>,
+	pub(super) middlewares: Vec<Arc<dyn ClientMiddleware>>,
+	pub(super) response_cache: Option<Arc<dyn ResponseCache>>,
+	pub(super) rate_limiter: Option<RateLimiter>,
+	pub(super) pricing_registry: Option<PricingRegistry>,
+	pub(super) spend_tracker: Option<SpendTracker>,
 }
 
 /// Chainable setters related to the ClientConfig.
@@ -60,6 +67,46 @@ impl ClientConfig {
 		self
 	}
 
+	/// Sets the default RetryPolicy for chat and embed requests.
+	///
+	/// A `retry_policy` set on the `ChatOptions` / `EmbedOptions` (client default or per call) takes precedence.
+	pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = Some(retry_policy);
+		self
+	}
+
+	/// Appends a ClientMiddleware (middlewares run in registration order).
+	pub fn with_middleware(mut self, middleware: impl ClientMiddleware + 'static) -> Self {
+		self.middlewares.push(Arc::new(middleware));
+		self
+	}
+
+	/// Sets the ResponseCache for `exec_chat` and `exec_embed` responses.
+	///
+	/// Can be bypassed per call with `ChatOptions::with_response_cache(false)` / `EmbedOptions::with_response_cache(false)`.
+	pub fn with_response_cache(mut self, response_cache: impl ResponseCache + 'static) -> Self {
+		self.response_cache = Some(Arc::new(response_cache));
+		self
+	}
+
+	/// Sets the client-side RateLimiter (calls wait for capacity instead of failing).
+	pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+		self.rate_limiter = Some(rate_limiter);
+		self
+	}
+
+	/// Sets the PricingRegistry, used to set `ChatResponse.pricing` (see `ChatResponse::cost`).
+	pub fn with_pricing_registry(mut self, pricing_registry: PricingRegistry) -> Self {
+		self.pricing_registry = Some(pricing_registry);
+		self
+	}
+
+	/// Sets the SpendTracker, accumulating the cost of the calls of the priced models.
+	pub fn with_spend_tracker(mut self, spend_tracker: SpendTracker) -> Self {
+		self.spend_tracker = Some(spend_tracker);
+		self
+	}
+
 	/// Returns the WebConfig, if set.
 	pub fn web_config(&self) -> Option<&WebConfig> {
 		self.web_config.as_ref()
@@ -92,6 +139,36 @@ impl ClientConfig {
 	pub fn embed_options(&self) -> Option<&EmbedOptions> {
 		self.embed_options.as_ref()
 	}
+
+	/// Returns the default RetryPolicy, if set.
+	pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+		self.retry_policy.as_ref()
+	}
+
+	/// Returns the registered ClientMiddlewares (in execution order).
+	pub fn middlewares(&self) -> &[Arc<dyn ClientMiddleware>] {
+		&self.middlewares
+	}
+
+	/// Returns the ResponseCache, if set.
+	pub fn response_cache(&self) -> Option<&Arc<dyn ResponseCache>> {
+		self.response_cache.as_ref()
+	}
+
+	/// Returns the RateLimiter, if set.
+	pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+		self.rate_limiter.as_ref()
+	}
+
+	/// Returns the PricingRegistry, if set.
+	pub fn pricing_registry(&self) -> Option<&PricingRegistry> {
+		self.pricing_registry.as_ref()
+	}
+
+	/// Returns the SpendTracker, if set.
+	pub fn spend_tracker(&self) -> Option<&SpendTracker> {
+		self.spend_tracker.as_ref()
+	}
 }
 
 /// Resolvers
@@ -0,0 +1,49 @@
+use crate::adapter::{ServiceType, WebRequestData};
+use crate::chat::ChatStreamEvent;
+use crate::webc::WebResponse;
+use crate::{BoxError, ModelIden, ServiceTarget};
+
+/// Hooks around the `Client` execution pipeline (`exec_chat`, `exec_chat_stream`, `exec_embed`).
+///
+/// Typical uses: injecting organization headers, logging payloads, redacting secrets,
+/// or patching provider payloads without forking an adapter.
+///
+/// Register with `ClientBuilder::with_middleware` (or `ClientConfig::with_middleware`).
+/// Middlewares run in registration order. All hooks default to no-op.
+///
+/// Returning an error from a hook aborts the call with `Error::Middleware`.
+pub trait ClientMiddleware: Send + Sync {
+	/// Called before sending, with the final request (after extra headers and `AuthData::RequestOverride`).
+	fn on_request(
+		&self,
+		target: &ServiceTarget,
+		service_type: ServiceType,
+		request: &mut WebRequestData,
+	) -> Result<(), BoxError> {
+		let _ = (target, service_type, request);
+		Ok(())
+	}
+
+	/// Called with the successful raw response, before the adapter parses it (`exec_chat` and `exec_embed` only).
+	fn on_response(
+		&self,
+		model_iden: &ModelIden,
+		service_type: ServiceType,
+		response: &mut WebResponse,
+	) -> Result<(), BoxError> {
+		let _ = (model_iden, service_type, response);
+		Ok(())
+	}
+
+	/// Called for each event of a chat stream (`exec_chat_stream` only).
+	fn on_stream_event(&self, model_iden: &ModelIden, event: &mut ChatStreamEvent) -> Result<(), BoxError> {
+		let _ = (model_iden, event);
+		Ok(())
+	}
+}
+
+impl std::fmt::Debug for dyn ClientMiddleware {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("ClientMiddleware")
+	}
+}
@@ -5,12 +5,17 @@
 
 // region:    --- Modules
 
+mod agent_loop;
 mod builder;
 mod client_impl;
 mod client_types;
 mod config;
 mod headers;
+mod middleware;
 mod model_spec;
+mod rate_limiter;
+mod response_cache;
+mod retry_policy;
 mod service_target;
 mod web_config;
 
@@ -18,7 +23,11 @@ pub use builder::*;
 pub use client_types::*;
 pub use config::*;
 pub use headers::*;
+pub use middleware::*;
 pub use model_spec::*;
+pub use rate_limiter::*;
+pub use response_cache::*;
+pub use retry_policy::*;
 pub use service_target::*;
 pub use web_config::*;
 
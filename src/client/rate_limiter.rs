@@ -0,0 +1,366 @@
+use crate::ModelIden;
+use crate::adapter::AdapterKind;
+use crate::chat::Usage;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The rate limit window (limits are per minute).
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Request and token budgets per minute (`None` means unlimited).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+	pub requests_per_minute: Option<u32>,
+	pub tokens_per_minute: Option<u32>,
+}
+
+/// Constructors & Setters
+impl RateLimit {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_requests_per_minute(mut self, value: u32) -> Self {
+		self.requests_per_minute = Some(value);
+		self
+	}
+
+	pub fn with_tokens_per_minute(mut self, value: u32) -> Self {
+		self.tokens_per_minute = Some(value);
+		self
+	}
+}
+
+/// Client-side rate limiter, with `RateLimit`s per `AdapterKind` and/or per `ModelIden`
+/// (set with `ClientBuilder::with_rate_limiter`).
+///
+/// `exec_chat`, `exec_chat_stream`, and `exec_embed` wait for capacity (over a sliding one-minute window)
+/// instead of sending a request the provider would reject with a 429.
+///
+/// When both an adapter and a model limit apply, both must have capacity.
+///
+/// Token consumption is only known after the call, so each request reserves its estimated tokens when admitted
+/// (the request payload size, at ~4 bytes per token, plus its `max_tokens` when set),
+/// and the reservation is settled from the response `Usage`
+/// (or `StreamEnd.captured_usage` for streams, which requires `ChatOptions::with_capture_usage(true)`).
+/// A request is admitted when the tokens used and reserved in the window, plus its estimate, fit in the budget
+/// (or when the window is empty), so concurrent in-flight requests cannot overrun it.
+///
+/// Each retry attempt of `exec_chat` and `exec_embed` is admitted as a new request (a failed attempt reserves
+/// no tokens). The retries of the `exec_chat_stream` initial connect share the stream admission.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+	adapter_limits: HashMap<AdapterKind, RateLimit>,
+	/// Keyed by the model name string (a `ModelName` hashes differently whether static or shared).
+	model_limits: HashMap<(AdapterKind, String), RateLimit>,
+	state: Arc<Mutex<RateLimiterState>>,
+}
+
+#[derive(Debug, Default)]
+struct RateLimiterState {
+	windows: HashMap<RateScope, RateWindow>,
+	next_id: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum RateScope {
+	Adapter(AdapterKind),
+	Model(AdapterKind, String),
+}
+
+/// The requests admitted within the last window, for one scope.
+#[derive(Debug, Default)]
+struct RateWindow {
+	entries: VecDeque<RateEntry>,
+}
+
+#[derive(Debug)]
+struct RateEntry {
+	id: u64,
+	at: Instant,
+	tokens: u64,
+}
+
+/// Constructors & Setters
+impl RateLimiter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the rate limit shared by all the models of an adapter.
+	pub fn with_adapter_limit(mut self, adapter_kind: AdapterKind, limit: RateLimit) -> Self {
+		self.adapter_limits.insert(adapter_kind, limit);
+		self
+	}
+
+	/// Sets the rate limit of a model.
+	pub fn with_model_limit(mut self, model_iden: ModelIden, limit: RateLimit) -> Self {
+		let ModelIden {
+			adapter_kind,
+			model_name,
+		} = model_iden;
+		self.model_limits.insert((adapter_kind, model_name.to_string()), limit);
+		self
+	}
+}
+
+/// Crate APIs
+impl RateLimiter {
+	/// Waits until all the limits applying to `model_iden` have capacity for the request,
+	/// then admits it with its `estimated_tokens` reserved.
+	pub(crate) async fn acquire(&self, model_iden: &ModelIden, estimated_tokens: u64) -> RateLimitPermit {
+		let scopes = self.scopes_for(model_iden);
+		if scopes.is_empty() {
+			return RateLimitPermit::default();
+		}
+
+		loop {
+			match self.try_acquire(&scopes, estimated_tokens) {
+				Ok(permit) => return permit,
+				Err(wait) => {
+					tracing::debug!("genai - rate limit reached for {model_iden}, waiting {wait:?}");
+					tokio::time::sleep(wait).await;
+				}
+			}
+		}
+	}
+
+	fn scopes_for(&self, model_iden: &ModelIden) -> Vec<(RateScope, RateLimit)> {
+		let adapter_scope = self
+			.adapter_limits
+			.get(&model_iden.adapter_kind)
+			.map(|limit| (RateScope::Adapter(model_iden.adapter_kind), *limit));
+		let model_key = (model_iden.adapter_kind, model_iden.model_name.to_string());
+		let model_scope = self
+			.model_limits
+			.get(&model_key)
+			.map(|limit| (RateScope::Model(model_key.0, model_key.1.clone()), *limit));
+
+		adapter_scope.into_iter().chain(model_scope).collect()
+	}
+
+	/// Admits the request if all scopes have capacity, otherwise returns the time to wait.
+	fn try_acquire(
+		&self,
+		scopes: &[(RateScope, RateLimit)],
+		estimated_tokens: u64,
+	) -> Result<RateLimitPermit, Duration> {
+		let Ok(mut state) = self.state.lock() else {
+			// NOTE: A poisoned lock should not block the calls.
+			return Ok(RateLimitPermit::default());
+		};
+		let now = Instant::now();
+
+		let mut wait = Duration::ZERO;
+		for (scope, limit) in scopes {
+			let window = state.windows.entry(scope.clone()).or_default();
+			window.prune(now);
+			wait = wait.max(window.wait_for_capacity(limit, now, estimated_tokens));
+		}
+		if !wait.is_zero() {
+			return Err(wait);
+		}
+
+		state.next_id += 1;
+		let id = state.next_id;
+		for (scope, _) in scopes {
+			let window = state.windows.entry(scope.clone()).or_default();
+			window.entries.push_back(RateEntry {
+				id,
+				at: now,
+				tokens: estimated_tokens,
+			});
+		}
+
+		Ok(RateLimitPermit {
+			state: Some(self.state.clone()),
+			scopes: scopes.iter().map(|(scope, _)| scope.clone()).collect(),
+			id,
+		})
+	}
+}
+
+impl RateWindow {
+	fn prune(&mut self, now: Instant) {
+		while self.entries.front().is_some_and(|entry| now.duration_since(entry.at) >= WINDOW) {
+			self.entries.pop_front();
+		}
+	}
+
+	/// Returns the time until this window can admit one more request of `estimated_tokens` (zero if it can now).
+	fn wait_for_capacity(&self, limit: &RateLimit, now: Instant, estimated_tokens: u64) -> Duration {
+		let mut wait = Duration::ZERO;
+
+		// -- Requests: wait for the oldest entries to leave the window
+		if let Some(rpm) = limit.requests_per_minute.map(|rpm| rpm.max(1) as usize)
+			&& self.entries.len() >= rpm
+			&& let Some(entry) = self.entries.get(self.entries.len() - rpm)
+		{
+			wait = wait.max(WINDOW.saturating_sub(now.duration_since(entry.at)));
+		}
+
+		// -- Tokens: wait for enough used (or reserved) tokens to leave the window
+		//    (a request over the whole budget waits for all of them)
+		if let Some(tpm) = limit.tokens_per_minute {
+			let mut used: u64 = self.entries.iter().map(|entry| entry.tokens).sum();
+			for entry in &self.entries {
+				if used == 0 || used.saturating_add(estimated_tokens) <= u64::from(tpm) {
+					break;
+				}
+				used -= entry.tokens;
+				wait = wait.max(WINDOW.saturating_sub(now.duration_since(entry.at)));
+			}
+		}
+
+		wait
+	}
+}
+
+/// Estimates the tokens of a request: its payload size (~4 bytes per token) plus its `max_tokens` (if set).
+pub(crate) fn estimate_request_tokens(payload: &Value, max_tokens: Option<u32>) -> u64 {
+	let payload_len = serde_json::to_vec(payload).map(|payload| payload.len()).unwrap_or_default();
+	(payload_len as u64).div_ceil(4) + u64::from(max_tokens.unwrap_or(0))
+}
+
+// region:    --- RateLimitPermit
+
+/// An admitted request, whose token usage is reconciled once known.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RateLimitPermit {
+	state: Option<Arc<Mutex<RateLimiterState>>>,
+	scopes: Vec<RateScope>,
+	id: u64,
+}
+
+impl RateLimitPermit {
+	/// Settles the reservation with the token usage of the admitted request (total tokens, or prompt + completion).
+	///
+	/// When the usage has no token counts, the estimate stays reserved.
+	pub(crate) fn record_usage(&self, usage: &Usage) {
+		let tokens = usage
+			.total_tokens
+			.or_else(|| match (usage.prompt_tokens, usage.completion_tokens) {
+				(None, None) => None,
+				(prompt, completion) => Some(prompt.unwrap_or(0) + completion.unwrap_or(0)),
+			});
+		if let Some(tokens) = tokens {
+			self.settle(u64::try_from(tokens).unwrap_or(0));
+		}
+	}
+
+	/// Settles the reservation of a failed request, which used no tokens (it still counts as a request).
+	pub(crate) fn release(&self) {
+		self.settle(0);
+	}
+
+	fn settle(&self, tokens: u64) {
+		let Some(Ok(mut state)) = self.state.as_ref().map(|state| state.lock()) else {
+			return;
+		};
+
+		// NOTE: The entry might have already left the window (long calls).
+		for scope in &self.scopes {
+			if let Some(entry) = state
+				.windows
+				.get_mut(scope)
+				.and_then(|window| window.entries.iter_mut().find(|entry| entry.id == self.id))
+			{
+				entry.tokens = tokens;
+			}
+		}
+	}
+}
+
+// endregion: --- RateLimitPermit
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn model() -> ModelIden {
+		ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini")
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_rate_limiter_requests_per_minute() {
+		let limiter =
+			RateLimiter::new().with_adapter_limit(AdapterKind::OpenAI, RateLimit::new().with_requests_per_minute(2));
+		let start = Instant::now();
+
+		limiter.acquire(&model(), 0).await;
+		limiter.acquire(&model(), 0).await;
+		assert!(start.elapsed() < Duration::from_secs(1));
+
+		limiter.acquire(&model(), 0).await;
+		assert!(start.elapsed() >= WINDOW);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_rate_limiter_tokens_per_minute() {
+		let limiter = RateLimiter::new().with_model_limit(model(), RateLimit::new().with_tokens_per_minute(100));
+		let start = Instant::now();
+		let usage = Usage {
+			total_tokens: Some(60),
+			..Default::default()
+		};
+
+		limiter.acquire(&model(), 0).await.record_usage(&usage);
+		limiter.acquire(&model(), 0).await.record_usage(&usage);
+		assert!(start.elapsed() < Duration::from_secs(1));
+
+		// 120 tokens used in the window, so it waits for the first request to leave the window.
+		limiter.acquire(&model(), 0).await;
+		assert!(start.elapsed() >= WINDOW);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_rate_limiter_tokens_reserved_in_flight() {
+		let limiter = RateLimiter::new().with_model_limit(model(), RateLimit::new().with_tokens_per_minute(100));
+		let start = Instant::now();
+
+		// Not settled yet (in flight), so the second request waits for the first reservation to leave the window.
+		let _in_flight = limiter.acquire(&model(), 60).await;
+		limiter.acquire(&model(), 60).await;
+		assert!(start.elapsed() >= WINDOW);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_rate_limiter_tokens_reservation_settled() {
+		let limiter = RateLimiter::new().with_model_limit(model(), RateLimit::new().with_tokens_per_minute(100));
+		let start = Instant::now();
+		let usage = Usage {
+			total_tokens: Some(10),
+			..Default::default()
+		};
+
+		limiter.acquire(&model(), 60).await.record_usage(&usage);
+		limiter.acquire(&model(), 60).await.release();
+		limiter.acquire(&model(), 60).await;
+		assert!(start.elapsed() < Duration::from_secs(1));
+
+		// A request over the whole budget waits for the window to be empty.
+		limiter.acquire(&model(), 200).await;
+		assert!(start.elapsed() >= WINDOW);
+	}
+
+	#[tokio::test(start_paused = true)]
+	async fn test_rate_limiter_other_adapter_not_limited() {
+		let limiter =
+			RateLimiter::new().with_adapter_limit(AdapterKind::Anthropic, RateLimit::new().with_requests_per_minute(1));
+		let start = Instant::now();
+
+		for _ in 0..5 {
+			limiter.acquire(&model(), 0).await;
+		}
+		assert!(start.elapsed() < Duration::from_secs(1));
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,366 @@
+use crate::ModelIden;
+use crate::chat::{ChatOptionsSet, ChatRequest};
+use crate::embed::{EmbedOptionsSet, EmbedRequest};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Cache of `exec_chat` / `exec_embed` responses (set with `ClientBuilder::with_response_cache`).
+///
+/// Keys are stable hashes (hex) of the resolved `ModelIden`, the request, and the cache-relevant options
+/// (e.g., temperature, seed, response format). Values are the serialized `ChatResponse` / `EmbedResponse`.
+///
+/// On a cache hit, no provider call is made (and the client middlewares do not run),
+/// and the response has `cache_hit: true`.
+///
+/// Implementations should not fail the call; a failing `get` should return `None`.
+pub trait ResponseCache: Send + Sync {
+	/// Returns the cached response for the key, if any (and not expired).
+	fn get(&self, key: &str) -> Option<Value>;
+
+	/// Stores the response for the key.
+	fn put(&self, key: &str, value: Value);
+}
+
+impl std::fmt::Debug for dyn ResponseCache {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("ResponseCache")
+	}
+}
+
+// region:    --- InMemoryResponseCache
+
+/// In-memory `ResponseCache`, with least-recently-used eviction and optional time-to-live.
+#[derive(Debug)]
+pub struct InMemoryResponseCache {
+	capacity: usize,
+	ttl: Option<Duration>,
+	state: Mutex<InMemoryCacheState>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryCacheState {
+	entries: HashMap<String, InMemoryCacheEntry>,
+	/// Monotonic counter, to track the least recently used entry.
+	tick: u64,
+}
+
+#[derive(Debug)]
+struct InMemoryCacheEntry {
+	value: Value,
+	inserted_at: Instant,
+	last_used: u64,
+}
+
+impl InMemoryResponseCache {
+	/// Creates a cache holding at most `capacity` responses.
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			ttl: None,
+			state: Mutex::default(),
+		}
+	}
+
+	/// Sets the time-to-live of the cached responses.
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = Some(ttl);
+		self
+	}
+
+	fn is_expired(&self, entry: &InMemoryCacheEntry) -> bool {
+		self.ttl.is_some_and(|ttl| entry.inserted_at.elapsed() > ttl)
+	}
+}
+
+impl ResponseCache for InMemoryResponseCache {
+	fn get(&self, key: &str) -> Option<Value> {
+		let mut state = self.state.lock().ok()?;
+		state.tick += 1;
+		let tick = state.tick;
+
+		let entry = state.entries.get_mut(key)?;
+		if self.is_expired(entry) {
+			state.entries.remove(key);
+			return None;
+		}
+		entry.last_used = tick;
+
+		Some(entry.value.clone())
+	}
+
+	fn put(&self, key: &str, value: Value) {
+		if self.capacity == 0 {
+			return;
+		}
+		let Ok(mut state) = self.state.lock() else {
+			return;
+		};
+		state.tick += 1;
+		let tick = state.tick;
+
+		if !state.entries.contains_key(key) && state.entries.len() >= self.capacity {
+			let lru_key = state
+				.entries
+				.iter()
+				.min_by_key(|(_, entry)| entry.last_used)
+				.map(|(key, _)| key.clone());
+			if let Some(lru_key) = lru_key {
+				state.entries.remove(&lru_key);
+			}
+		}
+
+		state.entries.insert(
+			key.to_string(),
+			InMemoryCacheEntry {
+				value,
+				inserted_at: Instant::now(),
+				last_used: tick,
+			},
+		);
+	}
+}
+
+// endregion: --- InMemoryResponseCache
+
+// region:    --- DiskResponseCache
+
+/// On-disk `ResponseCache`, one JSON file per key in the given directory, with optional time-to-live.
+#[derive(Debug)]
+pub struct DiskResponseCache {
+	dir: PathBuf,
+	ttl: Option<Duration>,
+}
+
+impl DiskResponseCache {
+	/// Creates a cache storing its files in `dir` (created on first write).
+	pub fn new(dir: impl Into<PathBuf>) -> Self {
+		Self {
+			dir: dir.into(),
+			ttl: None,
+		}
+	}
+
+	/// Sets the time-to-live of the cached responses (based on the file modification time).
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = Some(ttl);
+		self
+	}
+
+	fn file_path(&self, key: &str) -> PathBuf {
+		self.dir.join(format!("{key}.json"))
+	}
+
+	fn is_expired(&self, path: &std::path::Path) -> bool {
+		let Some(ttl) = self.ttl else {
+			return false;
+		};
+		std::fs::metadata(path)
+			.and_then(|meta| meta.modified())
+			.map(|modified| SystemTime::now().duration_since(modified).unwrap_or_default() > ttl)
+			.unwrap_or(true)
+	}
+}
+
+impl ResponseCache for DiskResponseCache {
+	fn get(&self, key: &str) -> Option<Value> {
+		let path = self.file_path(key);
+		if !path.exists() || self.is_expired(&path) {
+			return None;
+		}
+		let content = std::fs::read_to_string(&path).ok()?;
+		serde_json::from_str(&content).ok()
+	}
+
+	fn put(&self, key: &str, value: Value) {
+		let path = self.file_path(key);
+		let res = std::fs::create_dir_all(&self.dir).and_then(|_| std::fs::write(&path, value.to_string()));
+		if let Err(err) = res {
+			tracing::warn!(
+				"genai - cannot write response cache file '{}'. Cause: {err}",
+				path.display()
+			);
+		}
+	}
+}
+
+// endregion: --- DiskResponseCache
+
+// region:    --- Cache Keys
+
+/// Version of the key material (bump when the key material changes).
+const CACHE_KEY_VERSION: u32 = 2;
+
+/// The model key material: the adapter kind and the model name string
+/// (not the serialized `ModelIden`, whose `ModelName` representation depends on how it was built).
+fn model_key(model_iden: &ModelIden) -> Value {
+	json!({
+		"adapter_kind": model_iden.adapter_kind,
+		"model_name": &*model_iden.model_name,
+	})
+}
+
+pub(crate) fn chat_cache_key(model_iden: &ModelIden, chat_req: &ChatRequest, options_set: &ChatOptionsSet) -> String {
+	let material = json!({
+		"version": CACHE_KEY_VERSION,
+		"service": "chat",
+		"model": model_key(model_iden),
+		"request": chat_req,
+		"options": {
+			"temperature": options_set.temperature(),
+			"max_tokens": options_set.max_tokens(),
+			"top_p": options_set.top_p(),
+			"stop_sequences": options_set.stop_sequences(),
+			"top_k": options_set.top_k(),
+			"min_p": options_set.min_p(),
+			"frequency_penalty": options_set.frequency_penalty(),
+			"presence_penalty": options_set.presence_penalty(),
+			"repetition_penalty": options_set.repetition_penalty(),
+			"response_format": options_set.response_format(),
+			"tool_choice": options_set.tool_choice(),
+			"parallel_tool_calls": options_set.parallel_tool_calls(),
+			"strict_tools": options_set.strict_tools(),
+			"strict_response_format": options_set.strict_response_format(),
+			"logprobs": options_set.logprobs(),
+			"top_logprobs": options_set.top_logprobs(),
+			"candidate_count": options_set.candidate_count(),
+			"normalize_reasoning_content": options_set.normalize_reasoning_content(),
+			"reasoning_effort": options_set.reasoning_effort(),
+			"verbosity": options_set.verbosity(),
+			"seed": options_set.seed(),
+			"service_tier": options_set.service_tier(),
+			"extra_body": options_set.extra_body(),
+			"adapter_extra_body": options_set.adapter_extra_body(model_iden.adapter_kind),
+		},
+	});
+	stable_hash(&material)
+}
+
+pub(crate) fn embed_cache_key(
+	model_iden: &ModelIden,
+	embed_req: &EmbedRequest,
+	options_set: &EmbedOptionsSet,
+) -> String {
+	let material = json!({
+		"version": CACHE_KEY_VERSION,
+		"service": "embed",
+		"model": model_key(model_iden),
+		"request": embed_req,
+		"options": {
+			"dimensions": options_set.dimensions(),
+			"encoding_format": options_set.encoding_format(),
+			"user": options_set.user(),
+			"embedding_type": options_set.embedding_type(),
+			"truncate": options_set.truncate(),
+			"extra_body": options_set.extra_body(),
+			"adapter_extra_body": options_set.adapter_extra_body(model_iden.adapter_kind),
+		},
+	});
+	stable_hash(&material)
+}
+
+/// FNV-1a (128 bits) of the canonical JSON (sorted object keys), stable across runs and platforms.
+fn stable_hash(value: &Value) -> String {
+	const FNV_OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+	const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
+	let mut canonical = String::new();
+	write_canonical_json(value, &mut canonical);
+
+	let hash = canonical.bytes().fold(FNV_OFFSET, |hash, byte| {
+		(hash ^ u128::from(byte)).wrapping_mul(FNV_PRIME)
+	});
+	format!("{hash:032x}")
+}
+
+fn write_canonical_json(value: &Value, out: &mut String) {
+	match value {
+		Value::Object(map) => {
+			let mut entries: Vec<_> = map.iter().collect();
+			entries.sort_by_key(|(key, _)| *key);
+			out.push('{');
+			for (idx, (key, value)) in entries.into_iter().enumerate() {
+				if idx > 0 {
+					out.push(',');
+				}
+				out.push_str(&Value::String(key.clone()).to_string());
+				out.push(':');
+				write_canonical_json(value, out);
+			}
+			out.push('}');
+		}
+		Value::Array(items) => {
+			out.push('[');
+			for (idx, item) in items.iter().enumerate() {
+				if idx > 0 {
+					out.push(',');
+				}
+				write_canonical_json(item, out);
+			}
+			out.push(']');
+		}
+		other => out.push_str(&other.to_string()),
+	}
+}
+
+// endregion: --- Cache Keys
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ModelName;
+	use crate::adapter::AdapterKind;
+
+	#[test]
+	fn test_response_cache_stable_hash_key_order() {
+		let a = json!({"b": 1, "a": {"y": [1, 2], "x": null}});
+		let b = json!({"a": {"x": null, "y": [1, 2]}, "b": 1});
+		let c = json!({"a": {"x": null, "y": [2, 1]}, "b": 1});
+
+		assert_eq!(stable_hash(&a), stable_hash(&b));
+		assert_ne!(stable_hash(&a), stable_hash(&c));
+		assert_eq!(stable_hash(&a).len(), 32);
+	}
+
+	#[test]
+	fn test_response_cache_key_model_name_repr() {
+		let chat_req = ChatRequest::from_user("Hello");
+		let options_set = ChatOptionsSet::default();
+		let static_iden = ModelIden::new(AdapterKind::OpenAI, ModelName::from_static("gpt-4o-mini"));
+		let shared_iden = ModelIden::new(AdapterKind::OpenAI, "gpt-4o-mini");
+
+		assert_eq!(
+			chat_cache_key(&static_iden, &chat_req, &options_set),
+			chat_cache_key(&shared_iden, &chat_req, &options_set)
+		);
+	}
+
+	#[test]
+	fn test_response_cache_in_memory_lru_eviction() {
+		let cache = InMemoryResponseCache::new(2);
+		cache.put("a", json!(1));
+		cache.put("b", json!(2));
+		// Use "a", so "b" becomes the least recently used.
+		assert_eq!(cache.get("a"), Some(json!(1)));
+		cache.put("c", json!(3));
+
+		assert_eq!(cache.get("a"), Some(json!(1)));
+		assert_eq!(cache.get("b"), None);
+		assert_eq!(cache.get("c"), Some(json!(3)));
+	}
+
+	#[test]
+	fn test_response_cache_in_memory_ttl() {
+		let cache = InMemoryResponseCache::new(2).with_ttl(Duration::ZERO);
+		cache.put("a", json!(1));
+		std::thread::sleep(Duration::from_millis(2));
+
+		assert_eq!(cache.get("a"), None);
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,242 @@
+use crate::webc;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Retry configuration for provider calls.
+///
+/// Applies to `Client::exec_chat`, `Client::exec_embed`, and the initial connect of
+/// `Client::exec_chat_stream` (before any content has been received).
+///
+/// The delay before attempt `n + 1` is `base_delay * 2^(n - 1)`, capped at `max_delay`,
+/// unless the provider sent a `retry-after-ms` or `retry-after` header, which takes precedence
+/// (still capped at `max_delay`).
+///
+/// Can be set on the client (`ClientConfig::with_retry_policy`) or per call (`ChatOptions::with_retry_policy`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+	/// Total number of attempts, including the first one (e.g., `3` means up to 2 retries).
+	pub max_attempts: u32,
+
+	/// Delay before the first retry; doubled on each subsequent retry.
+	pub base_delay: Duration,
+
+	/// Upper bound for any single delay (backoff or `Retry-After`).
+	pub max_delay: Duration,
+
+	/// When true, the backoff delay is randomized in `[delay / 2, delay]`.
+	pub jitter: bool,
+
+	/// HTTP status codes considered retryable.
+	pub retryable_statuses: Vec<u16>,
+
+	/// When true, connect errors and timeouts are retried as well.
+	pub retry_on_connect_error: bool,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self {
+			max_attempts: 3,
+			base_delay: Duration::from_millis(500),
+			max_delay: Duration::from_secs(30),
+			jitter: true,
+			// 529 is Anthropic "overloaded"
+			retryable_statuses: vec![408, 409, 429, 500, 502, 503, 504, 529],
+			retry_on_connect_error: true,
+		}
+	}
+}
+
+/// Constructors
+impl RetryPolicy {
+	/// Creates a policy with default values and the given total number of attempts.
+	pub fn new(max_attempts: u32) -> Self {
+		Self::default().with_max_attempts(max_attempts)
+	}
+}
+
+/// Chainable Setters
+impl RetryPolicy {
+	/// Sets the total number of attempts (including the first one).
+	pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+		self.max_attempts = max_attempts;
+		self
+	}
+
+	/// Sets the delay before the first retry.
+	pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+		self.base_delay = base_delay;
+		self
+	}
+
+	/// Sets the maximum delay between two attempts.
+	pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+		self.max_delay = max_delay;
+		self
+	}
+
+	/// Enables or disables jitter on the backoff delay.
+	pub fn with_jitter(mut self, jitter: bool) -> Self {
+		self.jitter = jitter;
+		self
+	}
+
+	/// Sets the retryable HTTP status codes.
+	pub fn with_retryable_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+		self.retryable_statuses = statuses.into_iter().collect();
+		self
+	}
+
+	/// Enables or disables retrying on connect errors and timeouts.
+	pub fn with_retry_on_connect_error(mut self, value: bool) -> Self {
+		self.retry_on_connect_error = value;
+		self
+	}
+}
+
+/// Retry decisions
+impl RetryPolicy {
+	/// Returns true if `status` is in the retryable status set.
+	pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+		self.retryable_statuses.contains(&status.as_u16())
+	}
+
+	/// Returns true if a transport-level error (no HTTP response) should be retried.
+	pub fn is_retryable_reqwest_error(&self, error: &reqwest::Error) -> bool {
+		self.retry_on_connect_error && (error.is_connect() || error.is_timeout())
+	}
+
+	/// Returns the delay to wait after the failed attempt number `attempt` (1-based).
+	///
+	/// If `headers` carries `retry-after-ms` or `retry-after`, that value is used (capped by `max_delay`);
+	/// otherwise the exponential backoff applies.
+	pub fn delay_for_attempt(&self, attempt: u32, headers: Option<&HeaderMap>) -> Duration {
+		if let Some(retry_after) = headers.and_then(retry_after_from_headers) {
+			return retry_after.min(self.max_delay);
+		}
+
+		let exp = attempt.saturating_sub(1).min(31);
+		let delay = self.base_delay.saturating_mul(1u32 << exp).min(self.max_delay);
+
+		if self.jitter { jittered(delay) } else { delay }
+	}
+
+	/// Returns the delay to wait before retrying the failed attempt number `attempt` (1-based),
+	/// or None when the error is not retryable or there are no attempts left.
+	pub(crate) fn retry_delay(&self, attempt: u32, err: &webc::Error) -> Option<Duration> {
+		if attempt >= self.max_attempts {
+			return None;
+		}
+		match err {
+			webc::Error::ResponseFailedStatus { status, headers, .. } if self.is_retryable_status(*status) => {
+				Some(self.delay_for_attempt(attempt, Some(headers)))
+			}
+			webc::Error::Reqwest(reqwest_error) if self.is_retryable_reqwest_error(reqwest_error) => {
+				Some(self.delay_for_attempt(attempt, None))
+			}
+			_ => None,
+		}
+	}
+}
+
+// region:    --- Support
+
+/// Extracts the provider requested delay from `retry-after-ms` (OpenAI, Anthropic) or `retry-after` (seconds).
+///
+/// Note: The HTTP-date form of `retry-after` is not supported and is ignored.
+pub(crate) fn retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
+	let header_f64 = |name: &str| {
+		headers
+			.get(name)
+			.and_then(|v| v.to_str().ok())
+			.and_then(|v| v.trim().parse::<f64>().ok())
+			.filter(|v| v.is_finite() && *v >= 0.)
+	};
+
+	if let Some(ms) = header_f64("retry-after-ms") {
+		return Some(Duration::from_secs_f64(ms / 1000.));
+	}
+
+	header_f64("retry-after").map(Duration::from_secs_f64)
+}
+
+/// Returns a random duration in `[delay / 2, delay]`.
+fn jittered(delay: Duration) -> Duration {
+	// NOTE: RandomState is randomly seeded per instance, which is enough for jitter (no need for a rand dependency).
+	let random = RandomState::new().build_hasher().finish();
+	let ratio = (random as f64) / (u64::MAX as f64);
+	let half = delay / 2;
+	half + half.mul_f64(ratio)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reqwest::header::HeaderValue;
+
+	#[test]
+	fn test_retry_policy_delay_exponential_capped() {
+		let policy = RetryPolicy::default()
+			.with_jitter(false)
+			.with_base_delay(Duration::from_millis(100))
+			.with_max_delay(Duration::from_millis(500));
+
+		assert_eq!(policy.delay_for_attempt(1, None), Duration::from_millis(100));
+		assert_eq!(policy.delay_for_attempt(2, None), Duration::from_millis(200));
+		assert_eq!(policy.delay_for_attempt(3, None), Duration::from_millis(400));
+		assert_eq!(policy.delay_for_attempt(4, None), Duration::from_millis(500));
+		assert_eq!(policy.delay_for_attempt(100, None), Duration::from_millis(500));
+	}
+
+	#[test]
+	fn test_retry_policy_delay_jitter_in_range() {
+		let policy = RetryPolicy::default().with_base_delay(Duration::from_millis(1000));
+
+		for _ in 0..20 {
+			let delay = policy.delay_for_attempt(1, None);
+			assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_millis(1000));
+		}
+	}
+
+	#[test]
+	fn test_retry_policy_delay_from_retry_after_headers() {
+		let policy = RetryPolicy::default().with_max_delay(Duration::from_secs(10));
+
+		let mut headers = HeaderMap::new();
+		headers.insert("retry-after", HeaderValue::from_static("2"));
+		assert_eq!(policy.delay_for_attempt(1, Some(&headers)), Duration::from_secs(2));
+
+		// retry-after-ms takes precedence
+		headers.insert("retry-after-ms", HeaderValue::from_static("250"));
+		assert_eq!(policy.delay_for_attempt(1, Some(&headers)), Duration::from_millis(250));
+
+		// capped by max_delay
+		let mut headers = HeaderMap::new();
+		headers.insert("retry-after", HeaderValue::from_static("120"));
+		assert_eq!(policy.delay_for_attempt(1, Some(&headers)), Duration::from_secs(10));
+
+		// HTTP-date is ignored (falls back on backoff)
+		let mut headers = HeaderMap::new();
+		headers.insert("retry-after", HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+		assert!(retry_after_from_headers(&headers).is_none());
+	}
+
+	#[test]
+	fn test_retry_policy_retryable_status() {
+		let policy = RetryPolicy::default();
+		assert!(policy.is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+		assert!(policy.is_retryable_status(StatusCode::from_u16(529).unwrap()));
+		assert!(!policy.is_retryable_status(StatusCode::BAD_REQUEST));
+		assert!(!policy.is_retryable_status(StatusCode::UNAUTHORIZED));
+	}
+}
+
+// endregion: --- Tests
@@ -19,7 +19,25 @@ impl<'de> Deserialize<'de> for ModelName {
 	where
 		D: Deserializer<'de>,
 	{
-		let s: &str = <&str>::deserialize(deserializer)?;
+		// NOTE: Also accepts the serialized form (e.g., `{"Shared": "gpt-4o-mini"}`),
+		//       so that the serialized responses (e.g., response cache) can be read back.
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum NameRepr {
+			Plain(String),
+			Serialized(SerializedName),
+		}
+
+		#[derive(Deserialize)]
+		enum SerializedName {
+			Static(String),
+			Shared(String),
+		}
+
+		let s = match NameRepr::deserialize(deserializer)? {
+			NameRepr::Plain(s) => s,
+			NameRepr::Serialized(SerializedName::Static(s) | SerializedName::Shared(s)) => s,
+		};
 		Ok(ModelName(Inner::Shared(Arc::<str>::from(s))))
 	}
 }
@@ -158,3 +176,24 @@ impl PartialEq<ModelName> for String {
 }
 
 // endregion: --- EQ
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_model_name_serde_round_trip() -> Result<(), serde_json::Error> {
+		let model_name = ModelName::from("gpt-4o-mini");
+
+		let value = serde_json::to_value(&model_name)?;
+
+		assert_eq!(value, serde_json::json!({"Shared": "gpt-4o-mini"}));
+		assert_eq!(serde_json::from_value::<ModelName>(value)?, model_name);
+		assert_eq!(serde_json::from_str::<ModelName>("\"gpt-4o-mini\"")?, model_name);
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -2,8 +2,11 @@
 //! - It can be provided at the `client::embed(..)` level as an argument,
 //! - or set in the client config `client_config.with_embed_options(..)` to be used as the default for all requests
 
-use crate::Headers;
+use crate::adapter::AdapterKind;
+use crate::{Headers, RetryPolicy};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
 // region:    --- EmbedOptions
 
@@ -41,6 +44,18 @@ pub struct EmbedOptions {
 	/// Common values: "NONE", "START", "END"
 	/// Default: "END"
 	pub truncate: Option<String>,
+
+	/// Retry policy for failed provider calls (overrides `ClientConfig` retry policy).
+	pub retry_policy: Option<RetryPolicy>,
+
+	/// Whether to use the `ClientConfig` response cache, if any (default: true).
+	pub response_cache: Option<bool>,
+
+	/// JSON object deep-merged into the provider request payload (objects merged recursively, `null` removes the key).
+	pub extra_body: Option<Value>,
+
+	/// Like `extra_body`, but only for the requests of an adapter kind (merged after `extra_body`).
+	pub adapter_extra_bodies: Option<HashMap<AdapterKind, Value>>,
 }
 
 /// Constructors
@@ -100,6 +115,32 @@ impl EmbedOptions {
 		self.truncate = Some(truncate.into());
 		self
 	}
+
+	/// Set the retry policy.
+	pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = Some(retry_policy);
+		self
+	}
+
+	/// Enable or disable the use of the client response cache.
+	pub fn with_response_cache(mut self, use_cache: bool) -> Self {
+		self.response_cache = Some(use_cache);
+		self
+	}
+
+	/// Set the JSON deep-merged into the request payload.
+	pub fn with_extra_body(mut self, extra_body: Value) -> Self {
+		self.extra_body = Some(extra_body);
+		self
+	}
+
+	/// Set the JSON deep-merged into the request payload for one adapter kind only.
+	pub fn with_adapter_extra_body(mut self, adapter_kind: AdapterKind, extra_body: Value) -> Self {
+		self.adapter_extra_bodies
+			.get_or_insert_with(HashMap::new)
+			.insert(adapter_kind, extra_body);
+		self
+	}
 }
 
 /// Getters
@@ -143,6 +184,26 @@ impl EmbedOptions {
 	pub fn truncate(&self) -> Option<&str> {
 		self.truncate.as_deref()
 	}
+
+	/// Get the retry policy.
+	pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+		self.retry_policy.as_ref()
+	}
+
+	/// Get whether to use the client response cache.
+	pub fn response_cache(&self) -> bool {
+		self.response_cache.unwrap_or(true)
+	}
+
+	/// Get the extra body.
+	pub fn extra_body(&self) -> Option<&Value> {
+		self.extra_body.as_ref()
+	}
+
+	/// Get the extra body of an adapter kind.
+	pub fn adapter_extra_body(&self, adapter_kind: AdapterKind) -> Option<&Value> {
+		self.adapter_extra_bodies.as_ref()?.get(&adapter_kind)
+	}
 }
 
 // endregion: --- EmbedOptions
@@ -231,6 +292,35 @@ impl<'client, 'request> EmbedOptionsSet<'client, 'request> {
 			.and_then(|o| o.truncate())
 			.or_else(|| self.client_options.and_then(|o| o.truncate()))
 	}
+
+	/// Get the effective retry policy (request overrides client).
+	pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+		self.request_options
+			.and_then(|o| o.retry_policy())
+			.or_else(|| self.client_options.and_then(|o| o.retry_policy()))
+	}
+
+	/// Get the effective response_cache setting (request overrides client).
+	pub fn response_cache(&self) -> bool {
+		self.request_options
+			.and_then(|o| o.response_cache)
+			.or_else(|| self.client_options.and_then(|o| o.response_cache))
+			.unwrap_or(true)
+	}
+
+	/// Get the effective extra body (request overrides client).
+	pub fn extra_body(&self) -> Option<&Value> {
+		self.request_options
+			.and_then(|o| o.extra_body())
+			.or_else(|| self.client_options.and_then(|o| o.extra_body()))
+	}
+
+	/// Get the effective extra body of an adapter kind (request overrides client).
+	pub fn adapter_extra_body(&self, adapter_kind: AdapterKind) -> Option<&Value> {
+		self.request_options
+			.and_then(|o| o.adapter_extra_body(adapter_kind))
+			.or_else(|| self.client_options.and_then(|o| o.adapter_extra_body(adapter_kind)))
+	}
 }
 
 // endregion: --- EmbedOptionsSet
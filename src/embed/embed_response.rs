@@ -24,6 +24,10 @@ pub struct EmbedResponse {
 
 	/// The raw value of the response body, which can be used for provider specific features.
 	pub captured_raw_body: Option<serde_json::Value>,
+
+	/// Whether this response was served from the client response cache (no provider call).
+	#[serde(default)]
+	pub cache_hit: bool,
 }
 
 /// Constructors
@@ -41,6 +45,7 @@ impl EmbedResponse {
 			provider_model_iden,
 			usage,
 			captured_raw_body: None,
+			cache_hit: false,
 		}
 	}
 
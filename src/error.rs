@@ -1,5 +1,5 @@
 use crate::adapter::AdapterKind;
-use crate::chat::ChatRole;
+use crate::chat::{ChatRole, SchemaViolation};
 use crate::{ModelIden, resolver, webc};
 use derive_more::{Display, From};
 use reqwest::StatusCode;
@@ -43,6 +43,12 @@ pub enum Error {
 	#[display("Failed to parse service tier. Actual: '{actual}'")]
 	ServiceTierParsing { actual: String },
 
+	#[display("Failed to parse pricing.\nCause: {cause}")]
+	PricingParsing { cause: String },
+
+	#[display("Extra body for model '{model_iden}' must be a JSON object. Actual: {actual}")]
+	ExtraBodyNotObject { model_iden: ModelIden, actual: String },
+
 	// -- Chat Output
 	#[display("No chat response from model '{model_iden}'")]
 	NoChatResponse { model_iden: ModelIden },
@@ -50,6 +56,33 @@ pub enum Error {
 	#[display("Invalid JSON response element: {info}")]
 	InvalidJsonResponseElement { info: &'static str },
 
+	#[display(
+		"{target} does not match the JSON schema:\n{}",
+		violations.iter().map(|violation| format!("  - {violation}")).collect::<Vec<_>>().join("\n")
+	)]
+	JsonSchemaViolations {
+		target: String,
+		violations: Vec<SchemaViolation>,
+	},
+
+	#[display(
+		"Typed output of model '{model_iden}' still invalid after {repair_attempts} repair attempt(s).\nCause: {cause}"
+	)]
+	TypedOutputInvalid {
+		model_iden: ModelIden,
+		repair_attempts: u32,
+		cause: String,
+		/// Last response text
+		text: String,
+	},
+
+	// -- Tool Use
+	#[display("No tool response for the tool call '{call_id}' (function '{fn_name}')")]
+	ToolResponseMissing { call_id: String, fn_name: String },
+
+	#[display("Tool response '{call_id}' does not match any tool call of the chat response")]
+	ToolResponseUnmatched { call_id: String },
+
 	// -- Auth
 	#[display("Model '{model_iden}' requires an API key.")]
 	RequiresApiKey { model_iden: ModelIden },
@@ -122,6 +155,18 @@ Cause:\n{cause}
 		body: String,
 	},
 
+	// -- Middleware
+	#[display("Client middleware failed for model '{model_iden}'.\nCause: {cause}")]
+	Middleware {
+		model_iden: ModelIden,
+		cause: String,
+		error: BoxError,
+	},
+
+	// -- Fallbacks
+	#[display("No model provided for the fallback chain.")]
+	FallbackModelsEmpty,
+
 	// -- Modules
 	#[display("Resolver error for model '{model_iden}'.\nCause: {resolver_error}")]
 	Resolver {
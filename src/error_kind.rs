@@ -0,0 +1,464 @@
+//! Normalized provider error taxonomy.
+//!
+//! Provider errors arrive in many shapes (HTTP status + provider JSON body, or in-stream error events).
+//! `Error::kind()` and `Error::provider_error()` normalize them into an [`ErrorKind`] with the
+//! provider error code and message extracted by the adapter of the model.
+
+use crate::adapter::{AdapterDispatcher, AdapterKind};
+use crate::{Error, webc};
+use derive_more::Display;
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// region:    --- ErrorKind
+
+/// Provider-agnostic error category.
+#[derive(Debug, Clone, Copy, Display, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ErrorKind {
+	/// Too many requests or tokens for the current quota/rate limit (e.g., HTTP 429).
+	RateLimited,
+	/// Missing or invalid credentials (e.g., HTTP 401).
+	AuthenticationFailed,
+	/// Valid credentials, but no access to the resource/model (e.g., HTTP 403).
+	PermissionDenied,
+	/// The prompt (plus requested output) does not fit in the model context window.
+	ContextLengthExceeded,
+	/// The request or the response was blocked by a safety/content filter.
+	ContentFiltered,
+	/// The provider is temporarily overloaded (e.g., Anthropic 529, HTTP 503).
+	Overloaded,
+	/// The request is invalid (e.g., HTTP 400, unsupported parameter).
+	InvalidRequest,
+	/// The model or resource does not exist (e.g., HTTP 404).
+	NotFound,
+	/// Internal provider error (e.g., HTTP 500).
+	ServerError,
+	/// The request timed out (client or provider side).
+	Timeout,
+	/// Any other error (including non-provider errors).
+	Other,
+}
+
+impl ErrorKind {
+	/// Returns true for the transient kinds worth retrying (possibly on another model):
+	/// `RateLimited`, `Overloaded`, `ServerError`, `Timeout`.
+	pub fn is_transient(&self) -> bool {
+		matches!(
+			self,
+			ErrorKind::RateLimited | ErrorKind::Overloaded | ErrorKind::ServerError | ErrorKind::Timeout
+		)
+	}
+
+	/// Maps an HTTP status to its default kind.
+	pub fn from_status(status: StatusCode) -> Option<Self> {
+		let kind = match status.as_u16() {
+			400 | 409 | 413 | 415 | 422 => ErrorKind::InvalidRequest,
+			401 => ErrorKind::AuthenticationFailed,
+			402 | 403 => ErrorKind::PermissionDenied,
+			404 => ErrorKind::NotFound,
+			408 | 504 => ErrorKind::Timeout,
+			429 => ErrorKind::RateLimited,
+			503 | 529 => ErrorKind::Overloaded,
+			500..=599 => ErrorKind::ServerError,
+			_ => return None,
+		};
+		Some(kind)
+	}
+
+	/// Detects the kinds that providers only express in the error message
+	/// (`ContextLengthExceeded`, `ContentFiltered`).
+	pub fn from_message(message: &str) -> Option<Self> {
+		const CONTEXT_LENGTH_PATTERNS: &[&str] = &[
+			"context length",
+			"context_length",
+			"context window",
+			"maximum context",
+			"prompt is too long",
+			"input is too long",
+			"too many tokens",
+			"too many input tokens",
+			"exceeds the maximum number of tokens",
+			"reduce the length",
+		];
+		const CONTENT_FILTER_PATTERNS: &[&str] = &[
+			"content_filter",
+			"content filter",
+			"content management policy",
+			"content_policy",
+			"safety system",
+		];
+
+		let message = message.to_lowercase();
+		if CONTEXT_LENGTH_PATTERNS.iter().any(|p| message.contains(p)) {
+			Some(ErrorKind::ContextLengthExceeded)
+		} else if CONTENT_FILTER_PATTERNS.iter().any(|p| message.contains(p)) {
+			Some(ErrorKind::ContentFiltered)
+		} else {
+			None
+		}
+	}
+}
+
+// endregion: --- ErrorKind
+
+// region:    --- ProviderError
+
+/// Normalized provider error, parsed by the adapter from the provider error body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderError {
+	/// Normalized category.
+	pub kind: ErrorKind,
+
+	/// HTTP status, when the error came from an HTTP response.
+	pub status: Option<u16>,
+
+	/// Provider error code/type (e.g., `context_length_exceeded`, `overloaded_error`, `RESOURCE_EXHAUSTED`).
+	pub code: Option<String>,
+
+	/// Provider error message.
+	pub message: Option<String>,
+}
+
+impl ProviderError {
+	/// Builds the `ProviderError` and resolves its kind.
+	///
+	/// Resolution order:
+	/// - `code_kind` (the provider code mapping), unless it is a generic `InvalidRequest`/`ServerError`,
+	/// - the HTTP status, when transient (429, 5xx, 408),
+	/// - the message patterns (see [`ErrorKind::from_message`]),
+	/// - `code_kind` (generic),
+	/// - the HTTP status,
+	/// - `ErrorKind::Other`.
+	pub(crate) fn from_parts(
+		status: Option<StatusCode>,
+		code: Option<String>,
+		message: Option<String>,
+		code_kind: Option<ErrorKind>,
+	) -> Self {
+		let status_kind = status.and_then(ErrorKind::from_status);
+		let kind = match code_kind {
+			Some(kind) if !matches!(kind, ErrorKind::InvalidRequest | ErrorKind::ServerError) => kind,
+			_ => status_kind
+				.filter(ErrorKind::is_transient)
+				.or_else(|| message.as_deref().and_then(ErrorKind::from_message))
+				.or(code_kind)
+				.or(status_kind)
+				.unwrap_or(ErrorKind::Other),
+		};
+
+		ProviderError {
+			kind,
+			status: status.map(|s| s.as_u16()),
+			code,
+			message,
+		}
+	}
+
+	/// Builds a `ProviderError` from a raw (possibly non-JSON) error body.
+	fn from_raw_body(
+		adapter_kind: Option<AdapterKind>,
+		status: Option<StatusCode>,
+		headers: Option<&HeaderMap>,
+		body: &str,
+	) -> Self {
+		match (adapter_kind, serde_json::from_str::<Value>(body)) {
+			(Some(adapter_kind), Ok(json_body)) => {
+				AdapterDispatcher::to_provider_error(adapter_kind, status, headers, &json_body)
+			}
+			_ => {
+				let message = (!body.trim().is_empty()).then(|| body.to_string());
+				ProviderError::from_parts(status, None, message, None)
+			}
+		}
+	}
+}
+
+// endregion: --- ProviderError
+
+// region:    --- Error Kind Accessors
+
+impl Error {
+	/// Returns the normalized kind of this error.
+	///
+	/// Non-provider errors (e.g., resolver, request building) return `ErrorKind::Other`.
+	pub fn kind(&self) -> ErrorKind {
+		if self.is_timeout() {
+			return ErrorKind::Timeout;
+		}
+		self.provider_error().map(|p| p.kind).unwrap_or(ErrorKind::Other)
+	}
+
+	/// Returns the normalized provider error (kind, status, code, message) when this error
+	/// comes from the provider (HTTP error response or in-stream error event).
+	pub fn provider_error(&self) -> Option<ProviderError> {
+		match self {
+			Error::WebModelCall { model_iden, webc_error } => webc_provider_error(model_iden.adapter_kind, webc_error),
+			Error::WebAdapterCall {
+				adapter_kind,
+				webc_error,
+			} => webc_provider_error(*adapter_kind, webc_error),
+			Error::ChatResponse { model_iden, body } => Some(AdapterDispatcher::to_provider_error(
+				model_iden.adapter_kind,
+				None,
+				None,
+				body,
+			)),
+			Error::HttpError { status, body, .. } => {
+				Some(ProviderError::from_raw_body(None, Some(*status), None, body))
+			}
+			Error::WebStream { model_iden, error, .. } => match error.downcast_ref::<Error>() {
+				Some(Error::HttpError { status, body, .. }) => Some(ProviderError::from_raw_body(
+					Some(model_iden.adapter_kind),
+					Some(*status),
+					None,
+					body,
+				)),
+				Some(inner) => inner.provider_error(),
+				None => None,
+			},
+			_ => None,
+		}
+	}
+
+	/// Returns true if the underlying HTTP call timed out.
+	fn is_timeout(&self) -> bool {
+		match self {
+			Error::WebModelCall {
+				webc_error: webc::Error::Reqwest(err),
+				..
+			}
+			| Error::WebAdapterCall {
+				webc_error: webc::Error::Reqwest(err),
+				..
+			} => err.is_timeout(),
+			Error::WebStream { error, .. } => error.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_timeout()),
+			_ => false,
+		}
+	}
+}
+
+fn webc_provider_error(adapter_kind: AdapterKind, webc_error: &webc::Error) -> Option<ProviderError> {
+	match webc_error {
+		webc::Error::ResponseFailedStatus { status, body, headers } => Some(ProviderError::from_raw_body(
+			Some(adapter_kind),
+			Some(*status),
+			Some(headers),
+			body,
+		)),
+		_ => None,
+	}
+}
+
+// endregion: --- Error Kind Accessors
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ModelIden;
+	use reqwest::header::HeaderValue;
+	use serde_json::json;
+
+	fn failed_status_error(adapter_kind: AdapterKind, status: u16, body: Value) -> Error {
+		Error::WebModelCall {
+			model_iden: ModelIden::new(adapter_kind, "test-model"),
+			webc_error: webc::Error::ResponseFailedStatus {
+				status: StatusCode::from_u16(status).unwrap(),
+				body: body.to_string(),
+				headers: Box::default(),
+			},
+		}
+	}
+
+	#[test]
+	fn test_error_kind_openai_shapes() {
+		let err = failed_status_error(
+			AdapterKind::OpenAI,
+			400,
+			json!({"error": {
+				"message": "This model's maximum context length is 128000 tokens.",
+				"type": "invalid_request_error",
+				"param": "messages",
+				"code": "context_length_exceeded"
+			}}),
+		);
+		let provider_error = err.provider_error().unwrap();
+		assert_eq!(provider_error.kind, ErrorKind::ContextLengthExceeded);
+		assert_eq!(provider_error.code.as_deref(), Some("context_length_exceeded"));
+		assert_eq!(provider_error.status, Some(400));
+
+		let err = failed_status_error(
+			AdapterKind::Groq,
+			401,
+			json!({"error": {"message": "Invalid API Key", "type": "invalid_request_error", "code": "invalid_api_key"}}),
+		);
+		assert_eq!(err.kind(), ErrorKind::AuthenticationFailed);
+
+		let err = failed_status_error(
+			AdapterKind::OpenAI,
+			400,
+			json!({"error": {"message": "The response was filtered", "code": "content_filter"}}),
+		);
+		assert_eq!(err.kind(), ErrorKind::ContentFiltered);
+
+		// a transient status wins over the message patterns
+		let err = failed_status_error(
+			AdapterKind::OpenAI,
+			429,
+			json!({"error": {"message": "Rate limit reached: too many tokens per minute", "type": "tokens"}}),
+		);
+		assert_eq!(err.kind(), ErrorKind::RateLimited);
+
+		// in-stream error (the error object itself)
+		let err = Error::ChatResponse {
+			model_iden: ModelIden::new(AdapterKind::OpenAI, "gpt-4o"),
+			body: json!({"message": "Rate limit reached", "type": "requests", "code": "rate_limit_exceeded"}),
+		};
+		assert_eq!(err.kind(), ErrorKind::RateLimited);
+	}
+
+	#[test]
+	fn test_error_kind_anthropic_shapes() {
+		let err = failed_status_error(
+			AdapterKind::Anthropic,
+			529,
+			json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+		);
+		let provider_error = err.provider_error().unwrap();
+		assert_eq!(provider_error.kind, ErrorKind::Overloaded);
+		assert_eq!(provider_error.code.as_deref(), Some("overloaded_error"));
+		assert_eq!(provider_error.message.as_deref(), Some("Overloaded"));
+
+		let err = failed_status_error(
+			AdapterKind::Anthropic,
+			400,
+			json!({"type": "error", "error": {
+				"type": "invalid_request_error",
+				"message": "prompt is too long: 210000 tokens > 200000 maximum"
+			}}),
+		);
+		assert_eq!(err.kind(), ErrorKind::ContextLengthExceeded);
+
+		let err = Error::ChatResponse {
+			model_iden: ModelIden::new(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+			body: json!({"type": "error", "error": {"type": "permission_error", "message": "No access"}}),
+		};
+		assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+	}
+
+	#[test]
+	fn test_error_kind_gemini_shapes() {
+		let err = failed_status_error(
+			AdapterKind::Gemini,
+			429,
+			json!({"error": {"code": 429, "message": "Resource has been exhausted", "status": "RESOURCE_EXHAUSTED"}}),
+		);
+		let provider_error = err.provider_error().unwrap();
+		assert_eq!(provider_error.kind, ErrorKind::RateLimited);
+		assert_eq!(provider_error.code.as_deref(), Some("RESOURCE_EXHAUSTED"));
+
+		let err = failed_status_error(
+			AdapterKind::Gemini,
+			400,
+			json!({"error": {
+				"code": 400,
+				"message": "API key not valid. Please pass a valid API key.",
+				"status": "INVALID_ARGUMENT",
+				"details": [{"@type": "type.googleapis.com/google.rpc.ErrorInfo", "reason": "API_KEY_INVALID"}]
+			}}),
+		);
+		assert_eq!(err.kind(), ErrorKind::AuthenticationFailed);
+
+		let err = Error::ChatResponse {
+			model_iden: ModelIden::new(AdapterKind::Gemini, "gemini-2.5-flash"),
+			body: json!({"finishReason": "SAFETY", "usageMetadata": null}),
+		};
+		assert_eq!(err.kind(), ErrorKind::ContentFiltered);
+
+		let err = Error::ChatResponse {
+			model_iden: ModelIden::new(AdapterKind::Gemini, "gemini-2.5-flash"),
+			body: json!({"finishReason": "OTHER", "usageMetadata": null}),
+		};
+		assert_eq!(err.kind(), ErrorKind::Other);
+	}
+
+	#[test]
+	fn test_error_kind_bedrock_cohere_ollama_shapes() {
+		let mut headers = HeaderMap::new();
+		headers.insert(
+			"x-amzn-errortype",
+			HeaderValue::from_static("ThrottlingException:http://internal"),
+		);
+		let err = Error::WebModelCall {
+			model_iden: ModelIden::new(AdapterKind::Bedrock, "amazon.titan-text-express-v1"),
+			webc_error: webc::Error::ResponseFailedStatus {
+				status: StatusCode::TOO_MANY_REQUESTS,
+				body: json!({"message": "Too many requests, please wait before trying again."}).to_string(),
+				headers: Box::new(headers),
+			},
+		};
+		let provider_error = err.provider_error().unwrap();
+		assert_eq!(provider_error.kind, ErrorKind::RateLimited);
+		assert_eq!(provider_error.code.as_deref(), Some("ThrottlingException"));
+
+		let err = failed_status_error(
+			AdapterKind::Bedrock,
+			400,
+			json!({"message": "Input is too long for requested model."}),
+		);
+		assert_eq!(err.kind(), ErrorKind::ContextLengthExceeded);
+
+		let err = failed_status_error(
+			AdapterKind::Cohere,
+			400,
+			json!({"id": "abc", "message": "too many tokens: total number of tokens in the prompt cannot exceed 4081"}),
+		);
+		assert_eq!(err.kind(), ErrorKind::ContextLengthExceeded);
+
+		let err = failed_status_error(
+			AdapterKind::Ollama,
+			404,
+			json!({"error": "model \"llama9\" not found, try pulling it first"}),
+		);
+		let provider_error = err.provider_error().unwrap();
+		assert_eq!(provider_error.kind, ErrorKind::NotFound);
+		assert_eq!(
+			provider_error.message.as_deref(),
+			Some("model \"llama9\" not found, try pulling it first")
+		);
+	}
+
+	#[test]
+	fn test_error_kind_non_json_and_stream_http_error() {
+		let err = Error::WebModelCall {
+			model_iden: ModelIden::new(AdapterKind::OpenAI, "gpt-4o"),
+			webc_error: webc::Error::ResponseFailedStatus {
+				status: StatusCode::BAD_GATEWAY,
+				body: "<html>Bad Gateway</html>".to_string(),
+				headers: Box::default(),
+			},
+		};
+		assert_eq!(err.kind(), ErrorKind::ServerError);
+
+		let http_error = Error::HttpError {
+			status: StatusCode::TOO_MANY_REQUESTS,
+			canonical_reason: "Too Many Requests".to_string(),
+			body: json!({"type": "error", "error": {"type": "rate_limit_error", "message": "Slow down"}}).to_string(),
+		};
+		let err = Error::WebStream {
+			model_iden: ModelIden::new(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+			cause: http_error.to_string(),
+			error: Box::new(http_error),
+		};
+		let provider_error = err.provider_error().unwrap();
+		assert_eq!(provider_error.kind, ErrorKind::RateLimited);
+		assert_eq!(provider_error.code.as_deref(), Some("rate_limit_error"));
+
+		assert_eq!(Error::FallbackModelsEmpty.kind(), ErrorKind::Other);
+	}
+}
+
+// endregion: --- Tests
@@ -8,11 +8,14 @@ mod support;
 mod client;
 mod common;
 mod error;
+mod error_kind;
+mod macro_support;
 
 // -- Flatten
 pub use client::*;
 pub use common::*;
 pub use error::{BoxError, Error, Result};
+pub use error_kind::{ErrorKind, ProviderError};
 
 // -- Public Modules
 pub mod adapter;
@@ -21,4 +24,15 @@ pub mod embed;
 pub mod resolver;
 pub mod webc;
 
+// -- Macros (`macros` feature)
+#[cfg(feature = "macros")]
+pub use genai_macros::{GenaiSchema, tool};
+
+/// Support of the `genai-macros` generated code (not a public API).
+#[doc(hidden)]
+pub mod __private {
+	pub use crate::macro_support::*;
+	pub use serde_json;
+}
+
 // endregion: --- Modules
@@ -0,0 +1,98 @@
+//! Support of the `genai-macros` generated code (re-exported in the hidden `genai::__private` module).
+//!
+//! NOTE: Not a public API, can change without notice.
+
+use crate::BoxError;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+// region:    --- Schema Support
+
+/// Returns the schema with its description.
+pub fn schema_with_description(mut schema: Value, description: Option<&str>) -> Value {
+	if let (Some(description), Value::Object(map)) = (description, &mut schema) {
+		map.insert("description".to_string(), description.into());
+	}
+	schema
+}
+
+/// Returns the object schema of the properties `(name, schema, required)`.
+pub fn object_schema(description: Option<&str>, properties: Vec<(&str, Value, bool)>) -> Value {
+	let mut props = Map::new();
+	let mut required: Vec<Value> = Vec::new();
+	for (name, schema, is_required) in properties {
+		if is_required {
+			required.push(name.into());
+		}
+		props.insert(name.to_string(), schema);
+	}
+
+	let schema = json!({"type": "object", "properties": props, "required": required});
+	schema_with_description(schema, description)
+}
+
+/// Returns the tuple schema of the items (fixed length array).
+pub fn tuple_schema(description: Option<&str>, items: Vec<Value>) -> Value {
+	let len = items.len();
+	let schema = json!({"type": "array", "prefixItems": items, "minItems": len, "maxItems": len});
+	schema_with_description(schema, description)
+}
+
+/// Returns the schema of the string values.
+pub fn string_enum_schema(description: Option<&str>, values: &[&str]) -> Value {
+	schema_with_description(json!({"type": "string", "enum": values}), description)
+}
+
+/// Returns the schema of the externally tagged variant, `{"<name>": <content>}`.
+pub fn external_variant_schema(description: Option<&str>, name: &str, content: Value) -> Value {
+	let schema = json!({"type": "object", "properties": {name: content}, "required": [name]});
+	schema_with_description(schema, description)
+}
+
+/// Returns the schema of the internally tagged variant: the content object schema (empty for a unit variant)
+/// with the `tag` property.
+pub fn internal_variant_schema(description: Option<&str>, tag: &str, name: &str, content: Option<Value>) -> Value {
+	let mut schema = content.unwrap_or_else(|| object_schema(None, Vec::new()));
+	if let Some(Value::Object(props)) = schema.get_mut("properties") {
+		props.insert(tag.to_string(), json!({"type": "string", "enum": [name]}));
+	}
+	if let Some(Value::Array(required)) = schema.get_mut("required") {
+		required.insert(0, tag.into());
+	}
+	schema_with_description(schema, description)
+}
+
+/// Returns the schema of one of the variants, with `oneOf` (or `anyOf` when not `exclusive`).
+pub fn variants_schema(description: Option<&str>, variants: Vec<Value>, exclusive: bool) -> Value {
+	let keyword = if exclusive { "oneOf" } else { "anyOf" };
+	schema_with_description(json!({ keyword: variants }), description)
+}
+
+// endregion: --- Schema Support
+
+// region:    --- Tool Support
+
+/// Returns the tool call argument deserialized (a missing argument is deserialized from `null`).
+pub fn tool_argument<T: DeserializeOwned>(arguments: &Value, name: &str) -> Result<T, BoxError> {
+	let value = arguments.get(name).cloned().unwrap_or(Value::Null);
+	serde_json::from_value(value).map_err(|err| format!("invalid argument '{name}': {err}").into())
+}
+
+/// The return types of the `#[genai::tool]` functions.
+pub trait IntoToolOutput {
+	fn into_tool_output(self) -> Result<Value, BoxError>;
+}
+
+impl<T, E> IntoToolOutput for Result<T, E>
+where
+	T: Serialize,
+	E: Into<BoxError>,
+{
+	fn into_tool_output(self) -> Result<Value, BoxError> {
+		let output = self.map_err(Into::into)?;
+		Ok(serde_json::to_value(output)?)
+	}
+}
+
+// endregion: --- Tool Support
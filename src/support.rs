@@ -1,4 +1,5 @@
 use serde::{Deserialize, Deserializer};
+use serde_json::Value;
 
 // region:    --- Serde Support
 
@@ -35,3 +36,28 @@ pub fn combine_text_with_empty_line(combined: &mut String, text: &str) {
 }
 
 // endregion: --- Text Support
+
+// region:    --- JSON Support
+
+/// Deep-merges `patch` into `target` (JSON Merge Patch, RFC 7386):
+/// objects are merged recursively, a `null` removes the key, and any other value replaces the target one.
+pub fn merge_json(target: &mut Value, patch: &Value) {
+	let Value::Object(patch_map) = patch else {
+		*target = patch.clone();
+		return;
+	};
+	if !target.is_object() {
+		*target = Value::Object(Default::default());
+	}
+	if let Value::Object(target_map) = target {
+		for (key, patch_value) in patch_map {
+			if patch_value.is_null() {
+				target_map.remove(key);
+			} else {
+				merge_json(target_map.entry(key.clone()).or_insert(Value::Null), patch_value);
+			}
+		}
+	}
+}
+
+// endregion: --- JSON Support
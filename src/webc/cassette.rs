@@ -0,0 +1,302 @@
+//! Record/replay of HTTP interactions ("cassettes") for offline, deterministic tests.
+//!
+//! A cassette is a JSON file with the ordered list of request/response pairs:
+//!
+//! ```json
+//! { "interactions": [
+//!     { "request":  { "method": "POST", "url": "https://...", "payload": { } },
+//!       "response": { "status": 200, "headers": { "content-type": "..." }, "body": "..." } }
+//! ] }
+//! ```
+//!
+//! Streaming responses (SSE, JSON array, delimited) are recorded as their raw body text,
+//! so they are replayed through the same `WebStream` / `EventSourceStream` parsing.
+
+use crate::webc::{
+	Error, Result, Transport, TransportFuture, TransportRequest, TransportResponse, TransportStreamResponse,
+};
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The cassette mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+	/// Sends the requests, and records each request/response pair (overwrites the cassette file).
+	Record,
+	/// Never touches the network; serves the recorded responses.
+	Replay,
+}
+
+/// Record/replay cassette for the `Client` web calls (set with `ClientBuilder::with_cassette`).
+///
+/// Replay matches on method, URL, and JSON payload (key order insensitive).
+/// Each recorded interaction is served once, in recording order, so that identical requests
+/// (e.g., retries) replay their successive responses.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+	inner: Arc<CassetteInner>,
+}
+
+#[derive(Debug)]
+struct CassetteInner {
+	path: PathBuf,
+	mode: CassetteMode,
+	state: Mutex<CassetteState>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+	file: CassetteFile,
+	/// Replay only: the interactions already served.
+	used: Vec<bool>,
+}
+
+/// Constructors
+impl Cassette {
+	/// Creates a cassette recording to `path` (the file is written after each interaction).
+	pub fn record(path: impl Into<PathBuf>) -> Self {
+		Self::new(path.into(), CassetteMode::Record, CassetteFile::default())
+	}
+
+	/// Loads the cassette at `path` for replay.
+	pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+		let path = path.into();
+		let content = std::fs::read_to_string(&path).map_err(|err| cassette_error(&path, err))?;
+		let file: CassetteFile = serde_json::from_str(&content).map_err(|err| cassette_error(&path, err))?;
+		Ok(Self::new(path, CassetteMode::Replay, file))
+	}
+
+	fn new(path: PathBuf, mode: CassetteMode, file: CassetteFile) -> Self {
+		let used = vec![false; file.interactions.len()];
+		Self {
+			inner: Arc::new(CassetteInner {
+				path,
+				mode,
+				state: Mutex::new(CassetteState { file, used }),
+			}),
+		}
+	}
+}
+
+/// Getters
+impl Cassette {
+	/// Returns the cassette file path.
+	pub fn path(&self) -> &Path {
+		&self.inner.path
+	}
+
+	/// Returns the cassette mode.
+	pub fn mode(&self) -> CassetteMode {
+		self.inner.mode
+	}
+}
+
+/// Support
+impl Cassette {
+	fn replay_response(&self, request: &CassetteRequest) -> Result<CassetteResponse> {
+		let mut state = self.inner.state.lock().map_err(|err| cassette_error(&self.inner.path, err))?;
+		let CassetteState { file, used } = &mut *state;
+
+		let found = file
+			.interactions
+			.iter()
+			.zip(used.iter_mut())
+			.find(|(interaction, used)| !**used && interaction.request.matches(request));
+
+		match found {
+			Some((interaction, used)) => {
+				*used = true;
+				Ok(interaction.response.clone())
+			}
+			None => Err(Error::CassetteNoInteraction {
+				path: self.inner.path.to_string_lossy().to_string(),
+				method: request.method.clone(),
+				url: request.url.clone(),
+			}),
+		}
+	}
+
+	fn push_interaction(&self, interaction: CassetteInteraction) -> Result<()> {
+		let path = &self.inner.path;
+		let mut state = self.inner.state.lock().map_err(|err| cassette_error(path, err))?;
+		state.file.interactions.push(interaction);
+
+		if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+			std::fs::create_dir_all(dir).map_err(|err| cassette_error(path, err))?;
+		}
+		let content = serde_json::to_string_pretty(&state.file).map_err(|err| cassette_error(path, err))?;
+		std::fs::write(path, content).map_err(|err| cassette_error(path, err))?;
+
+		Ok(())
+	}
+}
+
+// region:    --- CassetteTransport
+
+/// The `Transport` decorator recording or replaying the calls of the inner transport.
+#[derive(Debug)]
+pub(crate) struct CassetteTransport {
+	cassette: Cassette,
+	inner: Arc<dyn Transport>,
+}
+
+impl CassetteTransport {
+	pub(crate) fn new(cassette: Cassette, inner: Arc<dyn Transport>) -> Self {
+		Self { cassette, inner }
+	}
+
+	async fn send(&self, request: TransportRequest, stream: bool) -> Result<TransportResponse> {
+		let cassette_request = CassetteRequest::from(&request);
+
+		match self.cassette.mode() {
+			CassetteMode::Replay => self.cassette.replay_response(&cassette_request)?.into_transport_response(),
+			CassetteMode::Record => {
+				let res = if stream {
+					// NOTE: The stream body is recorded as a whole, and replayed as a single chunk.
+					let res = self.inner.post_stream(request).await?;
+					let (status, headers) = (res.status, res.headers.clone());
+					let body = res
+						.collect_body()
+						.await
+						.map_err(|err| Error::Transport { cause: err.to_string() })?;
+					TransportResponse { status, headers, body }
+				} else if request.method == Method::GET {
+					self.inner.get(request).await?
+				} else {
+					self.inner.post_json(request).await?
+				};
+
+				let response = CassetteResponse::from(&res);
+				self.cassette.push_interaction(CassetteInteraction {
+					request: cassette_request,
+					response,
+				})?;
+
+				Ok(res)
+			}
+		}
+	}
+}
+
+impl Transport for CassetteTransport {
+	fn post_json(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		Box::pin(self.send(request, false))
+	}
+
+	fn post_stream(&self, request: TransportRequest) -> TransportFuture<'_, TransportStreamResponse> {
+		Box::pin(async move {
+			let res = self.send(request, true).await?;
+			let body = futures::stream::iter([Ok(res.body)]);
+			Ok(TransportStreamResponse {
+				status: res.status,
+				headers: res.headers,
+				body: Box::pin(body),
+			})
+		})
+	}
+
+	fn get(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		Box::pin(self.send(request, false))
+	}
+}
+
+// endregion: --- CassetteTransport
+
+// region:    --- Cassette File Types
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+	interactions: Vec<CassetteInteraction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteInteraction {
+	request: CassetteRequest,
+	response: CassetteResponse,
+}
+
+/// The request matching key (no headers, so that secrets are never recorded).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteRequest {
+	method: String,
+	url: String,
+	#[serde(default)]
+	payload: Value,
+}
+
+impl CassetteRequest {
+	fn matches(&self, other: &CassetteRequest) -> bool {
+		self.method.eq_ignore_ascii_case(&other.method) && self.url == other.url && self.payload == other.payload
+	}
+}
+
+impl From<&TransportRequest> for CassetteRequest {
+	fn from(request: &TransportRequest) -> Self {
+		Self {
+			method: request.method.to_string(),
+			url: request.url.clone(),
+			payload: request.body.clone().unwrap_or_default(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CassetteResponse {
+	status: u16,
+	#[serde(default)]
+	headers: BTreeMap<String, String>,
+	body: String,
+}
+
+impl CassetteResponse {
+	fn into_transport_response(self) -> Result<TransportResponse> {
+		let status = StatusCode::from_u16(self.status).map_err(|err| Error::Transport { cause: err.to_string() })?;
+		let mut headers = HeaderMap::new();
+		for (name, value) in &self.headers {
+			if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+				headers.append(name, value);
+			}
+		}
+
+		Ok(TransportResponse {
+			status,
+			headers,
+			body: Bytes::from(self.body),
+		})
+	}
+}
+
+impl From<&TransportResponse> for CassetteResponse {
+	fn from(res: &TransportResponse) -> Self {
+		let headers = res
+			.headers
+			.iter()
+			.filter(|(name, _)| *name != reqwest::header::SET_COOKIE)
+			.filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+			.collect();
+		Self {
+			status: res.status.as_u16(),
+			headers,
+			body: String::from_utf8_lossy(&res.body).into_owned(),
+		}
+	}
+}
+
+// endregion: --- Cassette File Types
+
+// region:    --- Support
+
+fn cassette_error(path: &Path, cause: impl std::fmt::Display) -> Error {
+	Error::Cassette {
+		path: path.to_string_lossy().to_string(),
+		cause: cause.to_string(),
+	}
+}
+
+// endregion: --- Support
@@ -21,6 +21,17 @@ pub enum Error {
 		headers: Box<HeaderMap>,
 	},
 
+	// -- Transport
+	#[display("Transport error. Cause: {cause}")]
+	Transport { cause: String },
+
+	// -- Cassette
+	#[display("Cassette '{path}' has no (unused) recorded interaction for {method} {url}")]
+	CassetteNoInteraction { path: String, method: String, url: String },
+
+	#[display("Cassette '{path}' error. Cause: {cause}")]
+	Cassette { path: String, cause: String },
+
 	// -- Utils
 	#[display("JSON value extension error: {_0}")]
 	#[from]
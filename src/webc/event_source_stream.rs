@@ -1,7 +1,7 @@
+use crate::RetryPolicy;
 use crate::error::BoxError;
-use crate::webc::WebStream;
+use crate::webc::{StreamRequest, WebStream};
 use futures::Stream;
-use reqwest::RequestBuilder;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -24,13 +24,19 @@ pub struct Message {
 }
 
 impl EventSourceStream {
-	pub fn new(reqwest_builder: RequestBuilder) -> Self {
+	pub fn new(stream_request: StreamRequest) -> Self {
 		// Standard EventSource uses \n\n as event separator
 		Self {
-			inner: WebStream::new_with_delimiter(reqwest_builder, "\n\n"),
+			inner: WebStream::new_with_delimiter(stream_request, "\n\n"),
 			opened: false,
 		}
 	}
+
+	/// Sets the retry policy for the initial request (see [`WebStream::with_retry_policy`]).
+	pub fn with_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+		self.inner = self.inner.with_retry_policy(retry_policy);
+		self
+	}
 }
 
 impl Stream for EventSourceStream {
@@ -0,0 +1,185 @@
+use crate::webc::{
+	Error, Result, Transport, TransportFuture, TransportRequest, TransportResponse, TransportStreamResponse,
+};
+use bytes::Bytes;
+use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A `Transport` serving canned responses without any network (e.g., for tests).
+///
+/// Responses are registered per method and URL, and each one is served once, in registration order.
+/// Clones share the same state, so a clone can be kept to inspect the received `requests()`.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTransport {
+	state: Arc<Mutex<InMemoryState>>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryState {
+	routes: Vec<InMemoryRoute>,
+	requests: Vec<TransportRequest>,
+}
+
+#[derive(Debug)]
+struct InMemoryRoute {
+	method: Method,
+	url: String,
+	response: InMemoryResponse,
+	used: bool,
+}
+
+/// Constructors & Setters
+impl InMemoryTransport {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers a response for the given method and URL.
+	pub fn with_response(self, method: Method, url: impl Into<String>, response: InMemoryResponse) -> Self {
+		if let Ok(mut state) = self.state.lock() {
+			state.routes.push(InMemoryRoute {
+				method,
+				url: url.into(),
+				response,
+				used: false,
+			});
+		}
+		self
+	}
+}
+
+/// Getters
+impl InMemoryTransport {
+	/// Returns the requests received so far, in order.
+	pub fn requests(&self) -> Vec<TransportRequest> {
+		self.state.lock().map(|state| state.requests.clone()).unwrap_or_default()
+	}
+}
+
+/// Support
+impl InMemoryTransport {
+	fn lock(&self) -> Result<MutexGuard<'_, InMemoryState>> {
+		self.state.lock().map_err(|err| Error::Transport { cause: err.to_string() })
+	}
+
+	fn take_response(&self, request: TransportRequest) -> Result<InMemoryResponse> {
+		let mut state = self.lock()?;
+
+		let route = state
+			.routes
+			.iter_mut()
+			.find(|route| !route.used && route.method == request.method && route.url == request.url);
+		let response = match route {
+			Some(route) => {
+				route.used = true;
+				route.response.clone()
+			}
+			None => {
+				return Err(Error::Transport {
+					cause: format!("No in-memory response for {} {}", request.method, request.url),
+				});
+			}
+		};
+		state.requests.push(request);
+
+		Ok(response)
+	}
+}
+
+impl Transport for InMemoryTransport {
+	fn post_json(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		Box::pin(async move { self.take_response(request).map(InMemoryResponse::into_transport_response) })
+	}
+
+	fn post_stream(&self, request: TransportRequest) -> TransportFuture<'_, TransportStreamResponse> {
+		Box::pin(async move {
+			self.take_response(request)
+				.map(InMemoryResponse::into_transport_stream_response)
+		})
+	}
+
+	fn get(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		Box::pin(async move { self.take_response(request).map(InMemoryResponse::into_transport_response) })
+	}
+}
+
+// region:    --- InMemoryResponse
+
+/// A canned `InMemoryTransport` response.
+///
+/// The body is kept as chunks, which are streamed one by one for streaming requests.
+#[derive(Debug, Clone)]
+pub struct InMemoryResponse {
+	status: StatusCode,
+	headers: HeaderMap,
+	chunks: Vec<Bytes>,
+}
+
+/// Constructors
+impl InMemoryResponse {
+	/// A response with a single body chunk.
+	pub fn new(status: StatusCode, content_type: &str, body: impl Into<String>) -> Self {
+		Self::from_chunks(status, content_type, vec![body.into()])
+	}
+
+	/// A `200 OK` JSON response.
+	pub fn json(body: Value) -> Self {
+		Self::new(StatusCode::OK, "application/json", body.to_string())
+	}
+
+	/// A `200 OK` `text/event-stream` response, streamed one chunk per item.
+	pub fn event_stream(chunks: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self::from_chunks(
+			StatusCode::OK,
+			"text/event-stream",
+			chunks.into_iter().map(Into::into).collect(),
+		)
+	}
+
+	/// A response streamed one chunk per item (e.g., to test messages split across chunks).
+	pub fn from_chunks(status: StatusCode, content_type: &str, chunks: Vec<String>) -> Self {
+		let mut headers = HeaderMap::new();
+		if let Ok(content_type) = HeaderValue::from_str(content_type) {
+			headers.insert(CONTENT_TYPE, content_type);
+		}
+		Self {
+			status,
+			headers,
+			chunks: chunks.into_iter().map(Bytes::from).collect(),
+		}
+	}
+}
+
+/// Setters
+impl InMemoryResponse {
+	pub fn with_header(mut self, name: &str, value: &str) -> Self {
+		if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+			self.headers.insert(name, value);
+		}
+		self
+	}
+}
+
+/// Support
+impl InMemoryResponse {
+	fn into_transport_response(self) -> TransportResponse {
+		TransportResponse {
+			status: self.status,
+			headers: self.headers,
+			body: self.chunks.concat().into(),
+		}
+	}
+
+	fn into_transport_stream_response(self) -> TransportStreamResponse {
+		let chunks = self.chunks.into_iter().map(Ok);
+		TransportStreamResponse {
+			status: self.status,
+			headers: self.headers,
+			body: Box::pin(futures::stream::iter(chunks)),
+		}
+	}
+}
+
+// endregion: --- InMemoryResponse
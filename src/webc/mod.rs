@@ -1,18 +1,27 @@
-//! The GenAI web client construct that uses reqwest. Only `webc::Error` is exposed as the public interface.
+//! The GenAI web client construct, sending through a pluggable `Transport` (reqwest by default).
+//! Only `webc::Error`, `WebResponse`, the transport types, and the cassette types are exposed as the public interface.
 
 // region:    --- Modules
 
+mod cassette;
 mod error;
 mod event_source_stream;
+mod in_memory_transport;
+mod transport;
 mod web_client;
 mod web_stream;
 
+pub(crate) use cassette::CassetteTransport;
 pub(crate) use error::Result;
 pub(crate) use event_source_stream::*;
 pub(crate) use web_client::*;
 pub(crate) use web_stream::*;
 
 // Only public for external use
+pub use cassette::{Cassette, CassetteMode};
 pub use error::Error;
+pub use in_memory_transport::{InMemoryResponse, InMemoryTransport};
+pub use transport::*;
+pub use web_client::WebResponse;
 
 // endregion: --- Modules
@@ -0,0 +1,168 @@
+//! The HTTP transport abstraction used by `WebClient`, `WebStream`, and `EventSourceStream`.
+//!
+//! `ReqwestTransport` is the default implementation. `InMemoryTransport` serves canned responses
+//! (e.g., for tests), and custom implementations can be set with `ClientBuilder::with_transport`.
+
+use crate::Headers;
+use crate::error::BoxError;
+use crate::webc::Result;
+use bytes::Bytes;
+use futures::{Future, Stream, TryStreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use std::pin::Pin;
+
+/// The future returned by the `Transport` methods.
+pub type TransportFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// The response body byte stream of `Transport::post_stream`.
+pub type TransportByteStream = Pin<Box<dyn Stream<Item = core::result::Result<Bytes, BoxError>> + Send>>;
+
+/// The HTTP transport used by the `Client` for all its web calls.
+///
+/// Implementations only move bytes; status handling, JSON parsing, retries, and stream parsing
+/// stay in `genai`.
+pub trait Transport: Send + Sync {
+	/// Sends a POST request with a JSON body, and returns the complete response.
+	fn post_json(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse>;
+
+	/// Sends a POST request with a JSON body, and returns the response with its body as a byte stream.
+	fn post_stream(&self, request: TransportRequest) -> TransportFuture<'_, TransportStreamResponse>;
+
+	/// Sends a GET request, and returns the complete response.
+	fn get(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse>;
+}
+
+impl std::fmt::Debug for dyn Transport {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Transport")
+	}
+}
+
+// region:    --- Transport Types
+
+/// A transport request (the `body` is `None` for GET requests).
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+	pub method: Method,
+	pub url: String,
+	pub headers: Headers,
+	pub body: Option<Value>,
+}
+
+impl TransportRequest {
+	pub fn get(url: impl Into<String>, headers: Headers) -> Self {
+		Self {
+			method: Method::GET,
+			url: url.into(),
+			headers,
+			body: None,
+		}
+	}
+
+	pub fn post_json(url: impl Into<String>, headers: Headers, body: Value) -> Self {
+		Self {
+			method: Method::POST,
+			url: url.into(),
+			headers,
+			body: Some(body),
+		}
+	}
+}
+
+/// A complete transport response.
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+	pub status: StatusCode,
+	pub headers: HeaderMap,
+	pub body: Bytes,
+}
+
+/// A transport response with its body as a byte stream.
+pub struct TransportStreamResponse {
+	pub status: StatusCode,
+	pub headers: HeaderMap,
+	pub body: TransportByteStream,
+}
+
+impl std::fmt::Debug for TransportStreamResponse {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("TransportStreamResponse")
+			.field("status", &self.status)
+			.field("headers", &self.headers)
+			.finish_non_exhaustive()
+	}
+}
+
+impl TransportStreamResponse {
+	/// Reads the whole body stream.
+	pub async fn collect_body(self) -> core::result::Result<Bytes, BoxError> {
+		let chunks: Vec<Bytes> = self.body.try_collect().await?;
+		Ok(chunks.concat().into())
+	}
+}
+
+// endregion: --- Transport Types
+
+// region:    --- ReqwestTransport
+
+/// The default `Transport`, backed by a `reqwest::Client`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+	reqwest_client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+	pub fn new(reqwest_client: reqwest::Client) -> Self {
+		Self { reqwest_client }
+	}
+
+	fn new_req_builder(&self, request: TransportRequest) -> reqwest::RequestBuilder {
+		let mut reqwest_builder = self.reqwest_client.request(request.method, request.url);
+		for (k, v) in request.headers.iter() {
+			reqwest_builder = reqwest_builder.header(k, v);
+		}
+		if let Some(body) = &request.body {
+			reqwest_builder = reqwest_builder.json(body);
+		}
+		reqwest_builder
+	}
+
+	async fn send(&self, request: TransportRequest) -> Result<TransportResponse> {
+		let res = self.new_req_builder(request).send().await?;
+		let status = res.status();
+		let headers = res.headers().clone();
+		let body = res.bytes().await?;
+
+		Ok(TransportResponse { status, headers, body })
+	}
+}
+
+impl Transport for ReqwestTransport {
+	fn post_json(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		Box::pin(self.send(request))
+	}
+
+	fn post_stream(&self, request: TransportRequest) -> TransportFuture<'_, TransportStreamResponse> {
+		Box::pin(async move {
+			let res = self.new_req_builder(request).send().await?;
+			let status = res.status();
+			let headers = res.headers().clone();
+			// NOTE: Keep the reqwest error as the source (e.g., for timeout detection).
+			let body = res.bytes_stream().map_err(|err| Box::new(err) as BoxError);
+
+			Ok(TransportStreamResponse {
+				status,
+				headers,
+				body: Box::pin(body),
+			})
+		})
+	}
+
+	fn get(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		Box::pin(self.send(request))
+	}
+}
+
+// endregion: --- ReqwestTransport
@@ -1,20 +1,23 @@
 use crate::Headers;
-use crate::webc::{Error, Result};
-use reqwest::header::HeaderMap;
-use reqwest::{Method, RequestBuilder, StatusCode};
+use crate::webc::{
+	Cassette, CassetteTransport, Error, ReqwestTransport, Result, Transport, TransportRequest, TransportResponse,
+	TransportStreamResponse,
+};
+use reqwest::StatusCode;
 use serde_json::Value;
+use std::sync::Arc;
 
-/// A simple reqwest client wrapper for this library.
-#[derive(Debug)]
+/// A simple web client for this library, sending through a `Transport` (reqwest by default).
+#[derive(Debug, Clone)]
 pub struct WebClient {
-	reqwest_client: reqwest::Client,
+	transport: Arc<dyn Transport>,
 }
 
 // Implements Default
 impl Default for WebClient {
 	fn default() -> Self {
 		WebClient {
-			reqwest_client: reqwest::Client::new(),
+			transport: Arc::new(ReqwestTransport::default()),
 		}
 	}
 }
@@ -23,7 +26,19 @@ impl Default for WebClient {
 
 impl WebClient {
 	pub fn from_reqwest_client(reqwest_client: reqwest::Client) -> Self {
-		WebClient { reqwest_client }
+		Self::from_transport(Arc::new(ReqwestTransport::new(reqwest_client)))
+	}
+
+	pub fn from_transport(transport: Arc<dyn Transport>) -> Self {
+		WebClient { transport }
+	}
+
+	/// Records or replays all the calls of this WebClient (including streams) with the given cassette.
+	pub fn with_cassette(mut self, cassette: Option<Cassette>) -> Self {
+		if let Some(cassette) = cassette {
+			self.transport = Arc::new(CassetteTransport::new(cassette, self.transport));
+		}
+		self
 	}
 }
 
@@ -33,41 +48,46 @@ impl WebClient {
 
 impl WebClient {
 	pub async fn do_get(&self, url: &str, headers: &Headers) -> Result<WebResponse> {
-		let mut reqwest_builder = self.reqwest_client.request(Method::GET, url);
-
-		for (k, v) in headers.iter() {
-			reqwest_builder = reqwest_builder.header(k, v);
-		}
-		let reqwest_res = reqwest_builder.send().await?;
-
-		let response = WebResponse::from_reqwest_response(reqwest_res).await?;
+		let request = TransportRequest::get(url, headers.clone());
+		let transport_res = self.transport.get(request).await?;
 
-		Ok(response)
+		WebResponse::from_transport_response(transport_res)
 	}
 
 	pub async fn do_post(&self, url: &str, headers: &Headers, content: &Value) -> Result<WebResponse> {
-		let reqwest_builder = self.new_req_builder(url, headers, content)?;
+		let request = TransportRequest::post_json(url, headers.clone(), content.clone());
+		let transport_res = self.transport.post_json(request).await?;
 
-		let reqwest_res = reqwest_builder.send().await?;
-
-		let response = WebResponse::from_reqwest_response(reqwest_res).await?;
+		WebResponse::from_transport_response(transport_res)
+	}
 
-		Ok(response)
+	/// Creates the request for a `WebStream` / `EventSourceStream` (sent lazily on the first poll).
+	pub fn new_stream_request(&self, url: &str, headers: &Headers, content: &Value) -> Result<StreamRequest> {
+		Ok(StreamRequest {
+			transport: self.transport.clone(),
+			request: TransportRequest::post_json(url, headers.clone(), content.clone()),
+		})
 	}
+}
+// endregion: --- Web Method Implementation
 
-	pub fn new_req_builder(&self, url: &str, headers: &Headers, content: &Value) -> Result<RequestBuilder> {
-		let method = Method::POST;
+// region:    --- StreamRequest
 
-		let mut reqwest_builder = self.reqwest_client.request(method, url);
-		for (k, v) in headers.iter() {
-			reqwest_builder = reqwest_builder.header(k, v);
-		}
-		reqwest_builder = reqwest_builder.json(content);
+/// A not-yet-sent streaming request, consumed by `WebStream` / `EventSourceStream`.
+#[derive(Clone)]
+pub struct StreamRequest {
+	transport: Arc<dyn Transport>,
+	request: TransportRequest,
+}
 
-		Ok(reqwest_builder)
+impl StreamRequest {
+	/// Sends the request through the transport.
+	pub(super) async fn send(self) -> Result<TransportStreamResponse> {
+		self.transport.post_stream(self.request).await
 	}
 }
-// endregion: --- Web Method Implementation
+
+// endregion: --- StreamRequest
 
 // region:    --- WebResponse
 
@@ -86,12 +106,11 @@ impl WebResponse {
 	/// Note 2: Currently, the WebResponse holds a Value (parsed from the entire body), and then the caller
 	///         can cherry-pick/deserialize further. In the future, we might consider returning `body: String`
 	///         to enable more optimized parsing, allowing for selective parsing constrained by the structure.
-	pub(crate) async fn from_reqwest_response(mut res: reqwest::Response) -> Result<WebResponse> {
-		let status = res.status();
+	pub(crate) fn from_transport_response(res: TransportResponse) -> Result<WebResponse> {
+		let TransportResponse { status, headers, body } = res;
+		let body = String::from_utf8_lossy(&body).into_owned();
 
 		if !status.is_success() {
-			let headers = res.headers().clone();
-			let body = res.text().await?;
 			tracing::trace!("AI Response failed. Body:\n{body}");
 			return Err(Error::ResponseFailedStatus {
 				status,
@@ -100,13 +119,8 @@ impl WebResponse {
 			});
 		}
 
-		// Move the headers into a new HeaderMap
-		let headers = res.headers_mut().drain().filter_map(|(n, v)| n.map(|n| (n, v)));
-		let header_map = HeaderMap::from_iter(headers);
-
 		// Capture the body
-		let ct = header_map.get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default();
-		let body = res.text().await?;
+		let ct = headers.get("content-type").and_then(|v| v.to_str().ok()).unwrap_or_default();
 
 		let body = if ct.starts_with("application/json") {
 			tracing::trace!("AI Response body:\n{body}");
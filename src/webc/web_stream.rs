@@ -1,12 +1,11 @@
-use bytes::Bytes;
-use futures::stream::TryStreamExt;
 use futures::{Future, Stream};
-use reqwest::{RequestBuilder, Response};
 use std::collections::VecDeque;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use crate::RetryPolicy;
 use crate::error::{BoxError, Error as GenaiError};
+use crate::webc::{Error as WebcError, StreamRequest, TransportByteStream, TransportStreamResponse};
 
 /// WebStream is a simple web stream implementation that splits the stream messages by a given delimiter.
 /// - It is intended to be a pragmatic solution for services that do not adhere to the `text/event-stream` format and content type.
@@ -17,9 +16,11 @@ use crate::error::{BoxError, Error as GenaiError};
 #[allow(clippy::type_complexity)]
 pub struct WebStream {
 	stream_mode: StreamMode,
-	reqwest_builder: Option<RequestBuilder>,
-	response_future: Option<Pin<Box<dyn Future<Output = Result<Response, BoxError>> + Send>>>,
-	bytes_stream: Option<Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>>>,
+	stream_request: Option<StreamRequest>,
+	// Applied to the initial request only (before any bytes are received)
+	retry_policy: Option<RetryPolicy>,
+	response_future: Option<Pin<Box<dyn Future<Output = Result<TransportStreamResponse, BoxError>> + Send>>>,
+	bytes_stream: Option<TransportByteStream>,
 	// If a poll was a partial message, then we keep the previous part
 	partial_message: Option<String>,
 	// If a poll retrieved multiple messages, we keep them to be sent in the next poll
@@ -34,10 +35,11 @@ pub enum StreamMode {
 }
 
 impl WebStream {
-	pub fn new_with_delimiter(reqwest_builder: RequestBuilder, message_delimiter: &'static str) -> Self {
+	pub fn new_with_delimiter(stream_request: StreamRequest, message_delimiter: &'static str) -> Self {
 		Self {
 			stream_mode: StreamMode::Delimiter(message_delimiter),
-			reqwest_builder: Some(reqwest_builder),
+			stream_request: Some(stream_request),
+			retry_policy: None,
 			response_future: None,
 			bytes_stream: None,
 			partial_message: None,
@@ -45,16 +47,23 @@ impl WebStream {
 		}
 	}
 
-	pub fn new_with_pretty_json_array(reqwest_builder: RequestBuilder) -> Self {
+	pub fn new_with_pretty_json_array(stream_request: StreamRequest) -> Self {
 		Self {
 			stream_mode: StreamMode::PrettyJsonArray,
-			reqwest_builder: Some(reqwest_builder),
+			stream_request: Some(stream_request),
+			retry_policy: None,
 			response_future: None,
 			bytes_stream: None,
 			partial_message: None,
 			remaining_messages: None,
 		}
 	}
+
+	/// Sets the retry policy for the initial request.
+	pub fn with_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+		self.retry_policy = retry_policy;
+		self
+	}
 }
 
 impl Stream for WebStream {
@@ -76,17 +85,17 @@ impl Stream for WebStream {
 				match Pin::new(fut).poll(cx) {
 					Poll::Ready(Ok(response)) => {
 						// Check HTTP status before proceeding with the stream
-						let status = response.status();
+						let status = response.status;
 						if !status.is_success() {
 							this.response_future = None;
 							// For error responses, we need to read the body to get the error message
 							// Store a future that reads the body and returns an error
 							let error_future = async move {
-								let body = response
-									.text()
-									.await
-									.unwrap_or_else(|e| format!("Failed to read error body: {}", e));
-								Err::<Response, BoxError>(Box::new(GenaiError::HttpError {
+								let body = match response.collect_body().await {
+									Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+									Err(e) => format!("Failed to read error body: {}", e),
+								};
+								Err::<TransportStreamResponse, BoxError>(Box::new(GenaiError::HttpError {
 									status,
 									canonical_reason: status.canonical_reason().unwrap_or("Unknown").to_string(),
 									body,
@@ -95,8 +104,7 @@ impl Stream for WebStream {
 							this.response_future = Some(Box::pin(error_future));
 							continue;
 						}
-						let bytes_stream = response.bytes_stream().map_err(|e| Box::new(e) as BoxError);
-						this.bytes_stream = Some(Box::pin(bytes_stream));
+						this.bytes_stream = Some(response.body);
 						this.response_future = None;
 					}
 					Poll::Ready(Err(e)) => {
@@ -165,8 +173,17 @@ impl Stream for WebStream {
 				}
 			}
 
-			if let Some(reqwest_builder) = this.reqwest_builder.take() {
-				let fut = async move { reqwest_builder.send().await.map_err(|e| Box::new(e) as BoxError) };
+			if let Some(stream_request) = this.stream_request.take() {
+				let retry_policy = this.retry_policy.take();
+				let fut = async move {
+					send_with_retry(stream_request, retry_policy.as_ref())
+						.await
+						.map_err(|e| match e {
+							// Keep the reqwest error as the source (e.g., for timeout detection)
+							WebcError::Reqwest(e) => Box::new(e) as BoxError,
+							e => Box::new(e) as BoxError,
+						})
+				};
 				this.response_future = Some(Box::pin(fut));
 				continue;
 			}
@@ -176,6 +193,37 @@ impl Stream for WebStream {
 	}
 }
 
+/// Sends the request, retrying retryable statuses and connect errors according to the `retry_policy` (if any).
+///
+/// Note: The last failed response (if any) is returned as is, so that the status check in `poll_next` handles it.
+async fn send_with_retry(
+	stream_request: StreamRequest,
+	retry_policy: Option<&RetryPolicy>,
+) -> crate::webc::Result<TransportStreamResponse> {
+	let Some(retry_policy) = retry_policy else {
+		return stream_request.send().await;
+	};
+
+	let mut attempt: u32 = 1;
+	loop {
+		let has_attempts_left = attempt < retry_policy.max_attempts;
+
+		let delay = match stream_request.clone().send().await {
+			Ok(res) if has_attempts_left && retry_policy.is_retryable_status(res.status) => {
+				retry_policy.delay_for_attempt(attempt, Some(&res.headers))
+			}
+			Err(WebcError::Reqwest(err)) if has_attempts_left && retry_policy.is_retryable_reqwest_error(&err) => {
+				retry_policy.delay_for_attempt(attempt, None)
+			}
+			other => return other,
+		};
+
+		tracing::debug!("genai - retrying stream request in {delay:?} (attempt {attempt} failed)");
+		tokio::time::sleep(delay).await;
+		attempt += 1;
+	}
+}
+
 struct BuffResponse {
 	first_message: Option<String>,
 	next_messages: Option<Vec<String>>,
@@ -1,6 +1,11 @@
 use super::TestResult;
 use bitflags::parser::to_writer;
-use genai::chat::{ChatStream, ChatStreamEvent, StreamEnd};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatMessage, ChatOptions, ChatRequest, ChatStream, ChatStreamEvent, StreamEnd};
+use genai::resolver::{AuthData, Endpoint};
+use genai::webc::InMemoryResponse;
+use genai::{Client, ModelIden, ServiceTarget};
+use serde_json::{Value, json};
 use tokio_stream::StreamExt;
 
 /// A macro to retrieve the value of an `Option` field from a struct, returning an error if the field is `None`.
@@ -112,3 +117,69 @@ pub async fn extract_stream_end(mut chat_stream: ChatStream) -> TestResult<Strea
 }
 
 // endregion: --- Stream Support
+
+// region:    --- Mock Support
+
+/// Base URL of the in-memory targets (for `InMemoryTransport`, or dry-run rendering).
+pub const IN_MEMORY_BASE_URL: &str = "http://in-memory/v1/";
+
+pub fn in_memory_target(adapter_kind: AdapterKind, model_name: &str) -> ServiceTarget {
+	ServiceTarget {
+		endpoint: Endpoint::from_static(IN_MEMORY_BASE_URL),
+		auth: AuthData::from_single("test-key"),
+		model: ModelIden::new(adapter_kind, model_name),
+	}
+}
+
+/// The OpenAI `gpt-4o-mini` in-memory target.
+pub fn in_memory_openai_target() -> ServiceTarget {
+	in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini")
+}
+
+/// Target on the `{base_url}/v1/` endpoint (e.g., a `MockServer` uri).
+pub fn base_url_target(base_url: &str, adapter_kind: AdapterKind, model_name: &str) -> ServiceTarget {
+	ServiceTarget {
+		endpoint: Endpoint::from_owned(format!("{base_url}/v1/")),
+		auth: AuthData::from_single("test-key"),
+		model: ModelIden::new(adapter_kind, model_name),
+	}
+}
+
+pub fn user_chat_req(prompt: &str) -> ChatRequest {
+	ChatRequest::new(vec![ChatMessage::user(prompt)])
+}
+
+/// OpenAI chat completion body (with a 10/5/15 usage).
+pub fn openai_chat_body(message: Value, finish_reason: &str) -> Value {
+	json!({
+		"id": "chatcmpl-1",
+		"object": "chat.completion",
+		"model": "gpt-4o-mini",
+		"choices": [{"index": 0, "message": message, "finish_reason": finish_reason}],
+		"usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+	})
+}
+
+/// OpenAI chat completion body with an assistant text message.
+pub fn openai_text_body(content: &str) -> Value {
+	openai_chat_body(json!({"role": "assistant", "content": content}), "stop")
+}
+
+/// OpenAI chat completion event stream (one `data:` message per event, then `[DONE]`).
+pub fn openai_event_stream(events: Vec<Value>) -> InMemoryResponse {
+	let mut messages: Vec<String> = events.into_iter().map(|event| format!("data: {event}\n\n")).collect();
+	messages.push("data: [DONE]\n\n".to_string());
+	InMemoryResponse::event_stream(messages)
+}
+
+/// Returns the payload of the rendered chat request (no network).
+pub async fn render_chat_payload(
+	target: ServiceTarget,
+	chat_req: ChatRequest,
+	options: Option<&ChatOptions>,
+) -> genai::Result<Value> {
+	let web_req = Client::default().render_chat_request(target, chat_req, options).await?;
+	Ok(web_req.payload)
+}
+
+// endregion: --- Mock Support
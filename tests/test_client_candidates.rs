@@ -0,0 +1,218 @@
+//! Multiple candidates (candidate_count) tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_target, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, StopReason};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use genai::{Client, Error};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+// region:    --- Support
+
+fn anthropic_body(text: &str) -> Value {
+	json!({
+		"id": "msg_1",
+		"type": "message",
+		"role": "assistant",
+		"model": "claude-sonnet-4-5",
+		"content": [{"type": "text", "text": text}],
+		"stop_reason": "end_turn",
+		"usage": {"input_tokens": 5, "output_tokens": 1}
+	})
+}
+
+/// OpenAI compatible chat body, with the logprob of its single token.
+fn openai_logprobs_body(text: &str, logprob: f64) -> Value {
+	json!({
+		"model": "llama-3.1-8b-instant",
+		"choices": [{
+			"index": 0,
+			"message": {"role": "assistant", "content": text},
+			"logprobs": {"content": [{"token": text, "logprob": logprob, "bytes": null, "top_logprobs": []}]},
+			"finish_reason": "stop"
+		}],
+		"usage": {"prompt_tokens": 5, "completion_tokens": 1, "total_tokens": 6}
+	})
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_candidates_openai_n() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::json(json!({
+			"model": "gpt-4o-mini",
+			"choices": [
+				{"index": 0, "message": {"role": "assistant", "content": "Red"}, "finish_reason": "stop"},
+				{"index": 1, "message": {"role": "assistant", "content": "Blue"}, "finish_reason": "length"}
+			],
+			"usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+		})),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let options = ChatOptions::default().with_candidate_count(2);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Pick a color"),
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Red"));
+	assert_eq!(res.candidate_texts(), vec![Some("Red"), Some("Blue")]);
+	assert_eq!(
+		res.candidates[1].stop_reason,
+		Some(StopReason::MaxTokens("length".to_string()))
+	);
+	let body = transport.requests()[0].body.clone().ok_or("should have a body")?;
+	assert_eq!(body["n"], 2);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_candidates_gemini_candidate_count() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}models/gemini-2.0-flash:generateContent"),
+		InMemoryResponse::json(json!({
+			"candidates": [
+				{"content": {"role": "model", "parts": [{"text": "Red"}]}, "finishReason": "STOP", "index": 0},
+				{"content": {"role": "model", "parts": [{"text": "Blue"}]}, "finishReason": "STOP", "index": 1}
+			],
+			"usageMetadata": {"promptTokenCount": 5, "candidatesTokenCount": 2, "totalTokenCount": 7}
+		})),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let options = ChatOptions::default().with_candidate_count(2);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::Gemini, "gemini-2.0-flash"),
+			user_chat_req("Pick a color"),
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Red"));
+	assert_eq!(res.candidate_texts(), vec![Some("Red"), Some("Blue")]);
+	let body = transport.requests()[0].body.clone().ok_or("should have a body")?;
+	assert_eq!(body["generationConfig"]["candidateCount"], 2);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_candidates_single_by_default() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}messages"),
+		InMemoryResponse::json(anthropic_body("Red")),
+	);
+	let client = Client::builder().with_transport(transport).build();
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+			user_chat_req("Pick a color"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.candidate_texts(), vec![Some("Red")]);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_candidates_not_supported_without_fan_out() -> TestResult<()> {
+	let client = Client::builder().with_transport(InMemoryTransport::new()).build();
+	let options = ChatOptions::default().with_candidate_count(2);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+			user_chat_req("Pick a color"),
+			Some(&options),
+		)
+		.await;
+
+	assert!(matches!(res, Err(Error::AdapterNotSupported { .. })));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_candidates_fan_out() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}messages");
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, InMemoryResponse::json(anthropic_body("Red")))
+		.with_response(Method::POST, &url, InMemoryResponse::json(anthropic_body("Blue")))
+		.with_response(Method::POST, &url, InMemoryResponse::json(anthropic_body("Green")));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let options = ChatOptions::default().with_candidate_count(3).with_candidate_fan_out(true);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+			user_chat_req("Pick a color"),
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(transport.requests().len(), 3);
+	let mut texts: Vec<&str> = res.candidate_texts().into_iter().flatten().collect();
+	texts.sort();
+	assert_eq!(texts, vec!["Blue", "Green", "Red"]);
+	assert_eq!(res.first_text(), res.candidates[0].content.first_text());
+	assert_eq!(res.usage.prompt_tokens, Some(15));
+	assert_eq!(res.usage.completion_tokens, Some(3));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_candidates_fan_out_logprobs() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(openai_logprobs_body("Red", -0.5)),
+		)
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(openai_logprobs_body("Blue", -1.5)),
+		);
+	let client = Client::builder().with_transport(transport).build();
+	let options = ChatOptions::default()
+		.with_candidate_count(2)
+		.with_candidate_fan_out(true)
+		.with_logprobs(true);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::Groq, "llama-3.1-8b-instant"),
+			user_chat_req("Pick a color"),
+			Some(&options),
+		)
+		.await?;
+
+	// -- Each candidate keeps the logprobs of its own call
+	assert_eq!(res.candidates.len(), 2);
+	for candidate in &res.candidates {
+		let logprobs = candidate.logprobs.as_deref().ok_or("candidate should have logprobs")?;
+		assert_eq!(Some(logprobs[0].token.as_str()), candidate.content.first_text());
+	}
+	assert_eq!(res.logprobs, res.candidates[0].logprobs);
+
+	Ok(())
+}
@@ -0,0 +1,148 @@
+//! Record/replay cassette tests (record against a local wiremock server, replay offline).
+
+mod support;
+
+use crate::support::{TestResult, base_url_target};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatMessage, ChatRequest, ChatStreamEvent};
+use genai::webc::{self, Cassette, CassetteMode};
+use genai::{Client, Error, ServiceTarget};
+use std::path::PathBuf;
+use tokio_stream::StreamExt;
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// region:    --- Support
+
+fn cassette_path(name: &str) -> PathBuf {
+	std::env::temp_dir().join(format!("genai-cassette-{name}-{}.json", uuid::Uuid::new_v4()))
+}
+
+async fn collect_stream_text(client: &Client, target: ServiceTarget) -> TestResult<String> {
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Hello stream")]);
+	let mut stream = client.exec_chat_stream(target, chat_req, None).await?.stream;
+
+	let mut content = String::new();
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::Chunk(chunk) = event? {
+			content.push_str(&chunk.content);
+		}
+	}
+	Ok(content)
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_cassette_record_then_replay_chat_and_stream() -> TestResult<()> {
+	let cassette_path = cassette_path("chat");
+	let mock_server = MockServer::start().await;
+	let base_url = mock_server.uri();
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.and(body_partial_json(serde_json::json!({"stream": false})))
+		.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+			"id": "chatcmpl-1",
+			"object": "chat.completion",
+			"model": "gpt-4o-mini",
+			"choices": [{
+				"index": 0,
+				"message": {"role": "assistant", "content": "Hello recorded"},
+				"finish_reason": "stop"
+			}],
+			"usage": {"prompt_tokens": 5, "completion_tokens": 2, "total_tokens": 7}
+		})))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let sse_body = concat!(
+		"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+		"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" replay\"},\"finish_reason\":\"stop\"}]}\n\n",
+		"data: [DONE]\n\n"
+	);
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.and(body_partial_json(serde_json::json!({"stream": true})))
+		.respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	// -- Record
+	let cassette = Cassette::record(&cassette_path);
+	assert_eq!(cassette.mode(), CassetteMode::Record);
+	let client = Client::builder().with_cassette(cassette).build();
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Hello")]);
+	let res = client
+		.exec_chat(
+			base_url_target(&base_url, AdapterKind::OpenAI, "gpt-4o-mini"),
+			chat_req,
+			None,
+		)
+		.await?;
+	assert_eq!(res.first_text(), Some("Hello recorded"));
+	let content = collect_stream_text(&client, base_url_target(&base_url, AdapterKind::OpenAI, "gpt-4o-mini")).await?;
+	assert_eq!(content, "Hello replay");
+
+	// -- Replay (server is gone)
+	drop(mock_server);
+	let cassette_content = std::fs::read_to_string(&cassette_path)?;
+	assert!(
+		!cassette_content.contains("test-key"),
+		"cassette should not record auth"
+	);
+
+	let client = Client::builder()
+		.with_cassette(Cassette::replay(&cassette_path).map_err(|err| err.to_string())?)
+		.build();
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Hello")]);
+	let res = client
+		.exec_chat(
+			base_url_target(&base_url, AdapterKind::OpenAI, "gpt-4o-mini"),
+			chat_req,
+			None,
+		)
+		.await?;
+	assert_eq!(res.first_text(), Some("Hello recorded"));
+	assert_eq!(res.usage.prompt_tokens, Some(5));
+	let content = collect_stream_text(&client, base_url_target(&base_url, AdapterKind::OpenAI, "gpt-4o-mini")).await?;
+	assert_eq!(content, "Hello replay");
+
+	std::fs::remove_file(&cassette_path)?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_cassette_replay_no_matching_interaction() -> TestResult<()> {
+	let cassette_path = cassette_path("miss");
+	std::fs::write(&cassette_path, r#"{"interactions": []}"#)?;
+
+	let client = Client::builder()
+		.with_cassette(Cassette::replay(&cassette_path).map_err(|err| err.to_string())?)
+		.build();
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Hello")]);
+	let res = client
+		.exec_chat(
+			base_url_target("http://localhost:1", AdapterKind::OpenAI, "gpt-4o-mini"),
+			chat_req,
+			None,
+		)
+		.await;
+
+	let Err(Error::WebModelCall {
+		webc_error: webc::Error::CassetteNoInteraction { method, url, .. },
+		..
+	}) = res
+	else {
+		return Err(format!("Should have failed with CassetteNoInteraction, got: {res:?}").into());
+	};
+	assert_eq!(method, "POST");
+	assert_eq!(url, "http://localhost:1/v1/chat/completions");
+
+	std::fs::remove_file(&cassette_path)?;
+
+	Ok(())
+}
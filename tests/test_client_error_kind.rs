@@ -0,0 +1,91 @@
+//! Normalized provider error (ErrorKind) tests against a local wiremock server.
+
+mod support;
+
+use crate::support::{TestResult, base_url_target, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::{Client, ErrorKind};
+use tokio_stream::StreamExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_error_kind_openai_context_length_exceeded() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+			"error": {
+				"message": "This model's maximum context length is 128000 tokens.",
+				"type": "invalid_request_error",
+				"param": "messages",
+				"code": "context_length_exceeded"
+			}
+		})))
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::default();
+	let err = client
+		.exec_chat(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await
+		.err()
+		.ok_or("Should have failed")?;
+
+	assert_eq!(err.kind(), ErrorKind::ContextLengthExceeded);
+	let provider_error = err.provider_error().ok_or("Should have a provider error")?;
+	assert_eq!(provider_error.status, Some(400));
+	assert_eq!(provider_error.code.as_deref(), Some("context_length_exceeded"));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_error_kind_anthropic_stream_error_event() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	let sse_body = concat!(
+		"event: message_start\n",
+		"data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"usage\":{\"input_tokens\":5,\"output_tokens\":0}}}\n\n",
+		"event: error\n",
+		"data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+	);
+
+	Mock::given(method("POST"))
+		.and(path("/v1/messages"))
+		.respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::default();
+	let mut stream = client
+		.exec_chat_stream(
+			base_url_target(&mock_server.uri(), AdapterKind::Anthropic, "claude-sonnet-4-5"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?
+		.stream;
+
+	let mut stream_err = None;
+	while let Some(event) = stream.next().await {
+		if let Err(err) = event {
+			stream_err = Some(err);
+			break;
+		}
+	}
+	let err = stream_err.ok_or("Stream should have failed")?;
+
+	assert_eq!(err.kind(), ErrorKind::Overloaded);
+	assert!(err.kind().is_transient());
+	let provider_error = err.provider_error().ok_or("Should have a provider error")?;
+	assert_eq!(provider_error.code.as_deref(), Some("overloaded_error"));
+	assert_eq!(provider_error.message.as_deref(), Some("Overloaded"));
+
+	Ok(())
+}
@@ -0,0 +1,125 @@
+//! Extra body passthrough tests (dry-run request rendering, no network).
+
+mod support;
+
+use crate::support::{TestResult, in_memory_target, render_chat_payload, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::chat::ChatOptions;
+use genai::embed::{EmbedOptions, EmbedRequest};
+use genai::{Client, Error};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_extra_body_chat_merge_and_remove() -> TestResult<()> {
+	let options = ChatOptions::default().with_temperature(0.5).with_extra_body(json!({
+		"metadata": {"user": "u-1"},
+		"store": true,
+		"temperature": null
+	}));
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+		user_chat_req("Hello"),
+		Some(&options),
+	)
+	.await?;
+
+	assert_eq!(payload["metadata"], json!({"user": "u-1"}));
+	assert_eq!(payload["store"], true);
+	assert!(payload.get("temperature").is_none());
+	assert_eq!(payload["model"], "gpt-4o-mini");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_extra_body_chat_deep_merge_per_adapter() -> TestResult<()> {
+	let safety_settings = json!([{"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_NONE"}]);
+	let options = ChatOptions::default()
+		.with_temperature(0.5)
+		.with_extra_body(json!({"generationConfig": {"seed": 3}}))
+		.with_adapter_extra_body(AdapterKind::Gemini, json!({"safetySettings": safety_settings.clone()}));
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Gemini, "gemini-2.0-flash"),
+		user_chat_req("Hello"),
+		Some(&options),
+	)
+	.await?;
+	assert_eq!(payload["generationConfig"]["temperature"], 0.5);
+	assert_eq!(payload["generationConfig"]["seed"], 3);
+	assert_eq!(payload["safetySettings"], safety_settings);
+
+	// The Gemini scoped extra body is not applied to the other adapters.
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+		user_chat_req("Hello"),
+		Some(&options),
+	)
+	.await?;
+	assert!(payload.get("safetySettings").is_none());
+	assert_eq!(payload["generationConfig"]["seed"], 3);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_extra_body_chat_non_object_root() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini");
+
+	// A non-object value replaces a nested value.
+	let options = ChatOptions::default().with_extra_body(json!({"metadata": {"user": "u-1"}, "stop": "END"}));
+	let payload = render_chat_payload(target(), user_chat_req("Hello"), Some(&options)).await?;
+	assert_eq!(payload["stop"], "END");
+
+	// But it cannot replace the whole payload.
+	let options = ChatOptions::default().with_extra_body(json!(["not", "an", "object"]));
+	let res = render_chat_payload(target(), user_chat_req("Hello"), Some(&options)).await;
+	assert!(matches!(res, Err(Error::ExtraBodyNotObject { .. })));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_extra_body_client_default() -> TestResult<()> {
+	let client = Client::builder()
+		.with_chat_options(
+			ChatOptions::default()
+				.with_adapter_extra_body(AdapterKind::OpenRouter, json!({"provider": {"sort": "price"}})),
+		)
+		.build();
+
+	let payload = client
+		.render_chat_request(
+			in_memory_target(AdapterKind::OpenRouter, "openai/gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?
+		.payload;
+
+	assert_eq!(payload["provider"], json!({"sort": "price"}));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_extra_body_embed() -> TestResult<()> {
+	let options = EmbedOptions::new()
+		.with_extra_body(json!({"user": "u-1"}))
+		.with_adapter_extra_body(AdapterKind::OpenAI, json!({"encoding_format": "base64"}));
+
+	let data = Client::default()
+		.render_embed_request(
+			in_memory_target(AdapterKind::OpenAI, "text-embedding-3-small"),
+			EmbedRequest::new("Hello"),
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(data.payload["user"], "u-1");
+	assert_eq!(data.payload["encoding_format"], "base64");
+	assert_eq!(data.payload["input"], "Hello");
+
+	Ok(())
+}
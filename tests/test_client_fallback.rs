@@ -0,0 +1,128 @@
+//! Client model fallback chain tests against a local wiremock server.
+
+mod support;
+
+use crate::support::{TestResult, base_url_target, openai_text_body, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::{Client, Error, ModelSpec};
+use serde_json::{Value, json};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// region:    --- Support
+
+/// Chat body of the model, with the "Hello from <model_name>" content.
+fn model_body(model_name: &str) -> Value {
+	let mut body = openai_text_body(&format!("Hello from {model_name}"));
+	body["model"] = json!(model_name);
+	body
+}
+
+async fn mount_model(mock_server: &MockServer, model_name: &str, template: ResponseTemplate, expect: u64) {
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.and(body_partial_json(serde_json::json!({"model": model_name})))
+		.respond_with(template)
+		.expect(expect)
+		.mount(mock_server)
+		.await;
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_fallback_on_server_error() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+	mount_model(&mock_server, "primary", ResponseTemplate::new(503), 1).await;
+	mount_model(&mock_server, "secondary", ResponseTemplate::new(429), 1).await;
+	let body = model_body("tertiary");
+	mount_model(
+		&mock_server,
+		"tertiary",
+		ResponseTemplate::new(200).set_body_json(body),
+		1,
+	)
+	.await;
+
+	let client = Client::default();
+	let models = [
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "primary")),
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "secondary")),
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "tertiary")),
+	];
+	let res = client.exec_chat_with_fallbacks(models, user_chat_req("Hello"), None).await?;
+
+	assert_eq!(res.model_iden.model_name.to_string(), "tertiary");
+	assert_eq!(res.first_text(), Some("Hello from tertiary"));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_fallback_on_rate_limit_with_context_length_message() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+	let error_body = json!({"error": {"message": "Rate limit reached: too many tokens per minute", "type": "tokens"}});
+	mount_model(
+		&mock_server,
+		"primary",
+		ResponseTemplate::new(429).set_body_json(error_body),
+		1,
+	)
+	.await;
+	let body = model_body("secondary");
+	mount_model(
+		&mock_server,
+		"secondary",
+		ResponseTemplate::new(200).set_body_json(body),
+		1,
+	)
+	.await;
+
+	let client = Client::default();
+	let models = [
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "primary")),
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "secondary")),
+	];
+	let res = client.exec_chat_with_fallbacks(models, user_chat_req("Hello"), None).await?;
+
+	assert_eq!(res.model_iden.model_name.to_string(), "secondary");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_fallback_stops_on_non_fallback_error() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+	mount_model(&mock_server, "primary", ResponseTemplate::new(400), 1).await;
+	let body = model_body("secondary");
+	mount_model(
+		&mock_server,
+		"secondary",
+		ResponseTemplate::new(200).set_body_json(body),
+		0,
+	)
+	.await;
+
+	let client = Client::default();
+	let models = [
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "primary")),
+		ModelSpec::from(base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "secondary")),
+	];
+	let res = client.exec_chat_with_fallbacks(models, user_chat_req("Hello"), None).await;
+
+	assert!(matches!(res, Err(Error::WebModelCall { .. })));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_fallback_empty_models() -> TestResult<()> {
+	let client = Client::default();
+	let res = client
+		.exec_chat_with_fallbacks(Vec::<ModelSpec>::new(), user_chat_req("Hello"), None)
+		.await;
+
+	assert!(matches!(res, Err(Error::FallbackModelsEmpty)));
+
+	Ok(())
+}
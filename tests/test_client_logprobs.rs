@@ -0,0 +1,178 @@
+//! Token logprobs tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_target, user_chat_req};
+use genai::Client;
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, ChatStreamEvent, TokenLogprob};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use reqwest::Method;
+use serde_json::json;
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+fn assert_hi_logprobs(logprobs: &[TokenLogprob]) {
+	assert_eq!(logprobs.len(), 1);
+	let logprob = &logprobs[0];
+	assert_eq!(logprob.token, "Hi");
+	assert_eq!(logprob.logprob, -0.25);
+	assert_eq!(logprob.top.len(), 2);
+	assert_eq!(logprob.top[1].token, "Hello");
+	assert_eq!(logprob.top[1].logprob, -1.5);
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_logprobs_openai_chat() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::json(json!({
+			"model": "gpt-4o-mini",
+			"choices": [{
+				"index": 0,
+				"message": {"role": "assistant", "content": "Hi"},
+				"logprobs": {"content": [{
+					"token": "Hi", "logprob": -0.25, "bytes": [72, 105],
+					"top_logprobs": [
+						{"token": "Hi", "logprob": -0.25, "bytes": [72, 105]},
+						{"token": "Hello", "logprob": -1.5, "bytes": null}
+					]
+				}]},
+				"finish_reason": "stop"
+			}],
+			"usage": {"prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4}
+		})),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let options = ChatOptions::default().with_top_logprobs(2);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Say hi"),
+			Some(&options),
+		)
+		.await?;
+
+	let logprobs = res.logprobs.as_deref().ok_or("should have logprobs")?;
+	assert_hi_logprobs(logprobs);
+	assert_eq!(logprobs[0].bytes.as_deref(), Some(&b"Hi"[..]));
+	let body = transport.requests()[0].body.clone().ok_or("should have a body")?;
+	assert_eq!(body["logprobs"], true);
+	assert_eq!(body["top_logprobs"], 2);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_logprobs_openai_chat_stream() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::event_stream([
+			"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hi\"},\"logprobs\":{\"content\":[{\"token\":\"Hi\",\"logprob\":-0.25,\"bytes\":null,\"top_logprobs\":[{\"token\":\"Hi\",\"logprob\":-0.25,\"bytes\":null},{\"token\":\"Hello\",\"logprob\":-1.5,\"bytes\":null}]}]},\"finish_reason\":null}]}\n\n",
+			"data: {\"choices\":[{\"index\":0,\"delta\":{},\"finish_reason\":\"stop\"}]}\n\n",
+			"data: [DONE]\n\n",
+		]),
+	);
+	let client = Client::builder().with_transport(transport).build();
+	let options = ChatOptions::default().with_top_logprobs(2);
+
+	let mut stream = client
+		.exec_chat_stream(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Say hi"),
+			Some(&options),
+		)
+		.await?
+		.stream;
+	let mut chunk_logprobs = Vec::new();
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::Chunk(chunk) = event? {
+			chunk_logprobs.extend(chunk.logprobs.unwrap_or_default());
+		}
+	}
+
+	assert_hi_logprobs(&chunk_logprobs);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_logprobs_gemini_chat() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}models/gemini-2.0-flash:generateContent"),
+		InMemoryResponse::json(json!({
+			"candidates": [{
+				"content": {"role": "model", "parts": [{"text": "Hi"}]},
+				"finishReason": "STOP",
+				"logprobsResult": {
+					"chosenCandidates": [{"token": "Hi", "logProbability": -0.25}],
+					"topCandidates": [{"candidates": [
+						{"token": "Hi", "logProbability": -0.25},
+						{"token": "Hello", "logProbability": -1.5}
+					]}]
+				}
+			}],
+			"usageMetadata": {"promptTokenCount": 3, "candidatesTokenCount": 1, "totalTokenCount": 4}
+		})),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let options = ChatOptions::default().with_top_logprobs(2);
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::Gemini, "gemini-2.0-flash"),
+			user_chat_req("Say hi"),
+			Some(&options),
+		)
+		.await?;
+
+	assert_hi_logprobs(res.logprobs.as_deref().ok_or("should have logprobs")?);
+	let body = transport.requests()[0].body.clone().ok_or("should have a body")?;
+	assert_eq!(body["generationConfig"]["responseLogprobs"], true);
+	assert_eq!(body["generationConfig"]["logprobs"], 2);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_logprobs_not_requested_by_default() -> TestResult<()> {
+	let options = ChatOptions::default();
+
+	let data = Client::default()
+		.render_chat_request(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Say hi"),
+			Some(&options),
+		)
+		.await?;
+
+	assert!(data.payload.get("logprobs").is_none());
+	assert!(data.payload.get("top_logprobs").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_logprobs_openai_resp_request() -> TestResult<()> {
+	let options = ChatOptions::default().with_logprobs(true).with_top_logprobs(3);
+
+	let data = Client::default()
+		.render_chat_request(
+			in_memory_target(AdapterKind::OpenAIResp, "gpt-5-mini"),
+			user_chat_req("Say hi"),
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(data.payload["include"], json!(["message.output_text.logprobs"]));
+	assert_eq!(data.payload["top_logprobs"], 3);
+
+	Ok(())
+}
@@ -0,0 +1,217 @@
+//! Client middleware tests (ClientMiddleware) against a local wiremock server.
+
+mod support;
+
+use crate::support::{TestResult, base_url_target, user_chat_req};
+use genai::adapter::{AdapterKind, ServiceType, WebRequestData};
+use genai::chat::ChatStreamEvent;
+use genai::embed::EmbedRequest;
+use genai::webc::WebResponse;
+use genai::{BoxError, Client, ClientMiddleware, Error, ModelIden, ServiceTarget};
+use std::sync::{Arc, Mutex};
+use tokio_stream::StreamExt;
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// region:    --- Support
+
+/// Injects an org header, patches the payload, and records what it sees.
+#[derive(Default)]
+struct RecordingMiddleware {
+	seen: Arc<Mutex<Vec<String>>>,
+}
+
+impl ClientMiddleware for RecordingMiddleware {
+	fn on_request(
+		&self,
+		target: &ServiceTarget,
+		service_type: ServiceType,
+		request: &mut WebRequestData,
+	) -> Result<(), BoxError> {
+		request.headers.merge(("x-org-id", "org-42"));
+		request.payload["user"] = "middleware-user".into();
+		self.seen
+			.lock()
+			.unwrap()
+			.push(format!("request {service_type:?} {}", target.model.model_name));
+		Ok(())
+	}
+
+	fn on_response(
+		&self,
+		_model_iden: &ModelIden,
+		service_type: ServiceType,
+		response: &mut WebResponse,
+	) -> Result<(), BoxError> {
+		self.seen
+			.lock()
+			.unwrap()
+			.push(format!("response {service_type:?} {}", response.status.as_u16()));
+		Ok(())
+	}
+
+	fn on_stream_event(&self, _model_iden: &ModelIden, event: &mut ChatStreamEvent) -> Result<(), BoxError> {
+		if let ChatStreamEvent::Chunk(chunk) = event {
+			chunk.content = chunk.content.to_uppercase();
+		}
+		Ok(())
+	}
+}
+
+struct DenyMiddleware;
+
+impl ClientMiddleware for DenyMiddleware {
+	fn on_request(&self, _: &ServiceTarget, _: ServiceType, _: &mut WebRequestData) -> Result<(), BoxError> {
+		Err("request denied by policy".into())
+	}
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_middleware_chat_request_and_response() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.and(header("x-org-id", "org-42"))
+		.and(body_partial_json(serde_json::json!({"user": "middleware-user"})))
+		.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+			"id": "chatcmpl-1",
+			"object": "chat.completion",
+			"model": "gpt-4o-mini",
+			"choices": [{
+				"index": 0,
+				"message": {"role": "assistant", "content": "Hello"},
+				"finish_reason": "stop"
+			}],
+			"usage": {"prompt_tokens": 5, "completion_tokens": 1, "total_tokens": 6}
+		})))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let middleware = RecordingMiddleware::default();
+	let seen = middleware.seen.clone();
+	let client = Client::builder().with_middleware(middleware).build();
+
+	let res = client
+		.exec_chat(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Hello"));
+	assert_eq!(
+		*seen.lock().unwrap(),
+		vec!["request Chat gpt-4o-mini".to_string(), "response Chat 200".to_string()]
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_middleware_chat_stream_events() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	let sse_body = concat!(
+		"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+		"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" stream\"},\"finish_reason\":\"stop\"}]}\n\n",
+		"data: [DONE]\n\n"
+	);
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.and(header("x-org-id", "org-42"))
+		.respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::builder().with_middleware(RecordingMiddleware::default()).build();
+	let mut stream = client
+		.exec_chat_stream(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?
+		.stream;
+
+	let mut content = String::new();
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::Chunk(chunk) = event? {
+			content.push_str(&chunk.content);
+		}
+	}
+	assert_eq!(content, "HELLO STREAM");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_middleware_embed_request() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/embeddings"))
+		.and(header("x-org-id", "org-42"))
+		.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+			"object": "list",
+			"data": [{"object": "embedding", "index": 0, "embedding": [0.1, 0.2]}],
+			"model": "text-embedding-3-small",
+			"usage": {"prompt_tokens": 2, "total_tokens": 2}
+		})))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let middleware = RecordingMiddleware::default();
+	let seen = middleware.seen.clone();
+	let client = Client::builder().with_middleware(middleware).build();
+
+	let res = client
+		.exec_embed(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			EmbedRequest::new("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.embeddings.len(), 1);
+	assert_eq!(
+		*seen.lock().unwrap(),
+		vec!["request Embed gpt-4o-mini".to_string(), "response Embed 200".to_string()]
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_middleware_error_aborts_call() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(200))
+		.expect(0)
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::builder().with_middleware(DenyMiddleware).build();
+	let res = client
+		.exec_chat(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await;
+
+	let Err(Error::Middleware { cause, .. }) = res else {
+		return Err("Should have failed with Error::Middleware".into());
+	};
+	assert_eq!(cause, "request denied by policy");
+
+	Ok(())
+}
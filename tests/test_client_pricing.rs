@@ -0,0 +1,140 @@
+//! Pricing / cost tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{
+	IN_MEMORY_BASE_URL, TestResult, in_memory_target, openai_event_stream, openai_text_body, user_chat_req,
+};
+use genai::Client;
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, Pricing, PricingRegistry, SpendTracker};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use reqwest::Method;
+use serde_json::{Value, json};
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+/// Chat body with a detailed usage (cached and reasoning tokens).
+fn detailed_usage_body(model_name: &str) -> Value {
+	let mut body = openai_text_body("Hello");
+	body["model"] = json!(model_name);
+	body["usage"] = json!({
+			"prompt_tokens": 1000,
+			"prompt_tokens_details": {"cached_tokens": 400},
+			"completion_tokens": 200,
+			"completion_tokens_details": {"reasoning_tokens": 50},
+		"total_tokens": 1200
+	});
+	body
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_pricing_chat_response_cost() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(detailed_usage_body("gpt-4o-mini")),
+		)
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(detailed_usage_body("o4-mini")),
+		);
+	let registry = PricingRegistry::from_json(
+		r#"{ "openai": { "gpt-4o-mini": { "input": 0.15, "output": 0.6, "cached_input": 0.075 } } }"#,
+	)?;
+	let client = Client::builder()
+		.with_transport(transport)
+		.with_pricing_registry(registry)
+		.build();
+
+	// -- Known model: 600 input, 400 cached input, 200 output (including reasoning)
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	assert_eq!(res.pricing, Some(Pricing::new(0.15, 0.6).with_cached_input(0.075)));
+	let expected = (600. * 0.15 + 400. * 0.075 + 200. * 0.6) / 1_000_000.;
+	let cost = res.cost().ok_or("Should have a cost")?;
+	assert!((cost - expected).abs() < 1e-12);
+
+	// -- Unknown model
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "o4-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	assert_eq!(res.pricing, None);
+	assert_eq!(res.cost(), None);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_pricing_spend_tracker_session() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(detailed_usage_body("gpt-4o-mini")),
+		)
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(detailed_usage_body("o4-mini")),
+		)
+		.with_response(
+			Method::POST,
+			&url,
+			openai_event_stream(vec![
+				json!({"choices": [{"index": 0, "delta": {"content": "Hello"}, "finish_reason": "stop"}]}),
+				json!({"choices": [], "usage": {"prompt_tokens": 1000, "completion_tokens": 100, "total_tokens": 1100}}),
+			]),
+		);
+	let pricing = Pricing::new(0.15, 0.6).with_cached_input(0.075);
+	let gpt_target = || in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini");
+	let spend_tracker = SpendTracker::new();
+	let client = Client::builder()
+		.with_transport(transport)
+		.with_pricing_registry(PricingRegistry::new().with_pricing(gpt_target().model, pricing))
+		.with_spend_tracker(spend_tracker.clone())
+		.build();
+
+	// -- Exec: a priced call, an unpriced call, and a priced stream
+	let res = client.exec_chat(gpt_target(), user_chat_req("Hello"), None).await?;
+	let chat_cost = res.cost().ok_or("Should have a cost")?;
+	client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "o4-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	let options = ChatOptions::default().with_capture_usage(true);
+	let mut stream = client
+		.exec_chat_stream(gpt_target(), user_chat_req("Hello"), Some(&options))
+		.await?
+		.stream;
+	while let Some(event) = stream.next().await {
+		event?;
+	}
+
+	// -- Check
+	let stream_cost = (1000. * 0.15 + 100. * 0.6) / 1_000_000.;
+	assert_eq!(spend_tracker.call_count(), 2);
+	assert!((spend_tracker.total() - (chat_cost + stream_cost)).abs() < 1e-12);
+	assert!((spend_tracker.model_total(&gpt_target().model) - spend_tracker.total()).abs() < 1e-12);
+
+	Ok(())
+}
@@ -0,0 +1,118 @@
+//! Client-side rate limiter tests (InMemoryTransport, paused time, no network).
+
+mod support;
+
+use crate::support::{
+	IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_event_stream, openai_text_body, user_chat_req,
+};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, ChatStreamEvent};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use genai::{Client, ModelIden, ModelName, RateLimit, RateLimiter};
+use reqwest::Method;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+// endregion: --- Support
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limiter_requests_per_minute_waits() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Hello")))
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Hello")));
+	let rate_limiter =
+		RateLimiter::new().with_adapter_limit(AdapterKind::OpenAI, RateLimit::new().with_requests_per_minute(1));
+	let client = Client::builder()
+		.with_transport(transport.clone())
+		.with_rate_limiter(rate_limiter)
+		.build();
+	let start = Instant::now();
+
+	client
+		.exec_chat(in_memory_openai_target(), user_chat_req("Hello"), None)
+		.await?;
+	assert!(start.elapsed() < Duration::from_secs(1));
+
+	client
+		.exec_chat(in_memory_openai_target(), user_chat_req("Hello"), None)
+		.await?;
+	assert!(start.elapsed() >= Duration::from_secs(60));
+	assert_eq!(transport.requests().len(), 2);
+
+	Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limiter_model_limit_static_iden() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Hello")))
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Hello")));
+	let static_iden = ModelIden::new(AdapterKind::OpenAI, ModelName::from_static("gpt-4o-mini"));
+	let rate_limiter = RateLimiter::new().with_model_limit(static_iden, RateLimit::new().with_requests_per_minute(1));
+	let client = Client::builder()
+		.with_transport(transport)
+		.with_rate_limiter(rate_limiter)
+		.build();
+	let start = Instant::now();
+
+	// The resolved target model name is shared, and still matches the static one.
+	client
+		.exec_chat(in_memory_openai_target(), user_chat_req("Hello"), None)
+		.await?;
+	client
+		.exec_chat(in_memory_openai_target(), user_chat_req("Hello"), None)
+		.await?;
+	assert!(start.elapsed() >= Duration::from_secs(60));
+
+	Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_rate_limiter_tokens_per_minute_from_stream_usage() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			openai_event_stream(vec![
+				json!({"choices": [{"index": 0, "delta": {"content": "Hello"}, "finish_reason": "stop"}]}),
+				json!({"choices": [], "usage": {"prompt_tokens": 50, "completion_tokens": 50, "total_tokens": 100}}),
+			]),
+		)
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Hello")));
+	let rate_limiter = RateLimiter::new().with_model_limit(
+		in_memory_openai_target().model,
+		RateLimit::new().with_tokens_per_minute(100),
+	);
+	let client = Client::builder()
+		.with_transport(transport)
+		.with_rate_limiter(rate_limiter)
+		.build();
+	let options = ChatOptions::default().with_capture_usage(true);
+	let start = Instant::now();
+
+	let mut stream = client
+		.exec_chat_stream(in_memory_openai_target(), user_chat_req("Hello"), Some(&options))
+		.await?
+		.stream;
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::End(end) = event? {
+			assert_eq!(end.captured_usage.and_then(|usage| usage.total_tokens), Some(100));
+		}
+	}
+	assert!(start.elapsed() < Duration::from_secs(1));
+
+	// The 100 tokens budget is used, so the next call waits for the window.
+	client
+		.exec_chat(in_memory_openai_target(), user_chat_req("Hello"), None)
+		.await?;
+	assert!(start.elapsed() >= Duration::from_secs(60));
+
+	Ok(())
+}
@@ -0,0 +1,175 @@
+//! Dry-run request rendering tests (no network).
+
+mod support;
+
+use crate::support::TestResult;
+use genai::adapter::{AdapterKind, WebRequestData};
+use genai::chat::{CacheControl, ChatMessage, ChatOptions, ChatRequest, ContentPart, MessageContent, Tool};
+use genai::embed::EmbedRequest;
+use genai::resolver::{AuthData, Endpoint};
+use genai::{Client, ModelIden, ServiceTarget};
+use std::collections::HashMap;
+
+// region:    --- Support
+
+const SECRET: &str = "sk-test-secret-123";
+
+fn target(adapter_kind: AdapterKind, base_url: &'static str, model_name: &'static str) -> ServiceTarget {
+	ServiceTarget {
+		endpoint: Endpoint::from_static(base_url),
+		auth: AuthData::from_single(SECRET),
+		model: ModelIden::new(adapter_kind, model_name),
+	}
+}
+
+fn headers_map(web_req: &WebRequestData) -> HashMap<String, String> {
+	web_req.headers.iter().map(|(k, v)| (k.to_lowercase(), v.clone())).collect()
+}
+
+fn assert_no_secret(web_req: &WebRequestData) {
+	assert!(!web_req.url.contains(SECRET));
+	for (name, value) in web_req.headers.iter() {
+		assert!(!value.contains(SECRET), "header '{name}' leaks the secret");
+	}
+}
+
+fn chat_req_with_tools_and_image() -> ChatRequest {
+	let user_msg = ChatMessage::user(MessageContent::from_parts(vec![
+		ContentPart::from_text("What is in this image?"),
+		ContentPart::from_binary_base64("image/png", "aGVsbG8=", None),
+	]))
+	.with_options(CacheControl::Ephemeral);
+
+	ChatRequest::new(vec![user_msg])
+		.with_system("Be concise.")
+		.with_tools(vec![Tool::new("get_weather").with_schema(serde_json::json!({
+			"type": "object",
+			"properties": {"city": {"type": "string"}},
+			"required": ["city"]
+		}))])
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_render_chat_request_openai() -> TestResult<()> {
+	let client = Client::default();
+	let options = ChatOptions::default().with_extra_headers(("x-trace-id", "trace-1"));
+
+	let web_req = client
+		.render_chat_request(
+			target(AdapterKind::OpenAI, "https://api.openai.com/v1/", "gpt-4o-mini"),
+			chat_req_with_tools_and_image(),
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(web_req.url, "https://api.openai.com/v1/chat/completions");
+	let headers = headers_map(&web_req);
+	assert_eq!(
+		headers.get("authorization").map(String::as_str),
+		Some("Bearer REDACTED")
+	);
+	assert_eq!(headers.get("x-trace-id").map(String::as_str), Some("trace-1"));
+	assert_no_secret(&web_req);
+
+	assert_eq!(web_req.payload["model"], "gpt-4o-mini");
+	assert_eq!(web_req.payload["stream"], false);
+	assert_eq!(web_req.payload["tools"][0]["function"]["name"], "get_weather");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_render_chat_request_anthropic() -> TestResult<()> {
+	let client = Client::default();
+
+	let web_req = client
+		.render_chat_request(
+			target(
+				AdapterKind::Anthropic,
+				"https://api.anthropic.com/v1/",
+				"claude-sonnet-4-5",
+			),
+			chat_req_with_tools_and_image(),
+			None,
+		)
+		.await?;
+
+	assert_eq!(web_req.url, "https://api.anthropic.com/v1/messages");
+	let headers = headers_map(&web_req);
+	assert_eq!(headers.get("x-api-key").map(String::as_str), Some("REDACTED"));
+	assert_no_secret(&web_req);
+
+	assert_eq!(web_req.payload["tools"][0]["name"], "get_weather");
+	let content = web_req.payload["messages"][0]["content"]
+		.as_array()
+		.ok_or("Should have content parts")?;
+	assert_eq!(content[1]["type"], "image");
+	assert!(
+		content.iter().any(|part| part.get("cache_control").is_some()),
+		"cache_control should be set on the user message"
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_render_chat_request_gemini() -> TestResult<()> {
+	let client = Client::default();
+
+	let web_req = client
+		.render_chat_request(
+			target(
+				AdapterKind::Gemini,
+				"https://generativelanguage.googleapis.com/v1beta/",
+				"gemini-2.5-flash",
+			),
+			chat_req_with_tools_and_image(),
+			None,
+		)
+		.await?;
+
+	assert_eq!(
+		web_req.url,
+		"https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash:generateContent"
+	);
+	let headers = headers_map(&web_req);
+	assert_eq!(headers.get("x-goog-api-key").map(String::as_str), Some("REDACTED"));
+	assert_no_secret(&web_req);
+
+	assert_eq!(
+		web_req.payload["contents"][0]["parts"][1]["inline_data"]["mime_type"],
+		"image/png"
+	);
+	assert_eq!(
+		web_req.payload["tools"][0]["functionDeclarations"][0]["name"],
+		"get_weather"
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_render_embed_request_openai() -> TestResult<()> {
+	let client = Client::default();
+
+	let web_req = client
+		.render_embed_request(
+			target(
+				AdapterKind::OpenAI,
+				"https://api.openai.com/v1/",
+				"text-embedding-3-small",
+			),
+			EmbedRequest::new("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(web_req.url, "https://api.openai.com/v1/embeddings");
+	assert_no_secret(&web_req);
+	assert_eq!(web_req.payload["model"], "text-embedding-3-small");
+	assert_eq!(web_req.payload["input"], "Hello");
+
+	Ok(())
+}
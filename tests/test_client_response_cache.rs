@@ -0,0 +1,170 @@
+//! Response cache tests (InMemoryResponseCache, DiskResponseCache) with an in-memory transport.
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_target, openai_text_body, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::chat::ChatOptions;
+use genai::embed::{EmbedOptions, EmbedRequest};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use genai::{Client, DiskResponseCache, InMemoryResponseCache};
+use reqwest::Method;
+
+// region:    --- Support
+
+fn chat_transport(contents: &[&str]) -> InMemoryTransport {
+	contents.iter().fold(InMemoryTransport::new(), |transport, content| {
+		transport.with_response(
+			Method::POST,
+			format!("{IN_MEMORY_BASE_URL}chat/completions"),
+			InMemoryResponse::json(openai_text_body(content)),
+		)
+	})
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_response_cache_chat_hit_and_miss() -> TestResult<()> {
+	let transport = chat_transport(&["First", "Second", "Third"]);
+	let client = Client::builder()
+		.with_transport(transport.clone())
+		.with_response_cache(InMemoryResponseCache::new(16))
+		.build();
+
+	// -- First call is a miss, second is a hit
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	assert_eq!(res.first_text(), Some("First"));
+	assert!(!res.cache_hit);
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	assert_eq!(res.first_text(), Some("First"));
+	assert!(res.cache_hit);
+	assert_eq!(transport.requests().len(), 1);
+
+	// -- A cache-relevant option changes the key
+	let options = ChatOptions::default().with_temperature(0.2);
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			Some(&options),
+		)
+		.await?;
+	assert_eq!(res.first_text(), Some("Second"));
+	assert!(!res.cache_hit);
+
+	// -- Per-call opt-out
+	let options = ChatOptions::default().with_response_cache(false);
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			Some(&options),
+		)
+		.await?;
+	assert_eq!(res.first_text(), Some("Third"));
+	assert!(!res.cache_hit);
+	assert_eq!(transport.requests().len(), 3);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_response_cache_disk_across_clients() -> TestResult<()> {
+	let cache_dir = std::env::temp_dir().join(format!("genai-response-cache-{}", uuid::Uuid::new_v4()));
+
+	let client = Client::builder()
+		.with_transport(chat_transport(&["Cached on disk"]))
+		.with_response_cache(DiskResponseCache::new(&cache_dir))
+		.build();
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	assert!(!res.cache_hit);
+
+	// New client, with no transport response registered, must be served from disk.
+	let client = Client::builder()
+		.with_transport(InMemoryTransport::new())
+		.with_response_cache(DiskResponseCache::new(&cache_dir))
+		.build();
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+	assert!(res.cache_hit);
+	assert_eq!(res.first_text(), Some("Cached on disk"));
+	assert_eq!(res.usage.prompt_tokens, Some(10));
+
+	std::fs::remove_dir_all(&cache_dir)?;
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_response_cache_embed() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}embeddings"),
+		InMemoryResponse::json(serde_json::json!({
+			"object": "list",
+			"data": [{"object": "embedding", "index": 0, "embedding": [0.1, 0.2]}],
+			"model": "text-embedding-3-small",
+			"usage": {"prompt_tokens": 2, "total_tokens": 2}
+		})),
+	);
+	let client = Client::builder()
+		.with_transport(transport.clone())
+		.with_response_cache(InMemoryResponseCache::new(16))
+		.build();
+
+	let res = client
+		.exec_embed(
+			in_memory_target(AdapterKind::OpenAI, "text-embedding-3-small"),
+			EmbedRequest::new("Hello"),
+			None,
+		)
+		.await?;
+	assert!(!res.cache_hit);
+	let res = client
+		.exec_embed(
+			in_memory_target(AdapterKind::OpenAI, "text-embedding-3-small"),
+			EmbedRequest::new("Hello"),
+			None,
+		)
+		.await?;
+	assert!(res.cache_hit);
+	assert_eq!(res.embeddings[0].vector, vec![0.1, 0.2]);
+	assert_eq!(transport.requests().len(), 1);
+
+	// -- Opt-out goes to the transport (which has no more responses)
+	let options = EmbedOptions::new().with_response_cache(false);
+	let res = client
+		.exec_embed(
+			in_memory_target(AdapterKind::OpenAI, "text-embedding-3-small"),
+			EmbedRequest::new("Hello"),
+			Some(&options),
+		)
+		.await;
+	assert!(res.is_err());
+
+	Ok(())
+}
@@ -0,0 +1,160 @@
+//! Client retry tests (RetryPolicy) against a local wiremock server.
+
+mod support;
+
+use crate::support::{TestResult, base_url_target, openai_text_body, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, ChatStreamEvent};
+use genai::{Client, Error, RetryPolicy, webc};
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+// region:    --- Support
+
+fn fast_retry_policy(max_attempts: u32) -> RetryPolicy {
+	RetryPolicy::new(max_attempts)
+		.with_base_delay(Duration::from_millis(5))
+		.with_jitter(false)
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_retry_chat_429_then_success() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(429).insert_header("retry-after-ms", "10"))
+		.up_to_n_times(2)
+		.expect(2)
+		.mount(&mock_server)
+		.await;
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(200).set_body_json(openai_text_body("Hello after retry")))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::builder().with_retry_policy(fast_retry_policy(3)).build();
+	let res = client
+		.exec_chat(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Hello after retry"));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_chat_gives_up_after_max_attempts() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(529))
+		.expect(2)
+		.mount(&mock_server)
+		.await;
+
+	// Per-call option takes precedence over the client policy
+	let client = Client::builder().with_retry_policy(fast_retry_policy(5)).build();
+	let options = ChatOptions::default().with_retry_policy(fast_retry_policy(2));
+	let err = client
+		.exec_chat(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			Some(&options),
+		)
+		.await
+		.err()
+		.ok_or("Should have failed")?;
+
+	let Error::WebModelCall {
+		webc_error: webc::Error::ResponseFailedStatus { status, .. },
+		..
+	} = err
+	else {
+		return Err(format!("Unexpected error: {err}").into());
+	};
+	assert_eq!(status.as_u16(), 529);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_chat_non_retryable_status_not_retried() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(400))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::builder().with_retry_policy(fast_retry_policy(3)).build();
+	let res = client
+		.exec_chat(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await;
+
+	assert!(res.is_err());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_retry_chat_stream_initial_connect() -> TestResult<()> {
+	let mock_server = MockServer::start().await;
+
+	let sse_body = concat!(
+		"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n",
+		"data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" stream\"},\"finish_reason\":\"stop\"}]}\n\n",
+		"data: [DONE]\n\n"
+	);
+
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(503))
+		.up_to_n_times(1)
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+	Mock::given(method("POST"))
+		.and(path("/v1/chat/completions"))
+		.respond_with(ResponseTemplate::new(200).set_body_raw(sse_body, "text/event-stream"))
+		.expect(1)
+		.mount(&mock_server)
+		.await;
+
+	let client = Client::builder().with_retry_policy(fast_retry_policy(2)).build();
+	let mut stream = client
+		.exec_chat_stream(
+			base_url_target(&mock_server.uri(), AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?
+		.stream;
+
+	let mut content = String::new();
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::Chunk(chunk) = event? {
+			content.push_str(&chunk.content);
+		}
+	}
+	assert_eq!(content, "Hello stream");
+
+	Ok(())
+}
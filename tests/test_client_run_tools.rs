@@ -0,0 +1,159 @@
+//! Tool execution agent loop tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_chat_body, openai_text_body};
+use genai::Client;
+use genai::chat::{ChatMessage, ChatOptions, ChatRequest, ChatRole, Tool, ToolRegistry, ToolRunStop};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+// region:    --- Support
+
+fn tool_calls_body(cities: &[&str]) -> Value {
+	let tool_calls: Vec<Value> = cities
+		.iter()
+		.enumerate()
+		.map(|(idx, city)| {
+			json!({
+				"id": format!("call_{idx}"),
+				"type": "function",
+				"function": {"name": "get_weather", "arguments": json!({"city": city}).to_string()}
+			})
+		})
+		.collect();
+	openai_chat_body(
+		json!({"role": "assistant", "content": null, "tool_calls": tool_calls}),
+		"tool_calls",
+	)
+}
+
+fn weather_registry() -> ToolRegistry {
+	let tool = Tool::new("get_weather").with_schema(json!({
+		"type": "object",
+		"properties": {"city": {"type": "string"}},
+		"required": ["city"]
+	}));
+	ToolRegistry::new().with_tool(tool, |args: Value| async move {
+		Ok(json!({"city": args["city"], "temperature": 21}))
+	})
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_run_tools_loop_until_completed() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(tool_calls_body(&["Paris", "London"])),
+		)
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(openai_chat_body(
+				json!({"role": "assistant", "content": "21C in both."}),
+				"stop",
+			)),
+		);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris and London?")]);
+
+	let res = client
+		.run_tools(in_memory_openai_target(), chat_req, &weather_registry(), None)
+		.await?;
+
+	// -- Check the run
+	assert_eq!(res.stop, ToolRunStop::Completed);
+	assert_eq!(res.iterations, 2);
+	assert_eq!(res.usage.total_tokens, Some(30));
+	assert_eq!(
+		res.chat_response.as_ref().and_then(|res| res.first_text()),
+		Some("21C in both.")
+	);
+
+	// -- Check the transcript (user, assistant tool calls, tool responses, assistant answer)
+	let roles: Vec<&ChatRole> = res.chat_req.messages.iter().map(|msg| &msg.role).collect();
+	assert_eq!(
+		roles,
+		vec![&ChatRole::User, &ChatRole::Assistant, &ChatRole::Tool, &ChatRole::Assistant]
+	);
+
+	// -- Check the second request (registry tool sent, both tool responses)
+	let body = transport.requests()[1].body.clone().ok_or("Should have a body")?;
+	assert_eq!(body["tools"][0]["function"]["name"], "get_weather");
+	let tool_messages: Vec<&Value> = body["messages"]
+		.as_array()
+		.ok_or("Should have messages")?
+		.iter()
+		.filter(|msg| msg["role"] == "tool")
+		.collect();
+	assert_eq!(tool_messages.len(), 2);
+	assert_eq!(tool_messages[0]["tool_call_id"], "call_0");
+	assert_eq!(tool_messages[1]["content"], r#"{"city":"London","temperature":21}"#);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_run_tools_max_iterations_and_invalid_arguments() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let invalid_call = openai_chat_body(
+		json!({"role": "assistant", "content": null, "tool_calls": [{
+			"id": "call_bad",
+			"type": "function",
+			"function": {"name": "get_weather", "arguments": "{\"town\": \"Paris\"}"}
+		}]}),
+		"tool_calls",
+	);
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, InMemoryResponse::json(invalid_call))
+		.with_response(Method::POST, &url, InMemoryResponse::json(tool_calls_body(&["Paris"])));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]);
+	let options = ChatOptions::default().with_max_tool_iterations(2);
+
+	let res = client
+		.run_tools(in_memory_openai_target(), chat_req, &weather_registry(), Some(&options))
+		.await?;
+
+	assert_eq!(res.stop, ToolRunStop::MaxIterations);
+	assert_eq!(res.iterations, 2);
+	// The pending tool calls are in the last response, but not in the transcript.
+	assert_eq!(res.chat_response.as_ref().map(|res| res.tool_calls().len()), Some(1));
+	assert_eq!(res.chat_req.messages.len(), 3);
+
+	// The invalid arguments were sent back to the model as an error.
+	let body = transport.requests()[1].body.clone().ok_or("Should have a body")?;
+	let tool_content = body["messages"][2]["content"].as_str().ok_or("Should be a string")?;
+	assert!(
+		tool_content.contains("missing required property 'city'"),
+		"{tool_content}"
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_run_tools_max_tokens_u32_max() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, InMemoryResponse::json(tool_calls_body(&["Paris"])))
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("21C.")));
+	let client = Client::builder().with_transport(transport).build();
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]);
+	let options = ChatOptions::default().with_max_tool_run_tokens(u32::MAX);
+
+	let res = client
+		.run_tools(in_memory_openai_target(), chat_req, &weather_registry(), Some(&options))
+		.await?;
+
+	// The budget does not wrap to a negative value.
+	assert_eq!(res.stop, ToolRunStop::Completed);
+	assert_eq!(res.iterations, 2);
+
+	Ok(())
+}
@@ -0,0 +1,201 @@
+//! Streaming agent loop tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_event_stream};
+use genai::Client;
+use genai::chat::{AgentEvent, ChatMessage, ChatOptions, ChatRequest, ChatRole, Tool, ToolRegistry, ToolRunStop};
+use genai::webc::InMemoryTransport;
+use reqwest::Method;
+use serde_json::{Value, json};
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+fn delta_event(delta: Value, finish_reason: Option<&str>) -> Value {
+	json!({"choices": [{"index": 0, "delta": delta, "finish_reason": finish_reason}]})
+}
+
+fn tool_call_delta(index: u32, id: Option<&str>, arguments: &str) -> Value {
+	let mut tool_call = json!({"index": index, "function": {"arguments": arguments}});
+	if let Some(id) = id {
+		tool_call["id"] = json!(id);
+		tool_call["type"] = json!("function");
+		tool_call["function"]["name"] = json!("get_weather");
+	}
+	delta_event(json!({"tool_calls": [tool_call]}), None)
+}
+
+fn usage_event() -> Value {
+	json!({"choices": [], "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}})
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_run_tools_stream_events() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			openai_event_stream(vec![
+				delta_event(json!({"content": "Checking."}), None),
+				tool_call_delta(0, Some("call_0"), "{\"city\": "),
+				tool_call_delta(0, None, "\"Paris\"}"),
+				tool_call_delta(1, Some("call_1"), "{\"city\": \"London\"}"),
+				delta_event(json!({}), Some("tool_calls")),
+				usage_event(),
+			]),
+		)
+		.with_response(
+			Method::POST,
+			&url,
+			openai_event_stream(vec![
+				delta_event(json!({"content": "21C in both."}), None),
+				delta_event(json!({}), Some("stop")),
+				usage_event(),
+			]),
+		);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let registry = ToolRegistry::new().with_tool(Tool::new("get_weather"), |args: Value| async move {
+		Ok(json!({"city": args["city"], "temperature": 21}))
+	});
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris and London?")]);
+
+	let mut stream = client
+		.run_tools_stream(in_memory_openai_target(), chat_req, &registry, None)
+		.await?;
+	let mut texts = Vec::new();
+	let mut started = Vec::new();
+	let mut finished = Vec::new();
+	let mut turn_ends = Vec::new();
+	while let Some(event) = stream.next().await {
+		match event? {
+			AgentEvent::Text(text) => texts.push(text),
+			AgentEvent::ToolStarted(tool_call) => started.push(tool_call),
+			AgentEvent::ToolFinished(tool_response) => finished.push(tool_response),
+			AgentEvent::TurnEnd(turn_end) => turn_ends.push(turn_end),
+			AgentEvent::Reasoning(_) | AgentEvent::ToolPaused(_) => (),
+		}
+	}
+
+	// -- Check the events
+	assert_eq!(texts, vec!["Checking.", "21C in both."]);
+	assert_eq!(started.len(), 2);
+	assert_eq!(started[0].call_id, "call_0");
+	assert_eq!(started[0].fn_arguments, json!({"city": "Paris"}));
+	assert_eq!(started[1].fn_arguments, json!({"city": "London"}));
+	assert_eq!(finished.len(), 2);
+	assert_eq!(turn_ends.len(), 2);
+	assert_eq!(turn_ends[0].stop, None);
+	assert_eq!(turn_ends[1].stop, Some(ToolRunStop::Completed));
+
+	// -- Check the transcript
+	let chat_req = turn_ends[1].chat_req.as_ref().ok_or("Should have the transcript")?;
+	let roles: Vec<&ChatRole> = chat_req.messages.iter().map(|msg| &msg.role).collect();
+	assert_eq!(
+		roles,
+		vec![&ChatRole::User, &ChatRole::Assistant, &ChatRole::Tool, &ChatRole::Assistant]
+	);
+
+	// -- Check the second request (both tool responses, in the tool calls order)
+	let body = transport.requests()[1].body.clone().ok_or("Should have a body")?;
+	let tool_call_ids: Vec<&Value> = body["messages"]
+		.as_array()
+		.ok_or("Should have messages")?
+		.iter()
+		.filter(|msg| msg["role"] == "tool")
+		.map(|msg| &msg["tool_call_id"])
+		.collect();
+	assert_eq!(tool_call_ids, vec!["call_0", "call_1"]);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_run_tools_stream_max_iterations() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		openai_event_stream(vec![
+			tool_call_delta(0, Some("call_0"), "{\"city\": \"Paris\"}"),
+			delta_event(json!({}), Some("tool_calls")),
+			usage_event(),
+		]),
+	);
+	let client = Client::builder().with_transport(transport).build();
+	let registry = ToolRegistry::new().with_tool(Tool::new("get_weather"), |args: Value| async move {
+		Ok(json!({"city": args["city"], "temperature": 21}))
+	});
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]);
+	let options = ChatOptions::default().with_max_tool_iterations(1);
+
+	let mut stream = client
+		.run_tools_stream(in_memory_openai_target(), chat_req, &registry, Some(&options))
+		.await?;
+	let mut started = Vec::new();
+	let mut turn_ends = Vec::new();
+	while let Some(event) = stream.next().await {
+		match event? {
+			AgentEvent::ToolStarted(tool_call) => started.push(tool_call),
+			AgentEvent::TurnEnd(turn_end) => turn_ends.push(turn_end),
+			_ => (),
+		}
+	}
+
+	// As with `run_tools`, the tool calls of the last iteration are not run, nor in the transcript.
+	assert!(started.is_empty());
+	assert_eq!(turn_ends.len(), 1);
+	assert_eq!(turn_ends[0].stop, Some(ToolRunStop::MaxIterations));
+	let chat_req = turn_ends[0].chat_req.as_ref().ok_or("Should have the transcript")?;
+	assert_eq!(chat_req.messages.len(), 1);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_run_tools_stream_max_tokens() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		openai_event_stream(vec![
+			tool_call_delta(0, Some("call_0"), "{\"city\": \"Paris\"}"),
+			tool_call_delta(1, Some("call_1"), "{\"city\": \"London\"}"),
+			delta_event(json!({}), Some("tool_calls")),
+			usage_event(),
+		]),
+	);
+	let client = Client::builder().with_transport(transport).build();
+	let registry = ToolRegistry::new().with_tool(Tool::new("get_weather"), |args: Value| async move {
+		Ok(json!({"city": args["city"], "temperature": 21}))
+	});
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris and London?")]);
+	let options = ChatOptions::default().with_max_tool_run_tokens(10);
+
+	let mut stream = client
+		.run_tools_stream(in_memory_openai_target(), chat_req, &registry, Some(&options))
+		.await?;
+	let mut started = Vec::new();
+	let mut turn_ends = Vec::new();
+	while let Some(event) = stream.next().await {
+		match event? {
+			AgentEvent::ToolStarted(tool_call) => started.push(tool_call),
+			AgentEvent::TurnEnd(turn_end) => turn_ends.push(turn_end),
+			_ => (),
+		}
+	}
+
+	// As with `run_tools`, the tool calls of the turn reaching the budget are not run, nor in the transcript.
+	assert!(started.is_empty());
+	assert_eq!(turn_ends.len(), 1);
+	assert_eq!(turn_ends[0].stop, Some(ToolRunStop::MaxTokens));
+	assert_eq!(
+		turn_ends[0].stream_end.captured_tool_calls().map(|calls| calls.len()),
+		Some(2)
+	);
+	let chat_req = turn_ends[0].chat_req.as_ref().ok_or("Should have the transcript")?;
+	assert_eq!(chat_req.messages.len(), 1);
+
+	Ok(())
+}
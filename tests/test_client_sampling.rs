@@ -0,0 +1,148 @@
+//! Extended sampling parameters tests (dry-run request rendering, no network).
+
+mod support;
+
+use crate::support::{TestResult, in_memory_target, render_chat_payload, user_chat_req};
+use genai::Error;
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatOptions, SamplingStrictness};
+
+// region:    --- Support
+
+fn sampling_options() -> ChatOptions {
+	ChatOptions::default()
+		.with_top_k(40)
+		.with_min_p(0.05)
+		.with_frequency_penalty(0.5)
+		.with_presence_penalty(0.25)
+		.with_repetition_penalty(1.1)
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_sampling_openai_penalties_only() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+		user_chat_req("Hello"),
+		Some(&sampling_options()),
+	)
+	.await?;
+
+	assert_eq!(payload["frequency_penalty"], 0.5);
+	assert_eq!(payload["presence_penalty"], 0.25);
+	assert!(payload.get("top_k").is_none());
+	assert!(payload.get("min_p").is_none());
+	assert!(payload.get("repetition_penalty").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_sampling_together_open_model_params() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Together, "meta-llama/Llama-3-8b-chat-hf"),
+		user_chat_req("Hello"),
+		Some(&sampling_options()),
+	)
+	.await?;
+
+	assert_eq!(payload["top_k"], 40);
+	assert_eq!(payload["min_p"], 0.05);
+	assert_eq!(payload["repetition_penalty"], 1.1);
+	assert_eq!(payload["frequency_penalty"], 0.5);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_sampling_anthropic_top_k() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+		user_chat_req("Hello"),
+		Some(&sampling_options()),
+	)
+	.await?;
+
+	assert_eq!(payload["top_k"], 40);
+	assert!(payload.get("frequency_penalty").is_none());
+	assert!(payload.get("min_p").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_sampling_gemini_generation_config() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Gemini, "gemini-2.0-flash"),
+		user_chat_req("Hello"),
+		Some(&sampling_options()),
+	)
+	.await?;
+
+	let generation_config = &payload["generationConfig"];
+	assert_eq!(generation_config["topK"], 40);
+	assert_eq!(generation_config["frequencyPenalty"], 0.5);
+	assert_eq!(generation_config["presencePenalty"], 0.25);
+	assert!(generation_config.get("minP").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_sampling_ollama_options() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Ollama, "llama3"),
+		user_chat_req("Hello"),
+		Some(&sampling_options()),
+	)
+	.await?;
+
+	let options = &payload["options"];
+	assert_eq!(options["top_k"], 40);
+	assert_eq!(options["min_p"], 0.05);
+	assert_eq!(options["repeat_penalty"], 1.1);
+	assert_eq!(options["presence_penalty"], 0.25);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_sampling_cohere_k() -> TestResult<()> {
+	let options = ChatOptions::default().with_top_k(40).with_top_p(0.9);
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Cohere, "command-r"),
+		user_chat_req("Hello"),
+		Some(&options),
+	)
+	.await?;
+
+	assert_eq!(payload["k"], 40);
+	assert_eq!(payload["p"], 0.9);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_sampling_strict_rejects_unsupported() -> TestResult<()> {
+	let options = sampling_options().with_sampling_strictness(SamplingStrictness::Strict);
+
+	let res = render_chat_payload(
+		in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+		user_chat_req("Hello"),
+		Some(&options),
+	)
+	.await;
+	assert!(matches!(res, Err(Error::AdapterNotSupported { .. })));
+
+	// All set parameters are supported by Ollama.
+	render_chat_payload(
+		in_memory_target(AdapterKind::Ollama, "llama3"),
+		user_chat_req("Hello"),
+		Some(&options),
+	)
+	.await?;
+
+	Ok(())
+}
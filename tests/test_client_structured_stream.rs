@@ -0,0 +1,146 @@
+//! Structured output streaming tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_event_stream};
+use genai::Client;
+use genai::chat::{ChatMessage, ChatOptions, ChatRequest, ChatStreamEvent, StructuredStreamEvent, Tool};
+use genai::webc::InMemoryTransport;
+use reqwest::Method;
+use serde_json::{Value, json};
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+fn client_with_events(events: Vec<Value>) -> Client {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		openai_event_stream(events),
+	);
+	Client::builder().with_transport(transport).build()
+}
+
+fn content_event(content: &str) -> Value {
+	json!({"choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}]})
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_structured_stream_partial_objects() -> TestResult<()> {
+	let client = client_with_events(vec![
+		content_event("```json\n{\"name\": \"Ad"),
+		content_event("a\", \"langs\": [\"en\", "),
+		content_event("\"fr\"], \"age\": 3"),
+		content_event("6}\n```"),
+	]);
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Who?")]);
+
+	let stream = client.exec_chat_stream(in_memory_openai_target(), chat_req, None).await?.stream;
+	let mut stream = stream.structured::<Value>();
+	let mut partials = Vec::new();
+	let mut final_value = None;
+	while let Some(event) = stream.next().await {
+		match event? {
+			StructuredStreamEvent::PartialObject(value) => partials.push(value),
+			StructuredStreamEvent::End(end) => final_value = end.value,
+			_ => (),
+		}
+	}
+
+	assert_eq!(
+		partials,
+		vec![
+			json!({"name": "Ad"}),
+			json!({"name": "Ada", "langs": ["en"]}),
+			json!({"name": "Ada", "langs": ["en", "fr"], "age": 3}),
+			json!({"name": "Ada", "langs": ["en", "fr"], "age": 36}),
+		]
+	);
+	assert_eq!(
+		final_value,
+		Some(json!({"name": "Ada", "langs": ["en", "fr"], "age": 36}))
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_structured_stream_tool_call_partial_arguments() -> TestResult<()> {
+	let tool_call_event =
+		|delta: Value| json!({"choices": [{"index": 0, "delta": {"tool_calls": [delta]}, "finish_reason": null}]});
+	let client = client_with_events(vec![
+		tool_call_event(
+			json!({"index": 0, "id": "call_abc", "type": "function", "function": {"name": "get_weather", "arguments": ""}}),
+		),
+		tool_call_event(json!({"index": 0, "function": {"arguments": "{\"city\": \"Par"}})),
+		tool_call_event(json!({"index": 0, "function": {"arguments": "is\", \"unit\": \"C\"}"}})),
+		json!({"choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]}),
+	]);
+	let chat_req =
+		ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]).with_tools(vec![Tool::new("get_weather")]);
+	let options = ChatOptions::default().with_capture_tool_calls(true);
+
+	let stream = client
+		.exec_chat_stream(in_memory_openai_target(), chat_req, Some(&options))
+		.await?
+		.stream;
+	let mut stream = stream.structured::<Value>();
+	let mut partial_calls = Vec::new();
+	let mut captured_tool_calls = None;
+	while let Some(event) = stream.next().await {
+		match event? {
+			StructuredStreamEvent::PartialToolCall(tool_call) => partial_calls.push(tool_call),
+			StructuredStreamEvent::End(end) => captured_tool_calls = end.stream_end.captured_into_tool_calls(),
+			_ => (),
+		}
+	}
+
+	// -- Check the partial tool calls (consistent id and name, parsed arguments)
+	assert_eq!(partial_calls.len(), 3);
+	assert!(
+		partial_calls
+			.iter()
+			.all(|call| call.call_id == "call_abc" && call.fn_name == "get_weather")
+	);
+	assert_eq!(partial_calls[1].fn_arguments, json!({"city": "Par"}));
+	assert_eq!(partial_calls[2].fn_arguments, json!({"city": "Paris", "unit": "C"}));
+
+	// -- Check the captured tool call
+	let captured_tool_calls = captured_tool_calls.ok_or("Should have captured tool calls")?;
+	assert_eq!(
+		captured_tool_calls[0].fn_arguments,
+		json!({"city": "Paris", "unit": "C"})
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_chat_stream_tool_call_chunks_raw_arguments() -> TestResult<()> {
+	let tool_call_event =
+		|delta: Value| json!({"choices": [{"index": 0, "delta": {"tool_calls": [delta]}, "finish_reason": null}]});
+	let client = client_with_events(vec![
+		tool_call_event(
+			json!({"index": 0, "id": "call_abc", "type": "function", "function": {"name": "get_weather", "arguments": "{\"city\""}}),
+		),
+		tool_call_event(json!({"index": 0, "function": {"arguments": ": \"Paris\"}"}})),
+		json!({"choices": [{"index": 0, "delta": {}, "finish_reason": "tool_calls"}]}),
+	]);
+	let chat_req =
+		ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]).with_tools(vec![Tool::new("get_weather")]);
+
+	let mut stream = client.exec_chat_stream(in_memory_openai_target(), chat_req, None).await?.stream;
+	let mut arguments = Vec::new();
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::ToolCallChunk(tool_chunk) = event? {
+			arguments.push(tool_chunk.tool_call.fn_arguments);
+		}
+	}
+
+	// -- Check the chunks keep the raw argument fragments (no partial parsing outside of `StructuredStream`)
+	assert_eq!(arguments, vec![json!("{\"city\""), json!(": \"Paris\"}")]);
+
+	Ok(())
+}
@@ -0,0 +1,208 @@
+//! ChatOptions tool_choice tests, per adapter (dry-run rendering, no network).
+
+mod support;
+
+use crate::support::{TestResult, in_memory_openai_target, in_memory_target, render_chat_payload};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatMessage, ChatOptions, ChatRequest, Tool, ToolChoice};
+use genai::{Client, Error, ServiceTarget};
+use serde_json::{Value, json};
+
+// region:    --- Support
+
+fn chat_req_with_tool() -> ChatRequest {
+	ChatRequest::new(vec![ChatMessage::user("What is the weather in Paris?")]).with_tools(vec![
+		Tool::new("get_weather").with_schema(json!({
+			"type": "object",
+			"properties": {"city": {"type": "string"}},
+			"required": ["city"]
+		})),
+	])
+}
+
+async fn render_payload(target: ServiceTarget, tool_choice: ToolChoice) -> genai::Result<Value> {
+	let options = ChatOptions::default().with_tool_choice(tool_choice);
+	render_chat_payload(target, chat_req_with_tool(), Some(&options)).await
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_tool_choice_openai() -> TestResult<()> {
+	assert_eq!(
+		render_payload(in_memory_openai_target(), ToolChoice::Auto).await?["tool_choice"],
+		"auto"
+	);
+	assert_eq!(
+		render_payload(in_memory_openai_target(), ToolChoice::None).await?["tool_choice"],
+		"none"
+	);
+	assert_eq!(
+		render_payload(in_memory_openai_target(), ToolChoice::Required).await?["tool_choice"],
+		"required"
+	);
+	assert_eq!(
+		render_payload(in_memory_openai_target(), ToolChoice::tool("get_weather")).await?["tool_choice"],
+		json!({"type": "function", "function": {"name": "get_weather"}})
+	);
+
+	// -- No tools, no tool_choice
+	let options = ChatOptions::default().with_tool_choice(ToolChoice::Required);
+	let web_req = Client::default()
+		.render_chat_request(
+			in_memory_openai_target(),
+			ChatRequest::from_user("Hello"),
+			Some(&options),
+		)
+		.await?;
+	assert!(web_req.payload.get("tool_choice").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_openai_resp() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::OpenAIResp, "gpt-5-mini");
+
+	assert_eq!(
+		render_payload(target(), ToolChoice::Required).await?["tool_choice"],
+		"required"
+	);
+	assert_eq!(
+		render_payload(target(), ToolChoice::tool("get_weather")).await?["tool_choice"],
+		json!({"type": "function", "name": "get_weather"})
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_anthropic() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::Anthropic, "claude-haiku-4-5");
+
+	assert_eq!(
+		render_payload(target(), ToolChoice::None).await?["tool_choice"],
+		json!({"type": "none"})
+	);
+	assert_eq!(
+		render_payload(target(), ToolChoice::Required).await?["tool_choice"],
+		json!({"type": "any"})
+	);
+	assert_eq!(
+		render_payload(target(), ToolChoice::tool("get_weather")).await?["tool_choice"],
+		json!({"type": "tool", "name": "get_weather"})
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_gemini() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::Gemini, "gemini-2.5-flash");
+
+	let payload = render_payload(target(), ToolChoice::None).await?;
+	assert_eq!(payload["toolConfig"]["functionCallingConfig"], json!({"mode": "NONE"}));
+	let payload = render_payload(target(), ToolChoice::tool("get_weather")).await?;
+	assert_eq!(
+		payload["toolConfig"]["functionCallingConfig"],
+		json!({"mode": "ANY", "allowedFunctionNames": ["get_weather"]})
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_bedrock() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::Bedrock, "anthropic.claude-3-haiku-20240307-v1:0");
+
+	let payload = render_payload(target(), ToolChoice::Required).await?;
+	assert_eq!(payload["toolConfig"]["toolChoice"], json!({"any": {}}));
+	let payload = render_payload(target(), ToolChoice::tool("get_weather")).await?;
+	assert_eq!(
+		payload["toolConfig"]["toolChoice"],
+		json!({"tool": {"name": "get_weather"}})
+	);
+
+	let res = render_payload(target(), ToolChoice::None).await;
+	assert!(matches!(
+		res,
+		Err(Error::AdapterNotSupported {
+			adapter_kind: AdapterKind::Bedrock,
+			..
+		})
+	));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_ollama() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::Ollama, "gemma3:4b");
+
+	let payload = render_payload(target(), ToolChoice::Auto).await?;
+	assert_eq!(payload["tools"][0]["function"]["name"], "get_weather");
+	// None is honored by not sending the tools
+	let payload = render_payload(target(), ToolChoice::None).await?;
+	assert!(payload.get("tools").is_none());
+
+	let res = render_payload(target(), ToolChoice::Required).await;
+	assert!(matches!(
+		res,
+		Err(Error::AdapterNotSupported {
+			adapter_kind: AdapterKind::Ollama,
+			..
+		})
+	));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_cohere_not_supported() -> TestResult<()> {
+	let target = || in_memory_target(AdapterKind::Cohere, "command-r7b-12-2024");
+
+	render_payload(target(), ToolChoice::Auto).await?;
+	let res = render_payload(target(), ToolChoice::tool("get_weather")).await;
+	assert!(matches!(
+		res,
+		Err(Error::AdapterNotSupported {
+			adapter_kind: AdapterKind::Cohere,
+			..
+		})
+	));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_choice_parallel_tool_calls() -> TestResult<()> {
+	let options = ChatOptions::default().with_parallel_tool_calls(false);
+
+	// -- OpenAI
+	let web_req = Client::default()
+		.render_chat_request(in_memory_openai_target(), chat_req_with_tool(), Some(&options))
+		.await?;
+	assert_eq!(web_req.payload["parallel_tool_calls"], false);
+
+	// -- Anthropic (no tool_choice, so defaults to auto)
+	let anthropic = in_memory_target(AdapterKind::Anthropic, "claude-haiku-4-5");
+	let web_req = Client::default()
+		.render_chat_request(anthropic.clone(), chat_req_with_tool(), Some(&options))
+		.await?;
+	assert_eq!(
+		web_req.payload["tool_choice"],
+		json!({"type": "auto", "disable_parallel_tool_use": true})
+	);
+
+	// -- Anthropic with a forced tool
+	let options = options.with_tool_choice(ToolChoice::tool("get_weather"));
+	let web_req = Client::default()
+		.render_chat_request(anthropic, chat_req_with_tool(), Some(&options))
+		.await?;
+	assert_eq!(
+		web_req.payload["tool_choice"],
+		json!({"type": "tool", "name": "get_weather", "disable_parallel_tool_use": true})
+	);
+
+	Ok(())
+}
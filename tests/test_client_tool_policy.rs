@@ -0,0 +1,292 @@
+//! Tool policy tests for the agent loops (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{
+	IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_chat_body, openai_event_stream, openai_text_body,
+};
+use genai::Client;
+use genai::chat::{
+	AgentEvent, ChatMessage, ChatRequest, ChatRole, Tool, ToolCall, ToolDecision, ToolPolicy, ToolRegistry, ToolRunStop,
+};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use reqwest::Method;
+use serde_json::{Value, json};
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+/// Response with the tool calls (function name, arguments), with the call ids `call_<index>`.
+fn tool_calls_body(calls: &[(&str, Value)]) -> Value {
+	let tool_calls: Vec<Value> = calls
+		.iter()
+		.enumerate()
+		.map(|(idx, (name, args))| {
+			json!({
+				"id": format!("call_{idx}"),
+				"type": "function",
+				"function": {"name": name, "arguments": args.to_string()}
+			})
+		})
+		.collect();
+	openai_chat_body(
+		json!({"role": "assistant", "content": null, "tool_calls": tool_calls}),
+		"tool_calls",
+	)
+}
+
+fn registry() -> ToolRegistry {
+	ToolRegistry::new()
+		.with_tool(Tool::new("get_weather"), |args: Value| async move {
+			Ok(json!({"city": args["city"], "temperature": 21}))
+		})
+		.with_tool(
+			Tool::new("delete_files"),
+			|_args: Value| async move { Ok(json!("deleted")) },
+		)
+}
+
+/// Returns the tool message contents of the request body, by tool call id.
+fn tool_contents(body: &Value) -> Vec<(String, String)> {
+	body["messages"]
+		.as_array()
+		.into_iter()
+		.flatten()
+		.filter(|msg| msg["role"] == "tool")
+		.map(|msg| {
+			let call_id = msg["tool_call_id"].as_str().unwrap_or_default().to_string();
+			let content = msg["content"].as_str().unwrap_or_default().to_string();
+			(call_id, content)
+		})
+		.collect()
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_tool_policy_deny_rewrite_and_max_calls() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(tool_calls_body(&[
+				("get_weather", json!({"city": "Paris"})),
+				("delete_files", json!({})),
+				("get_weather", json!({"city": "London"})),
+				("get_weather", json!({"city": "Rome"})),
+			])),
+		)
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Done.")));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let policy = ToolPolicy::new()
+		.with_denied_tools(["delete_files"])
+		.with_tool_max_calls("get_weather", 2)
+		.with_hook(|tool_call: ToolCall| async move {
+			if tool_call.fn_arguments["city"] == "Paris" {
+				ToolDecision::Rewrite(json!({"city": "Paris, FR"}))
+			} else {
+				ToolDecision::Approve
+			}
+		});
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather?")]);
+
+	let res = client
+		.run_tools(
+			in_memory_openai_target(),
+			chat_req,
+			&registry().with_policy(policy),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.stop, ToolRunStop::Completed);
+	let body = transport.requests()[1].body.clone().ok_or("Should have a body")?;
+	let contents = tool_contents(&body);
+	assert_eq!(contents.len(), 4);
+	assert_eq!(contents[0].1, r#"{"city":"Paris, FR","temperature":21}"#);
+	assert_eq!(
+		contents[1].1,
+		r#"{"error":"tool call denied: tool 'delete_files' is denied"}"#
+	);
+	assert_eq!(contents[2].1, r#"{"city":"London","temperature":21}"#);
+	assert!(
+		contents[3].1.contains("reached its maximum of 2 calls"),
+		"{}",
+		contents[3].1
+	);
+	// The transcript has the rewritten tool call.
+	let arguments = &body["messages"][1]["tool_calls"][0]["function"]["arguments"];
+	assert_eq!(arguments, r#"{"city":"Paris, FR"}"#);
+	let tool_calls = res.chat_req.messages[1].content.tool_calls();
+	assert_eq!(tool_calls[0].fn_arguments, json!({"city": "Paris, FR"}));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_policy_pause_and_resume() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(tool_calls_body(&[
+				("get_weather", json!({"city": "Paris"})),
+				("delete_files", json!({})),
+			])),
+		)
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Done.")));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let pause_deletes = |tool_call: ToolCall| async move {
+		if tool_call.fn_name == "delete_files" {
+			ToolDecision::Pause
+		} else {
+			ToolDecision::Approve
+		}
+	};
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather, then cleanup?")]);
+
+	// -- Run until paused
+	let registry_1 = registry().with_policy(ToolPolicy::new().with_hook(pause_deletes));
+	let res = client.run_tools(in_memory_openai_target(), chat_req, &registry_1, None).await?;
+
+	assert_eq!(res.stop, ToolRunStop::Paused);
+	assert_eq!(res.paused_tool_calls.len(), 1);
+	assert_eq!(res.paused_tool_calls[0].call_id, "call_1");
+	let last_msg = res.chat_req.messages.last().ok_or("Should have messages")?;
+	assert_eq!(last_msg.role, ChatRole::Tool);
+	assert_eq!(last_msg.content.tool_responses().len(), 1);
+
+	// -- Resume from the serialized transcript, with the approval
+	let saved = serde_json::to_string(&res.chat_req).map_err(|err| err.to_string())?;
+	let chat_req: ChatRequest = serde_json::from_str(&saved).map_err(|err| err.to_string())?;
+	let policy = ToolPolicy::new()
+		.with_hook(pause_deletes)
+		.with_call_decision("call_1", ToolDecision::Approve);
+	let res = client
+		.run_tools(
+			in_memory_openai_target(),
+			chat_req,
+			&registry().with_policy(policy),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.stop, ToolRunStop::Completed);
+	assert_eq!(res.iterations, 1);
+	assert_eq!(transport.requests().len(), 2);
+	let body = transport.requests()[1].body.clone().ok_or("Should have a body")?;
+	let tool_messages = tool_contents(&body);
+	assert_eq!(tool_messages.len(), 2);
+	assert_eq!(tool_messages[1], ("call_1".to_string(), "deleted".to_string()));
+	let roles: Vec<&Value> = body["messages"]
+		.as_array()
+		.ok_or("Should have messages")?
+		.iter()
+		.map(|msg| &msg["role"])
+		.collect();
+	assert_eq!(
+		roles,
+		[&json!("user"), &json!("assistant"), &json!("tool"), &json!("tool")]
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_policy_max_calls_across_resume() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let mut resumed_calls_body = tool_calls_body(&[("get_weather", json!({"city": "London"}))]);
+	resumed_calls_body["choices"][0]["message"]["tool_calls"][0]["id"] = json!("call_2");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(tool_calls_body(&[
+				("get_weather", json!({"city": "Paris"})),
+				("delete_files", json!({})),
+			])),
+		)
+		.with_response(Method::POST, &url, InMemoryResponse::json(resumed_calls_body))
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Done.")));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let policy = ToolPolicy::new()
+		.with_tool_max_calls("get_weather", 1)
+		.with_hook(|tool_call: ToolCall| async move {
+			if tool_call.fn_name == "delete_files" {
+				ToolDecision::Pause
+			} else {
+				ToolDecision::Approve
+			}
+		});
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather, then cleanup?")]);
+
+	// -- Run until paused (get_weather called once)
+	let registry_1 = registry().with_policy(policy.clone());
+	let res = client.run_tools(in_memory_openai_target(), chat_req, &registry_1, None).await?;
+	assert_eq!(res.stop, ToolRunStop::Paused);
+
+	// -- Resume, the get_weather calls of the paused run still count
+	let registry = registry().with_policy(policy.with_call_decision("call_1", ToolDecision::Approve));
+	let res = client
+		.run_tools(in_memory_openai_target(), res.chat_req, &registry, None)
+		.await?;
+
+	assert_eq!(res.stop, ToolRunStop::Completed);
+	let body = transport.requests()[2].body.clone().ok_or("Should have a body")?;
+	let contents = tool_contents(&body);
+	assert_eq!(contents.len(), 3);
+	assert_eq!(contents[2].0, "call_2");
+	assert!(
+		contents[2].1.contains("reached its maximum of 1 calls"),
+		"{}",
+		contents[2].1
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_policy_stream_pause() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let tool_call_event = json!({"choices": [{"index": 0, "delta": {"tool_calls": [{
+		"index": 0,
+		"id": "call_0",
+		"type": "function",
+		"function": {"name": "delete_files", "arguments": "{}"}
+	}]}, "finish_reason": "tool_calls"}]});
+	let transport =
+		InMemoryTransport::new().with_response(Method::POST, &url, openai_event_stream(vec![tool_call_event]));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let policy = ToolPolicy::new().with_hook(|_tool_call: ToolCall| async { ToolDecision::Pause });
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Cleanup?")]);
+
+	let mut stream = client
+		.run_tools_stream(
+			in_memory_openai_target(),
+			chat_req,
+			&registry().with_policy(policy),
+			None,
+		)
+		.await?;
+	let mut paused = Vec::new();
+	let mut turn_ends = Vec::new();
+	while let Some(event) = stream.next().await {
+		match event? {
+			AgentEvent::ToolPaused(tool_call) => paused.push(tool_call),
+			AgentEvent::TurnEnd(turn_end) => turn_ends.push(turn_end),
+			_ => (),
+		}
+	}
+
+	assert_eq!(paused.len(), 1);
+	assert_eq!(turn_ends.len(), 1);
+	assert_eq!(turn_ends[0].stop, Some(ToolRunStop::Paused));
+	let chat_req = turn_ends[0].chat_req.as_ref().ok_or("Should have the transcript")?;
+	let last_msg = chat_req.messages.last().ok_or("Should have messages")?;
+	assert_eq!(last_msg.role, ChatRole::Assistant);
+	assert_eq!(last_msg.content.tool_calls().len(), 1);
+
+	Ok(())
+}
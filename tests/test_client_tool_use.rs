@@ -0,0 +1,99 @@
+//! Multi tool call correlation tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_chat_body, openai_text_body};
+use genai::chat::{ChatMessage, ChatRequest, Tool, ToolResponse};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use genai::{Client, Error};
+use reqwest::Method;
+use serde_json::json;
+
+// region:    --- Support
+
+fn weather_chat_req() -> ChatRequest {
+	ChatRequest::new(vec![ChatMessage::user("Weather in Paris and Tokyo?")]).with_tools(vec![Tool::new("get_weather")])
+}
+
+fn tool_call(call_id: &str, city: &str) -> serde_json::Value {
+	json!({
+		"id": call_id,
+		"type": "function",
+		"function": {"name": "get_weather", "arguments": json!({"city": city}).to_string()}
+	})
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_tool_use_pair_and_messages_in_call_order() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(
+			Method::POST,
+			&url,
+			InMemoryResponse::json(openai_chat_body(
+				json!({
+					"role": "assistant",
+					"content": null,
+					"tool_calls": [tool_call("call_a", "Paris"), tool_call("call_b", "Tokyo")]
+				}),
+				"tool_calls",
+			)),
+		)
+		.with_response(Method::POST, &url, InMemoryResponse::json(openai_text_body("Done")));
+	let client = Client::builder().with_transport(transport.clone()).build();
+
+	let res = client.exec_chat(in_memory_openai_target(), weather_chat_req(), None).await?;
+
+	// -- Responses produced out of order are paired in the tool calls order
+	let responses = vec![ToolResponse::new("call_b", "rainy"), ToolResponse::new("call_a", "sunny")];
+	let pairs = res.pair_tool_responses(responses.clone())?;
+	let pairs: Vec<(&str, String)> = pairs
+		.iter()
+		.map(|(call, response)| (call.call_id.as_str(), response.content_text()))
+		.collect();
+	assert_eq!(
+		pairs,
+		vec![("call_a", "sunny".to_string()), ("call_b", "rainy".to_string())]
+	);
+
+	// -- Next turn
+	let chat_req = weather_chat_req().append_messages(res.tool_use_messages(responses)?);
+	client.exec_chat(in_memory_openai_target(), chat_req, None).await?;
+
+	let requests = transport.requests();
+	let messages = &requests[1].body.as_ref().ok_or("Should have a body")?["messages"];
+	assert_eq!(messages[1]["tool_calls"][0]["id"], "call_a");
+	assert_eq!(messages[1]["tool_calls"][1]["id"], "call_b");
+	assert_eq!(messages[2]["tool_call_id"], "call_a");
+	assert_eq!(messages[2]["content"], "sunny");
+	assert_eq!(messages[3]["tool_call_id"], "call_b");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_use_pair_errors() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::json(openai_chat_body(
+			json!({
+				"role": "assistant",
+				"tool_calls": [tool_call("call_a", "Paris"), tool_call("call_b", "Tokyo")]
+			}),
+			"tool_calls",
+		)),
+	);
+	let client = Client::builder().with_transport(transport).build();
+	let res = client.exec_chat(in_memory_openai_target(), weather_chat_req(), None).await?;
+
+	let err = res.pair_tool_responses(vec![ToolResponse::new("call_a", "sunny")]).err();
+	assert!(matches!(err, Some(Error::ToolResponseMissing { call_id, .. }) if call_id == "call_b"));
+
+	let err = res.pair_tool_responses(vec![ToolResponse::new("call_x", "sunny")]).err();
+	assert!(matches!(err, Some(Error::ToolResponseUnmatched { call_id }) if call_id == "call_x"));
+
+	Ok(())
+}
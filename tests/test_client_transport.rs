@@ -0,0 +1,194 @@
+//! Pluggable transport tests (InMemoryTransport and a custom Transport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_target, openai_text_body, user_chat_req};
+use genai::adapter::AdapterKind;
+use genai::chat::ChatStreamEvent;
+use genai::webc::{
+	InMemoryResponse, InMemoryTransport, Transport, TransportFuture, TransportRequest, TransportResponse,
+	TransportStreamResponse,
+};
+use genai::{Client, WebConfig};
+use reqwest::{Method, StatusCode};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio_stream::StreamExt;
+
+// region:    --- Support
+
+/// Counts the calls, and delegates to an inner transport.
+struct CountingTransport {
+	inner: InMemoryTransport,
+	calls: Arc<AtomicUsize>,
+}
+
+impl Transport for CountingTransport {
+	fn post_json(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		self.calls.fetch_add(1, Ordering::SeqCst);
+		self.inner.post_json(request)
+	}
+
+	fn post_stream(&self, request: TransportRequest) -> TransportFuture<'_, TransportStreamResponse> {
+		self.calls.fetch_add(1, Ordering::SeqCst);
+		self.inner.post_stream(request)
+	}
+
+	fn get(&self, request: TransportRequest) -> TransportFuture<'_, TransportResponse> {
+		self.calls.fetch_add(1, Ordering::SeqCst);
+		self.inner.get(request)
+	}
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_transport_in_memory_chat() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		&url,
+		InMemoryResponse::json(openai_text_body("Hello in memory")),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Hello in memory"));
+	let requests = transport.requests();
+	assert_eq!(requests.len(), 1);
+	assert_eq!(requests[0].url, url);
+	let auth = requests[0]
+		.headers
+		.iter()
+		.find(|(k, _)| k.eq_ignore_ascii_case("authorization"));
+	assert_eq!(auth.map(|(_, v)| v.as_str()), Some("Bearer test-key"));
+	let body = requests[0].body.as_ref().ok_or("Should have a body")?;
+	assert_eq!(body["model"], "gpt-4o-mini");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_transport_in_memory_event_stream_split_chunks() -> TestResult<()> {
+	// Events are split across chunks to exercise the stream parsing.
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::event_stream([
+			"data: {\"choices\":[{\"index\":0,\"delta\":{\"con",
+			"tent\":\"Hello\"}}]}\n\ndata: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" chunks\"},",
+			"\"finish_reason\":\"stop\"}]}\n\n",
+			"data: [DONE]\n\n",
+		]),
+	);
+	let client = Client::builder().with_transport(transport).build();
+
+	let mut stream = client
+		.exec_chat_stream(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?
+		.stream;
+
+	let mut content = String::new();
+	while let Some(event) = stream.next().await {
+		if let ChatStreamEvent::Chunk(chunk) = event? {
+			content.push_str(&chunk.content);
+		}
+	}
+	assert_eq!(content, "Hello chunks");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_transport_in_memory_error_status() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::new(
+			StatusCode::UNAUTHORIZED,
+			"application/json",
+			r#"{"error": {"message": "Invalid key", "code": "invalid_api_key"}}"#,
+		),
+	);
+	let client = Client::builder().with_transport(transport).build();
+
+	let err = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await
+		.err()
+		.ok_or("Should have failed")?;
+
+	assert_eq!(err.kind(), genai::ErrorKind::AuthenticationFailed);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_transport_custom_impl() -> TestResult<()> {
+	let calls = Arc::new(AtomicUsize::new(0));
+	let inner = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::json(openai_text_body("Hello custom")),
+	);
+	let client = Client::builder()
+		.with_transport(CountingTransport {
+			inner,
+			calls: calls.clone(),
+		})
+		.build();
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Hello custom"));
+	assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_transport_with_web_config_transport_wins() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::json(openai_text_body("Hello transport")),
+	);
+	let client = Client::builder()
+		.with_web_config(WebConfig::default())
+		.with_transport(transport.clone())
+		.build();
+
+	let res = client
+		.exec_chat(
+			in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+			user_chat_req("Hello"),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.first_text(), Some("Hello transport"));
+	assert_eq!(transport.requests().len(), 1);
+
+	Ok(())
+}
@@ -0,0 +1,185 @@
+//! Typed structured output tests (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_text_body, user_chat_req};
+use genai::chat::{ChatOptions, JsonSpec};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use genai::{Client, Error};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+
+// region:    --- Support
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Color {
+	name: String,
+	hex: String,
+}
+
+fn color_spec() -> JsonSpec {
+	JsonSpec::new(
+		"color",
+		json!({
+			"type": "object",
+			"properties": {"name": {"type": "string"}, "hex": {"type": "string"}},
+			"required": ["name", "hex"],
+			"additionalProperties": false
+		}),
+	)
+}
+
+fn openai_text_response(text: &str) -> InMemoryResponse {
+	InMemoryResponse::json(openai_text_body(text))
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_exec_chat_typed_strips_code_fences() -> TestResult<()> {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		openai_text_response("```json\n{\"name\": \"red\", \"hex\": \"#ff0000\"}\n```"),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+
+	let res = client
+		.exec_chat_typed::<Color>(
+			in_memory_openai_target(),
+			user_chat_req("Give me a color"),
+			color_spec(),
+			None,
+		)
+		.await?;
+
+	assert_eq!(
+		res.value,
+		Color {
+			name: "red".to_string(),
+			hex: "#ff0000".to_string()
+		}
+	);
+	assert_eq!(res.repair_attempts, 0);
+	let body = transport.requests()[0].body.clone().ok_or("should have a body")?;
+	assert_eq!(body["response_format"]["json_schema"]["name"], "color");
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_exec_chat_typed_repairs_invalid_output() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, openai_text_response("{\"name\": \"red\"}"))
+		.with_response(
+			Method::POST,
+			&url,
+			openai_text_response("{\"name\": \"red\", \"hex\": \"#ff0000\"}"),
+		);
+	let client = Client::builder().with_transport(transport.clone()).build();
+
+	let res = client
+		.exec_chat_typed::<Color>(
+			in_memory_openai_target(),
+			user_chat_req("Give me a color"),
+			color_spec(),
+			None,
+		)
+		.await?;
+
+	assert_eq!(res.value.hex, "#ff0000");
+	assert_eq!(res.repair_attempts, 1);
+	let requests = transport.requests();
+	assert_eq!(requests.len(), 2);
+	let messages = requests[1].body.as_ref().ok_or("should have a body")?["messages"].clone();
+	assert_eq!(messages[1]["role"], "assistant");
+	assert_eq!(messages[1]["content"], "{\"name\": \"red\"}");
+	assert_eq!(messages[2]["role"], "user");
+	assert!(
+		messages[2]["content"]
+			.as_str()
+			.unwrap_or_default()
+			.contains("missing required property 'hex'")
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_exec_chat_typed_fails_after_max_repairs() -> TestResult<()> {
+	let url = format!("{IN_MEMORY_BASE_URL}chat/completions");
+	let transport = InMemoryTransport::new()
+		.with_response(Method::POST, &url, openai_text_response("not json"))
+		.with_response(Method::POST, &url, openai_text_response("still not json"))
+		.with_response(Method::POST, &url, openai_text_response("never json"));
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let options = ChatOptions::default().with_max_output_repairs(2);
+
+	let res = client
+		.exec_chat_typed::<Color>(
+			in_memory_openai_target(),
+			user_chat_req("Give me a color"),
+			color_spec(),
+			Some(&options),
+		)
+		.await;
+
+	match res {
+		Err(Error::TypedOutputInvalid {
+			repair_attempts, text, ..
+		}) => {
+			assert_eq!(repair_attempts, 2);
+			assert_eq!(text, "never json");
+		}
+		other => return Err(format!("expected TypedOutputInvalid, got {other:?}").into()),
+	}
+	assert_eq!(transport.requests().len(), 3);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_exec_chat_typed_strict_optional_field() -> TestResult<()> {
+	#[derive(Debug, Deserialize)]
+	struct Labeled {
+		name: String,
+		label: Option<String>,
+	}
+
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		openai_text_response("{\"name\": \"red\", \"label\": null}"),
+	);
+	let client = Client::builder().with_transport(transport.clone()).build();
+	let spec = JsonSpec::new(
+		"labeled",
+		json!({
+			"type": "object",
+			"properties": {"name": {"type": "string"}, "label": {"type": "string"}},
+			"required": ["name"]
+		}),
+	);
+	let options = ChatOptions::default().with_strict_response_format(true);
+
+	let res = client
+		.exec_chat_typed::<Labeled>(
+			in_memory_openai_target(),
+			user_chat_req("Give me a color"),
+			spec,
+			Some(&options),
+		)
+		.await?;
+
+	assert_eq!(res.value.name, "red");
+	assert_eq!(res.value.label, None);
+	assert_eq!(res.repair_attempts, 0);
+	let body = transport.requests()[0].body.clone().ok_or("should have a body")?;
+	let schema = &body["response_format"]["json_schema"]["schema"];
+	assert_eq!(schema["required"], json!(["label", "name"]));
+	assert_eq!(schema["properties"]["label"]["type"], json!(["string", "null"]));
+
+	Ok(())
+}
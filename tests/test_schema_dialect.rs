@@ -0,0 +1,165 @@
+//! Provider schema dialect tests (dry-run request rendering, no network).
+
+mod support;
+
+use crate::support::{TestResult, in_memory_target, render_chat_payload};
+use genai::adapter::AdapterKind;
+use genai::chat::{ChatMessage, ChatOptions, ChatRequest, ChatResponseFormat, JsonSpec, Tool};
+use serde_json::{Value, json};
+
+// region:    --- Support
+
+fn weather_schema() -> Value {
+	json!({
+		"$schema": "https://json-schema.org/draft/2020-12/schema",
+		"type": "object",
+		"properties": {
+			"city": { "type": "string" },
+			"unit": { "type": "string", "enum": ["C", "F"] }
+		},
+		"required": ["city"]
+	})
+}
+
+fn weather_chat_req() -> ChatRequest {
+	ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")])
+		.with_tools(vec![Tool::new("get_weather").with_schema(weather_schema())])
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_schema_dialect_openai_strict_tools() -> TestResult<()> {
+	let target = in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini");
+
+	// -- Default, non-strict, schema as is (without the meta keywords)
+	let payload = render_chat_payload(target.clone(), weather_chat_req(), None).await?;
+	let function = &payload["tools"][0]["function"];
+	assert_eq!(function["strict"], false);
+	assert_eq!(function["parameters"]["required"], json!(["city"]));
+	assert!(function["parameters"].get("$schema").is_none());
+
+	// -- Strict, all required with optional ones nullable
+	let options = ChatOptions::default().with_strict_tools(true);
+	let payload = render_chat_payload(target, weather_chat_req(), Some(&options)).await?;
+	let function = &payload["tools"][0]["function"];
+	assert_eq!(function["strict"], true);
+	assert_eq!(function["parameters"]["additionalProperties"], false);
+	assert_eq!(function["parameters"]["required"], json!(["city", "unit"]));
+	assert_eq!(
+		function["parameters"]["properties"]["unit"]["type"],
+		json!(["string", "null"])
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_dialect_openai_resp_strict_tools() -> TestResult<()> {
+	let options = ChatOptions::default().with_strict_tools(true);
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAIResp, "gpt-5-mini"),
+		weather_chat_req(),
+		Some(&options),
+	)
+	.await?;
+
+	let tool = &payload["tools"][0];
+	assert_eq!(tool["strict"], true);
+	assert_eq!(tool["parameters"]["required"], json!(["city", "unit"]));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_dialect_openai_json_spec_default() -> TestResult<()> {
+	let options = ChatOptions::default()
+		.with_response_format(ChatResponseFormat::JsonSpec(JsonSpec::new("weather", weather_schema())));
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]);
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+		chat_req,
+		Some(&options),
+	)
+	.await?;
+
+	let schema = &payload["response_format"]["json_schema"]["schema"];
+	assert_eq!(payload["response_format"]["json_schema"]["strict"], true);
+	assert_eq!(schema["additionalProperties"], false);
+	assert_eq!(schema["required"], json!(["city"]));
+	assert_eq!(
+		schema["properties"]["unit"],
+		json!({ "type": "string", "enum": ["C", "F"] })
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_dialect_openai_json_spec_strict() -> TestResult<()> {
+	let options = ChatOptions::default()
+		.with_response_format(ChatResponseFormat::JsonSpec(JsonSpec::new("weather", weather_schema())))
+		.with_strict_response_format(true);
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]);
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+		chat_req,
+		Some(&options),
+	)
+	.await?;
+
+	let schema = &payload["response_format"]["json_schema"]["schema"];
+	assert_eq!(payload["response_format"]["json_schema"]["strict"], true);
+	assert_eq!(schema["additionalProperties"], false);
+	assert_eq!(schema["required"], json!(["city", "unit"]));
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_dialect_anthropic_tool_input_object() -> TestResult<()> {
+	let schema = json!({
+		"anyOf": [
+			{ "properties": { "city": { "type": "string" } }, "required": ["city"] },
+			{ "type": "null" }
+		]
+	});
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")])
+		.with_tools(vec![Tool::new("get_weather").with_schema(schema)]);
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+		chat_req,
+		None,
+	)
+	.await?;
+
+	let input_schema = &payload["tools"][0]["input_schema"];
+	assert_eq!(input_schema["type"], "object");
+	assert_eq!(input_schema["required"], json!(["city"]));
+	assert!(input_schema.get("anyOf").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_dialect_ollama_json_spec_format() -> TestResult<()> {
+	let options = ChatOptions::default()
+		.with_response_format(ChatResponseFormat::JsonSpec(JsonSpec::new("weather", weather_schema())));
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]);
+
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Ollama, "gemma3"),
+		chat_req,
+		Some(&options),
+	)
+	.await?;
+
+	assert_eq!(payload["format"]["properties"], weather_schema()["properties"]);
+	assert!(payload["format"].get("$schema").is_none());
+
+	Ok(())
+}
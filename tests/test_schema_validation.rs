@@ -0,0 +1,106 @@
+//! Local JSON Schema validation of tool call arguments and structured outputs (InMemoryTransport, no network).
+
+mod support;
+
+use crate::support::{IN_MEMORY_BASE_URL, TestResult, in_memory_openai_target, openai_chat_body};
+use genai::chat::{ChatMessage, ChatRequest, JsonSpec, Tool};
+use genai::webc::{InMemoryResponse, InMemoryTransport};
+use genai::{Client, Error};
+use reqwest::Method;
+use serde_json::{Value, json};
+
+// region:    --- Support
+
+fn weather_tool() -> Tool {
+	Tool::new("get_weather").with_schema(json!({
+		"type": "object",
+		"properties": {
+			"city": {"type": "string"},
+			"unit": {"type": "string", "enum": ["C", "F"]}
+		},
+		"required": ["city", "unit"]
+	}))
+}
+
+fn client_with_message(message: Value) -> Client {
+	let transport = InMemoryTransport::new().with_response(
+		Method::POST,
+		format!("{IN_MEMORY_BASE_URL}chat/completions"),
+		InMemoryResponse::json(openai_chat_body(message, "stop")),
+	);
+	Client::builder().with_transport(transport).build()
+}
+
+fn violation_paths(err: Error) -> TestResult<Vec<String>> {
+	match err {
+		Error::JsonSchemaViolations { violations, .. } => {
+			Ok(violations.into_iter().map(|violation| violation.path).collect())
+		}
+		other => Err(format!("expected JsonSchemaViolations, got {other:?}").into()),
+	}
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_schema_validation_tool_call_arguments() -> TestResult<()> {
+	let client = client_with_message(json!({
+		"role": "assistant",
+		"content": null,
+		"tool_calls": [{
+			"id": "call_a",
+			"type": "function",
+			"function": {"name": "get_weather", "arguments": "{\"city\": 42, \"unit\": \"K\"}"}
+		}]
+	}));
+	let chat_req = ChatRequest::new(vec![ChatMessage::user("Weather in Paris?")]).with_tools(vec![weather_tool()]);
+
+	let res = client.exec_chat(in_memory_openai_target(), chat_req, None).await?;
+	let tool_call = res.tool_calls().into_iter().next().ok_or("should have a tool call")?;
+	let err = tool_call.validate_against(&weather_tool()).err().ok_or("should be invalid")?;
+
+	assert!(err.to_string().contains("Tool call 'get_weather' arguments"));
+	assert_eq!(violation_paths(err)?, vec!["$.city", "$.unit"]);
+	let other_tool = Tool::new("get_time");
+	assert!(tool_call.validate_against(&other_tool).is_err());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_validation_chat_response_json() -> TestResult<()> {
+	let spec = JsonSpec::new(
+		"city",
+		json!({
+			"type": "object",
+			"properties": {"name": {"type": "string"}, "population": {"type": "integer", "minimum": 0}},
+			"required": ["name", "population"]
+		}),
+	);
+
+	let client = client_with_message(
+		json!({"role": "assistant", "content": "```json\n{\"name\": \"Paris\", \"population\": 2100000}\n```"}),
+	);
+	let res = client
+		.exec_chat(in_memory_openai_target(), ChatRequest::from_user("Paris?"), None)
+		.await?;
+	let value = res.validate_json(&spec)?;
+	assert_eq!(value["name"], "Paris");
+
+	let client =
+		client_with_message(json!({"role": "assistant", "content": "{\"name\": \"Paris\", \"population\": -1}"}));
+	let res = client
+		.exec_chat(in_memory_openai_target(), ChatRequest::from_user("Paris?"), None)
+		.await?;
+	let err = res.validate_json(&spec).err().ok_or("should be invalid")?;
+	assert_eq!(violation_paths(err)?, vec!["$.population"]);
+
+	let client = client_with_message(json!({"role": "assistant", "content": "Paris has 2.1M people"}));
+	let res = client
+		.exec_chat(in_memory_openai_target(), ChatRequest::from_user("Paris?"), None)
+		.await?;
+	let err = res.validate_json(&spec).err().ok_or("should be invalid")?;
+	assert_eq!(violation_paths(err)?, vec!["$"]);
+
+	Ok(())
+}
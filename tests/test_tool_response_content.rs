@@ -0,0 +1,147 @@
+//! Rich tool response content tests (dry-run request rendering, no network).
+
+mod support;
+
+use crate::support::{TestResult, in_memory_target, render_chat_payload};
+use genai::adapter::AdapterKind;
+use genai::chat::{Binary, ChatMessage, ChatRequest, Tool, ToolCall, ToolResponse, ToolResponsePart};
+use serde_json::json;
+
+// region:    --- Support
+
+fn screenshot() -> Binary {
+	Binary::from_base64("image/png", "iVBORw0KGgo=", Some("screen.png".to_string()))
+}
+
+/// Transcript with a screenshot tool response, and a failed tool response.
+fn screenshot_chat_req() -> ChatRequest {
+	let tool_calls = vec![
+		ToolCall {
+			call_id: "call_shot".to_string(),
+			fn_name: "take_screenshot".to_string(),
+			fn_arguments: json!({}),
+			thought_signatures: None,
+		},
+		ToolCall {
+			call_id: "call_fail".to_string(),
+			fn_name: "take_screenshot".to_string(),
+			fn_arguments: json!({"window": "missing"}),
+			thought_signatures: None,
+		},
+	];
+	let tool_responses = vec![
+		ToolResponse::from_json("call_shot", json!({"width": 800})).append(screenshot()),
+		ToolResponse::new("call_fail", "window not found").with_is_error(true),
+	];
+	ChatRequest::new(vec![
+		ChatMessage::user("Take a screenshot"),
+		ChatMessage::from(tool_calls),
+		ChatMessage::from(tool_responses),
+	])
+	.with_tools(vec![Tool::new("take_screenshot")])
+}
+
+// endregion: --- Support
+
+#[tokio::test]
+async fn test_tool_response_content_anthropic_and_bedrock() -> TestResult<()> {
+	// -- Anthropic, tool_result content array, and is_error
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Anthropic, "claude-sonnet-4-5"),
+		screenshot_chat_req(),
+		None,
+	)
+	.await?;
+	let results = &payload["messages"][2]["content"];
+	assert_eq!(results[0]["type"], "tool_result");
+	assert_eq!(
+		results[0]["content"][0],
+		json!({"type": "text", "text": r#"{"width":800}"#})
+	);
+	assert_eq!(results[0]["content"][1]["type"], "image");
+	assert_eq!(results[0]["content"][1]["source"]["data"], "iVBORw0KGgo=");
+	assert!(results[0].get("is_error").is_none());
+	assert_eq!(results[1]["content"], "window not found");
+	assert_eq!(results[1]["is_error"], true);
+
+	// -- Bedrock, toolResult json and image blocks, and error status
+	let target = in_memory_target(AdapterKind::Bedrock, "anthropic.claude-3-5-sonnet-20241022-v2:0");
+	let payload = render_chat_payload(target, screenshot_chat_req(), None).await?;
+	let results = &payload["messages"][2]["content"];
+	let content = &results[0]["toolResult"]["content"];
+	assert_eq!(content[0], json!({"json": {"width": 800}}));
+	assert_eq!(content[1]["image"]["format"], "png");
+	assert!(results[0]["toolResult"].get("status").is_none());
+	assert_eq!(results[1]["toolResult"]["status"], "error");
+	assert_eq!(
+		results[1]["toolResult"]["content"],
+		json!([{"text": "window not found"}])
+	);
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_response_content_gemini() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::Gemini, "gemini-2.5-flash"),
+		screenshot_chat_req(),
+		None,
+	)
+	.await?;
+
+	let parts = &payload["contents"][2]["parts"];
+	let shot_response = &parts[0]["functionResponse"];
+	assert_eq!(shot_response["response"]["content"], json!({"width": 800}));
+	assert_eq!(shot_response["parts"][0]["inline_data"]["mime_type"], "image/png");
+	let fail_response = &parts[1]["functionResponse"];
+	assert_eq!(fail_response["response"]["error"], "window not found");
+	assert!(fail_response["response"].get("content").is_none());
+
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_tool_response_content_openai_follow_up_images() -> TestResult<()> {
+	let payload = render_chat_payload(
+		in_memory_target(AdapterKind::OpenAI, "gpt-4o-mini"),
+		screenshot_chat_req(),
+		None,
+	)
+	.await?;
+
+	let messages = payload["messages"].as_array().ok_or("Should have messages")?;
+	assert_eq!(messages.len(), 5);
+	assert_eq!(messages[2]["role"], "tool");
+	assert_eq!(messages[2]["content"], r#"{"width":800}"#);
+	assert_eq!(messages[3]["content"], "window not found");
+	// The images follow the tool messages, in a user message.
+	assert_eq!(messages[4]["role"], "user");
+	assert_eq!(
+		messages[4]["content"][1]["image_url"]["url"],
+		"data:image/png;base64,iVBORw0KGgo="
+	);
+
+	Ok(())
+}
+
+#[test]
+fn test_tool_response_content_serde() -> TestResult<()> {
+	// -- Former format, content as a string
+	let tool_response: ToolResponse =
+		serde_json::from_value(json!({"call_id": "call_1", "content": "sunny"})).map_err(|err| err.to_string())?;
+	assert_eq!(tool_response.content_text(), "sunny");
+	assert!(!tool_response.is_error);
+
+	// -- Round trip
+	let tool_response = ToolResponse::from_parts("call_1", vec![ToolResponsePart::Json(json!({"a": 1}))])
+		.append(screenshot())
+		.with_is_error(true);
+	let value = serde_json::to_value(&tool_response).map_err(|err| err.to_string())?;
+	let tool_response: ToolResponse = serde_json::from_value(value).map_err(|err| err.to_string())?;
+	assert_eq!(tool_response.json_content(), Some(&json!({"a": 1})));
+	assert_eq!(tool_response.binaries().len(), 1);
+	assert!(tool_response.is_error);
+
+	Ok(())
+}